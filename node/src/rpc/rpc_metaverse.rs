@@ -3,6 +3,8 @@
 use std::collections::BTreeMap;
 use std::sync::Arc;
 
+use continuum_rpc::{Continuum, ContinuumApi};
+use estate_rpc::{Estate, EstateApi};
 use fc_rpc::{
 	EthApi, EthApiServer, EthBlockDataCache, EthFilterApi, EthFilterApiServer, EthPubSubApi, EthPubSubApiServer,
 	HexEncodedIdProvider, NetApi, NetApiServer, OverrideHandle, RuntimeApiStorageOverride, SchemaV1Override,
@@ -114,6 +116,8 @@ where
 		+ pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>
 		+ fp_rpc::ConvertTransactionRuntimeApi<Block>
 		+ fp_rpc::EthereumRuntimeRPCApi<Block>
+		+ estate_rpc::EstateRuntimeApi<Block, AccountId>
+		+ continuum_rpc::ContinuumRuntimeApi<Block, AccountId, Balance, BlockNumber>
 		+ BlockBuilder<Block>,
 	P: TransactionPool<Block = Block> + Sync + Send + 'static,
 	BE: Backend<Block> + 'static,
@@ -145,6 +149,10 @@ where
 		client.clone(),
 	)));
 
+	io.extend_with(EstateApi::to_delegate(Estate::new(client.clone())));
+
+	io.extend_with(ContinuumApi::to_delegate(Continuum::new(client.clone())));
+
 	let max_past_logs: u32 = 10_000;
 	let max_stored_filters: usize = 500;
 	let block_data_cache = Arc::new(EthBlockDataCache::new(50, 50));
@@ -8,7 +8,7 @@
 use std::sync::Arc;
 
 use pioneer_runtime::{opaque::Block, AccountId, Index};
-use primitives::Balance;
+use primitives::{Balance, BlockNumber};
 pub use sc_rpc_api::DenyUnsafe;
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
@@ -33,9 +33,13 @@ where
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: estate_rpc::EstateRuntimeApi<Block, AccountId>,
+	C::Api: continuum_rpc::ContinuumRuntimeApi<Block, AccountId, Balance, BlockNumber>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
 {
+	use continuum_rpc::{Continuum, ContinuumApi};
+	use estate_rpc::{Estate, EstateApi};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
 
@@ -56,6 +60,10 @@ where
 		client.clone(),
 	)));
 
+	io.extend_with(EstateApi::to_delegate(Estate::new(client.clone())));
+
+	io.extend_with(ContinuumApi::to_delegate(Continuum::new(client.clone())));
+
 	// Extend this RPC with a custom API by using the following syntax.
 	// `YourRpcStruct` should have a reference to a client, which is needed
 	// to call into the runtime.
@@ -0,0 +1,47 @@
+// This file is part of Bit.Country.
+
+// Copyright (C) 2020-2021 Bit.Country.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for the auction pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+use auction_manager::SaleRecord;
+use primitives::{ClassId, ItemId, RoundIndex};
+
+sp_api::decl_runtime_apis! {
+	pub trait AuctionApi<AccountId, Balance, BlockNumber> where
+		AccountId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+	{
+		/// Most recent sales of an item, oldest first
+		fn item_sales_history(item_id: ItemId) -> Vec<SaleRecord<AccountId, Balance, BlockNumber>>;
+		/// Most recent sales of tokens in a collection, oldest first
+		fn class_sales_history(class_id: ClassId) -> Vec<SaleRecord<AccountId, Balance, BlockNumber>>;
+		/// Price of the last sale of an item
+		fn last_sale_price(item_id: ItemId) -> Option<Balance>;
+		/// Price of the last sale of any token in a collection
+		fn class_last_sale_price(class_id: ClassId) -> Option<Balance>;
+		/// Sales volume of a collection during a round
+		fn class_volume(class_id: ClassId, round: RoundIndex) -> Balance;
+	}
+}
@@ -29,7 +29,7 @@ use sp_std::{collections::btree_map::BTreeMap, prelude::*, vec};
 
 // use orml_traits::BasicCurrencyExtended;
 use auction_manager::{CheckAuctionItemHandler, ListingLevel};
-use core_primitives::{MetaverseInfo, MetaverseTrait};
+use core_primitives::{MetaverseInfo, MetaverseRole, MetaverseTrait};
 use pallet_nft::{Attributes, CollectionType, TokenType};
 // use pallet_estate::Pallet as EstateModule;
 // use pallet_metaverse::Pallet as MetaverseModule;
@@ -112,6 +112,10 @@ impl MetaverseTrait<AccountId> for MetaverseInfoSource {
 		}
 	}
 
+	fn check_role(who: &AccountId, metaverse_id: &MetaverseId, _role: &MetaverseRole) -> bool {
+		Self::check_ownership(who, metaverse_id)
+	}
+
 	fn get_metaverse(metaverse_id: u64) -> Option<MetaverseInfo<u128>> {
 		None
 	}
@@ -123,6 +127,10 @@ impl MetaverseTrait<AccountId> for MetaverseInfoSource {
 	fn update_metaverse_token(metaverse_id: u64, currency_id: FungibleTokenId) -> Result<(), DispatchError> {
 		Ok(())
 	}
+
+	fn transfer_metaverse(from: &AccountId, to: &AccountId, metaverse_id: u64) -> Result<(), DispatchError> {
+		Ok(())
+	}
 }
 
 benchmarks! {
@@ -163,6 +171,20 @@ benchmarks! {
 
 		crate::Pallet::<T>::create_new_buy_now(RawOrigin::Signed(caller.clone()).into(), ItemId::NFT(0), 100u32.into(), 100u32.into(), ListingLevel::Global);
 	}: _(RawOrigin::Signed(bidder.clone()), 0u32.into(), 100u32.into())
+
+	// finalize_auction, settling the bid of an NFT auction
+	finalize_auction{
+		frame_system::Pallet::<T>::set_block_number(1u32.into());
+
+		let caller = funded_account::<T>("caller", 0);
+		let bidder = funded_account::<T>("bidder", 0);
+		mint_NFT::<T>(caller.clone());
+
+		crate::Pallet::<T>::create_new_auction(RawOrigin::Signed(caller.clone()).into(), ItemId::NFT(0), 100u32.into(), 100u32.into(), ListingLevel::Global);
+		crate::Pallet::<T>::bid(RawOrigin::Signed(bidder.clone()).into(), 0u32.into(), 100u32.into());
+	}: {
+		crate::Pallet::<T>::finalize_auction(0u32.into());
+	}
 }
 
 impl_benchmark_test_suite!(Pallet, crate::benchmarking::tests::new_test_ext(), crate::mock::Test);
@@ -31,14 +31,22 @@ use frame_system::{self as system, ensure_signed};
 use sp_core::sp_std::convert::TryInto;
 use sp_runtime::SaturatedConversion;
 use sp_runtime::{
-	traits::{CheckedDiv, One, Saturating, Zero},
+	traits::{CheckedDiv, CheckedMul, One, Saturating, Zero},
 	DispatchError, DispatchResult,
 };
+use sp_std::vec::Vec;
 
-use auction_manager::{Auction, AuctionHandler, AuctionInfo, AuctionItem, AuctionType, Change, OnNewBidResult};
+use auction_manager::{
+	Auction, AuctionHandler, AuctionInfo, AuctionItem, AuctionType, Change, CollectionOffer, OnNewBidResult,
+	SaleRecord, SalesHistory,
+};
 pub use pallet::*;
 use pallet_nft::Pallet as NFTModule;
-use primitives::{continuum::Continuum, estate::Estate, AuctionId, ItemId};
+use primitives::{
+	continuum::Continuum,
+	estate::{Estate, LandAsset},
+	AuctionId, ItemId,
+};
 pub use weights::WeightInfo;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -106,8 +114,8 @@ pub mod pallet {
 	use orml_traits::{MultiCurrency, MultiReservableCurrency};
 
 	use auction_manager::{CheckAuctionItemHandler, ListingLevel};
-	use core_primitives::{MetaverseTrait, NFTTrait};
-	use primitives::{AssetId, Balance, ClassId, FungibleTokenId, MetaverseId, TokenId};
+	use core_primitives::{MetaverseRole, MetaverseTrait, NFTTrait, RoundTrait, UndeployedLandBlocksTrait};
+	use primitives::{AssetId, Balance, ClassId, FungibleTokenId, MetaverseId, RoundIndex, TokenId};
 
 	use crate::migration_v2::V1ItemId;
 
@@ -144,13 +152,26 @@ pub mod pallet {
 		type MinimumAuctionDuration: Get<Self::BlockNumber>;
 		/// Handle Estate logic
 		type EstateHandler: Estate<Self::AccountId>;
+		/// Undeployed land block handler
+		type UndeployedLandBlockHandler: UndeployedLandBlocksTrait<Self::AccountId>;
 		/// Loyalty fee in percentage applied NFT promotion
 		#[pallet::constant]
 		type RoyaltyFee: Get<u16>;
+		/// Maximum auctions finalized at the start of a block, the rest are carried over
 		#[pallet::constant]
 		type MaxFinality: Get<u32>;
+		/// Number of settlement attempts before a failed auction is refunded and its item released
+		#[pallet::constant]
+		type MaxSettlementAttempts: Get<u32>;
+		/// Number of sales kept in the history of each item and collection
+		#[pallet::constant]
+		type MaxSalesHistory: Get<u32>;
+		/// Round info used to aggregate sales volume
+		type RoundHandler: RoundTrait<Self::BlockNumber>;
 		/// NFT Handler
 		type NFTHandler: NFTTrait<Self::AccountId, ClassId = ClassId, TokenId = TokenId>;
+		/// Weight information for the extrinsics and hooks in this pallet
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::storage]
@@ -187,6 +208,52 @@ pub mod pallet {
 	pub(super) type MetaverseAuthorizedCollection<T: Config> =
 		StorageMap<_, Twox64Concat, (MetaverseId, ClassId), (), OptionQuery>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn failed_settlements)]
+	/// Ended auctions whose settlement failed, with the number of attempts made so far
+	pub(super) type FailedSettlements<T: Config> = StorageMap<_, Twox64Concat, AuctionId, u32, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn finalization_cursor)]
+	/// Earliest end block that may still have auctions waiting to be finalized. Auctions over
+	/// the per block cap stay in `AuctionEndTime` and are carried over from here.
+	pub(super) type FinalizationCursor<T: Config> = StorageValue<_, T::BlockNumber, OptionQuery>;
+
+	/// Last `MaxSalesHistory` sales, oldest first
+	pub(super) type SalesHistoryOf<T> = BoundedVec<
+		SaleRecord<<T as frame_system::Config>::AccountId, BalanceOf<T>, <T as frame_system::Config>::BlockNumber>,
+		<T as Config>::MaxSalesHistory,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn item_sales_history)]
+	/// Most recent sales of each item, oldest first
+	pub(super) type ItemSalesHistory<T: Config> = StorageMap<_, Twox64Concat, ItemId, SalesHistoryOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn class_sales_history)]
+	/// Most recent sales of tokens in each collection, oldest first
+	pub(super) type ClassSalesHistory<T: Config> = StorageMap<_, Twox64Concat, ClassId, SalesHistoryOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn class_sales_volume)]
+	/// Sales volume of each collection per round
+	pub(super) type ClassSalesVolume<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, ClassId, Twox64Concat, RoundIndex, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn collection_offers)]
+	/// Escrowed floor offers by collection and offerer
+	pub(super) type CollectionOffers<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		ClassId,
+		Twox64Concat,
+		T::AccountId,
+		CollectionOffer<BalanceOf<T>>,
+		OptionQuery,
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub (crate) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -205,6 +272,16 @@ pub mod pallet {
 		AuctionFinalizedNoBid(AuctionId),
 		CollectionAuthorizedInMetaverse(ClassId, MetaverseId),
 		CollectionAuthorizationRemoveInMetaverse(ClassId, MetaverseId),
+		/// Settlement failed and was queued for retry. [auction_id, attempts]
+		AuctionSettlementFailed(AuctionId, u32),
+		/// Settlement gave up, bid refunded and item released. [auction_id, bidder, bid_amount]
+		AuctionSettlementAbandoned(AuctionId, T::AccountId, BalanceOf<T>),
+		/// New collection offer. [class_id, offerer, price_per_token, quantity]
+		CollectionOfferCreated(ClassId, T::AccountId, BalanceOf<T>, u32),
+		/// Collection offer cancelled and escrow released. [class_id, offerer]
+		CollectionOfferCancelled(ClassId, T::AccountId),
+		/// Collection offer filled for one token. [class_id, token_id, seller, offerer, price]
+		CollectionOfferAccepted(ClassId, TokenId, T::AccountId, T::AccountId, BalanceOf<T>),
 	}
 
 	/// Errors inform users that something went wrong.
@@ -238,12 +315,34 @@ pub mod pallet {
 		Overflow,
 		EstateDoesNotExist,
 		LandUnitDoesNotExist,
+		/// Estate is leased out
+		EstateIsLeased,
+		/// Land unit is leased out
+		LandUnitIsLeased,
+		/// Estate is locked as loan collateral
+		EstateIsCollateralized,
+		/// Land unit is locked as loan collateral
+		LandUnitIsCollateralized,
 		/// User has no permission to authorise collection
 		NoPermissionToAuthoriseCollection,
 		/// Collection has already authorised
 		CollectionAlreadyAuthorised,
 		/// Collection is not authorised
 		CollectionIsNotAuthorised,
+		/// Auction is not waiting for settlement retry
+		AuctionSettlementNotFailed,
+		/// Offerer already has an offer on this collection
+		CollectionOfferAlreadyExists,
+		/// Collection offer does not exist
+		CollectionOfferNotExist,
+		/// Offer price or quantity is zero
+		InvalidCollectionOffer,
+		/// Cannot fill your own collection offer
+		CannotAcceptOwnOffer,
+		/// Only the token owner can fill a collection offer
+		NoPermissionToAcceptOffer,
+		/// Collection offers can only be made on existing classes
+		ClassDoesNotExist,
 	}
 
 	#[pallet::call]
@@ -302,6 +401,7 @@ pub mod pallet {
 
 		/// User can buy now on listing
 		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		#[transactional]
 		pub fn buy_now(origin: OriginFor<T>, auction_id: AuctionId, value: BalanceOf<T>) -> DispatchResultWithPostInfo {
 			let from = ensure_signed(origin)?;
 
@@ -328,78 +428,12 @@ pub mod pallet {
 				Error::<T>::InsufficientFunds
 			);
 
+			// Transfer balance and asset, the whole call reverts if either fails
+			Self::transfer_auction_item(&auction_item, &from, value)?;
 			Self::remove_auction(auction_id.clone(), auction_item.item_id);
+			Self::record_sale(auction_item.item_id, &auction_item.recipient, &from, value);
 
-			// Transfer balance from buy it now user to asset owner
-			let currency_transfer = <T as Config>::Currency::transfer(
-				&from,
-				&auction_item.recipient,
-				value,
-				ExistenceRequirement::KeepAlive,
-			);
-			match currency_transfer {
-				Err(_e) => {}
-				Ok(_v) => {
-					// Transfer asset from asset owner to buy it now user
-					<ItemsInAuction<T>>::remove(auction_item.item_id);
-					match auction_item.item_id {
-						ItemId::NFT(class_id, token_id) => {
-							Self::collect_royalty_fee(
-								&value,
-								&auction_item.recipient,
-								&(class_id, token_id),
-								FungibleTokenId::NativeToken(0),
-							);
-
-							let asset_transfer =
-								T::NFTHandler::transfer_nft(&auction_item.recipient, &from, &(class_id, token_id));
-							match asset_transfer {
-								Err(_) => (),
-								Ok(_) => {
-									Self::deposit_event(Event::BuyNowFinalised(auction_id, from, value));
-								}
-							}
-						}
-						ItemId::Spot(spot_id, metaverse_id) => {
-							let continuum_spot = T::ContinuumHandler::transfer_spot(
-								spot_id,
-								&auction_item.recipient,
-								&(from.clone(), metaverse_id),
-							);
-							match continuum_spot {
-								Err(_) => (),
-								Ok(_) => {
-									Self::deposit_event(Event::BuyNowFinalised(auction_id, from, value));
-								}
-							}
-						}
-						ItemId::Estate(estate_id) => {
-							let estate =
-								T::EstateHandler::transfer_estate(estate_id, &auction_item.recipient, &from.clone());
-							match estate {
-								Err(_) => (),
-								Ok(_) => {
-									Self::deposit_event(Event::BuyNowFinalised(auction_id, from, value));
-								}
-							}
-						}
-						ItemId::LandUnit(coordinate, metaverse_id) => {
-							let land_unit = T::EstateHandler::transfer_landunit(
-								coordinate,
-								&auction_item.recipient,
-								&(from.clone(), metaverse_id),
-							);
-							match land_unit {
-								Err(_) => (),
-								Ok(_) => {
-									Self::deposit_event(Event::BuyNowFinalised(auction_id, from, value));
-								}
-							}
-						}
-						_ => {} // Future implementation for Land, Metaverse
-					}
-				}
-			}
+			Self::deposit_event(Event::BuyNowFinalised(auction_id, from, value));
 			Ok(().into())
 		}
 
@@ -414,14 +448,14 @@ pub mod pallet {
 			let from = ensure_signed(origin)?;
 
 			ensure!(
-				matches!(item_id, ItemId::NFT(_, _)),
+				matches!(item_id, ItemId::NFT(_, _) | ItemId::Block(_) | ItemId::Country(_)),
 				Error::<T>::NoPermissionToCreateAuction
 			);
 
 			match listing_level {
 				ListingLevel::Local(metaverse_id) => {
 					ensure!(
-						T::MetaverseInfoSource::check_ownership(&from, &metaverse_id),
+						T::MetaverseInfoSource::check_role(&from, &metaverse_id, &MetaverseRole::Admin),
 						Error::<T>::NoPermissionToCreateAuction
 					);
 				}
@@ -459,14 +493,14 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			let from = ensure_signed(origin)?;
 			ensure!(
-				matches!(item_id, ItemId::NFT(_, _)),
+				matches!(item_id, ItemId::NFT(_, _) | ItemId::Block(_) | ItemId::Country(_)),
 				Error::<T>::NoPermissionToCreateAuction
 			);
 
 			match listing_level {
 				ListingLevel::Local(metaverse_id) => {
 					ensure!(
-						T::MetaverseInfoSource::check_ownership(&from, &metaverse_id),
+						T::MetaverseInfoSource::check_role(&from, &metaverse_id, &MetaverseRole::Admin),
 						Error::<T>::NoPermissionToCreateAuction
 					);
 				}
@@ -502,7 +536,7 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			let from = ensure_signed(origin)?;
 			ensure!(
-				T::MetaverseInfoSource::check_ownership(&from, &metaverse_id),
+				T::MetaverseInfoSource::check_role(&from, &metaverse_id, &MetaverseRole::Admin),
 				Error::<T>::NoPermissionToAuthoriseCollection
 			);
 
@@ -526,7 +560,7 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			let from = ensure_signed(origin)?;
 			ensure!(
-				T::MetaverseInfoSource::check_ownership(&from, &metaverse_id),
+				T::MetaverseInfoSource::check_role(&from, &metaverse_id, &MetaverseRole::Admin),
 				Error::<T>::NoPermissionToAuthoriseCollection
 			);
 
@@ -542,128 +576,173 @@ pub mod pallet {
 			));
 			Ok(().into())
 		}
+
+		/// Anyone can retry the settlement of an auction that failed to finalize. Once
+		/// `MaxSettlementAttempts` is reached the bid is refunded and the item released.
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(3))]
+		pub fn retry_auction_settlement(origin: OriginFor<T>, auction_id: AuctionId) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			let attempts = Self::failed_settlements(auction_id).ok_or(Error::<T>::AuctionSettlementNotFailed)?;
+			let auction = Self::auctions(auction_id).ok_or(Error::<T>::AuctionNotExist)?;
+			let auction_item = Self::get_auction_item(auction_id).ok_or(Error::<T>::AuctionNotExist)?;
+			let (high_bidder, high_bid_price) = auction.bid.ok_or(Error::<T>::AuctionNotExist)?;
+
+			match Self::settle_auction(auction_id, &auction_item, &high_bidder, high_bid_price) {
+				Ok(_) => {
+					FailedSettlements::<T>::remove(auction_id);
+					Self::deposit_event(Event::AuctionFinalized(auction_id, high_bidder, high_bid_price));
+				}
+				Err(_) => {
+					let attempts = attempts.saturating_add(1);
+					if attempts >= T::MaxSettlementAttempts::get() {
+						<T as Config>::Currency::unreserve(&high_bidder, high_bid_price);
+						Self::remove_auction(auction_id, auction_item.item_id);
+						FailedSettlements::<T>::remove(auction_id);
+						Self::deposit_event(Event::AuctionSettlementAbandoned(
+							auction_id,
+							high_bidder,
+							high_bid_price,
+						));
+					} else {
+						FailedSettlements::<T>::insert(auction_id, attempts);
+						Self::deposit_event(Event::AuctionSettlementFailed(auction_id, attempts));
+					}
+				}
+			}
+
+			Ok(().into())
+		}
+
+		/// Place an escrowed offer for `quantity` tokens of any kind in `class_id`
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 1))]
+		pub fn make_collection_offer(
+			origin: OriginFor<T>,
+			class_id: ClassId,
+			price_per_token: BalanceOf<T>,
+			quantity: u32,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+
+			ensure!(
+				!price_per_token.is_zero() && quantity > 0,
+				Error::<T>::InvalidCollectionOffer
+			);
+			ensure!(
+				T::NFTHandler::check_class_exists(&class_id),
+				Error::<T>::ClassDoesNotExist
+			);
+			ensure!(
+				!CollectionOffers::<T>::contains_key(class_id, &from),
+				Error::<T>::CollectionOfferAlreadyExists
+			);
+
+			let total = price_per_token
+				.checked_mul(&quantity.into())
+				.ok_or(Error::<T>::Overflow)?;
+			<T as Config>::Currency::reserve(&from, total).map_err(|_| Error::<T>::InsufficientFreeBalance)?;
+
+			CollectionOffers::<T>::insert(
+				class_id,
+				&from,
+				CollectionOffer {
+					price_per_token,
+					quantity,
+				},
+			);
+			Self::deposit_event(Event::<T>::CollectionOfferCreated(
+				class_id,
+				from,
+				price_per_token,
+				quantity,
+			));
+
+			Ok(().into())
+		}
+
+		/// Withdraw a collection offer and release what is left in escrow
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		pub fn cancel_collection_offer(origin: OriginFor<T>, class_id: ClassId) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+
+			let offer = CollectionOffers::<T>::take(class_id, &from).ok_or(Error::<T>::CollectionOfferNotExist)?;
+			<T as Config>::Currency::unreserve(&from, offer.price_per_token.saturating_mul(offer.quantity.into()));
+
+			Self::deposit_event(Event::<T>::CollectionOfferCancelled(class_id, from));
+
+			Ok(().into())
+		}
+
+		/// Token holder fills one unit of a collection offer
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(2))]
+		#[transactional]
+		pub fn accept_collection_offer(
+			origin: OriginFor<T>,
+			class_id: ClassId,
+			token_id: TokenId,
+			offerer: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+
+			ensure!(from != offerer, Error::<T>::CannotAcceptOwnOffer);
+			ensure!(
+				T::NFTHandler::check_ownership(&from, &(class_id, token_id))?,
+				Error::<T>::NoPermissionToAcceptOffer
+			);
+			ensure!(
+				Self::items_in_auction(ItemId::NFT(class_id, token_id)) == None,
+				Error::<T>::ItemAlreadyInAuction
+			);
+
+			let price = CollectionOffers::<T>::try_mutate_exists(
+				class_id,
+				&offerer,
+				|maybe_offer| -> Result<BalanceOf<T>, DispatchError> {
+					let offer = maybe_offer.as_mut().ok_or(Error::<T>::CollectionOfferNotExist)?;
+					let price = offer.price_per_token;
+					offer.quantity = offer.quantity.saturating_sub(1);
+					if offer.quantity == 0 {
+						*maybe_offer = None;
+					}
+					Ok(price)
+				},
+			)?;
+
+			<T as Config>::Currency::unreserve(&offerer, price);
+			<T as Config>::Currency::transfer(&offerer, &from, price, ExistenceRequirement::KeepAlive)?;
+			Self::collect_royalty_fee(&price, &from, &(class_id, token_id), FungibleTokenId::NativeToken(0))?;
+			T::NFTHandler::transfer_nft(&from, &offerer, &(class_id, token_id))?;
+			Self::record_sale(ItemId::NFT(class_id, token_id), &from, &offerer, price);
+
+			Self::deposit_event(Event::<T>::CollectionOfferAccepted(
+				class_id, token_id, from, offerer, price,
+			));
+
+			Ok(().into())
+		}
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
-		fn on_finalize(now: T::BlockNumber) {
-			let max_finality = T::MaxFinality::get();
-			let mut proceeded_item: u32 = 0;
-			for (auction_id, _) in <AuctionEndTime<T>>::drain_prefix(&now) {
-				if proceeded_item == max_finality {
-					break;
-				};
-				if let Some(auction) = <Auctions<T>>::get(&auction_id) {
-					if let Some(auction_item) = <AuctionItems<T>>::get(&auction_id) {
-						proceeded_item.checked_add(One::one()).ok_or("Overflow");
-						Self::remove_auction(auction_id.clone(), auction_item.item_id);
-						// Transfer balance from high bidder to asset owner
-						if let Some(current_bid) = auction.bid {
-							let (high_bidder, high_bid_price): (T::AccountId, BalanceOf<T>) = current_bid;
-							// Handle listing
-							<T as Config>::Currency::unreserve(&high_bidder, high_bid_price);
-
-							// Handle balance transfer
-							let currency_transfer = <T as Config>::Currency::transfer(
-								&high_bidder,
-								&auction_item.recipient,
-								high_bid_price,
-								ExistenceRequirement::KeepAlive,
-							);
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			Self::process_ended_auctions(now, T::MaxFinality::get())
+		}
 
-							match currency_transfer {
-								Err(_e) => continue,
-								Ok(_v) => {
-									// Transfer asset from asset owner to high bidder
-									// Check asset type and handle internal logic
-
-									match auction_item.item_id {
-										ItemId::NFT(class_id, token_id) => {
-											Self::collect_royalty_fee(
-												&high_bid_price,
-												&auction_item.recipient,
-												&(class_id, token_id),
-												FungibleTokenId::NativeToken(0),
-											);
-											let asset_transfer = T::NFTHandler::transfer_nft(
-												&auction_item.recipient,
-												&high_bidder,
-												&(class_id, token_id),
-											);
-
-											match asset_transfer {
-												Err(_) => continue,
-												Ok(_) => {
-													Self::deposit_event(Event::AuctionFinalized(
-														auction_id,
-														high_bidder,
-														high_bid_price,
-													));
-												}
-											}
-										}
-										ItemId::Spot(spot_id, metaverse_id) => {
-											let continuum_spot = T::ContinuumHandler::transfer_spot(
-												spot_id,
-												&auction_item.recipient,
-												&(high_bidder.clone(), metaverse_id),
-											);
-											match continuum_spot {
-												Err(_) => continue,
-												Ok(_) => {
-													Self::deposit_event(Event::AuctionFinalized(
-														auction_id,
-														high_bidder,
-														high_bid_price,
-													));
-												}
-											}
-										}
-										ItemId::Estate(estate_id) => {
-											let estate = T::EstateHandler::transfer_estate(
-												estate_id,
-												&auction_item.recipient,
-												&high_bidder.clone(),
-											);
-											match estate {
-												Err(_) => (),
-												Ok(_) => {
-													Self::deposit_event(Event::AuctionFinalized(
-														auction_id,
-														high_bidder,
-														high_bid_price,
-													));
-												}
-											}
-										}
-										ItemId::LandUnit(coordinate, metaverse_id) => {
-											let land_unit = T::EstateHandler::transfer_landunit(
-												coordinate,
-												&auction_item.recipient,
-												&(high_bidder.clone(), metaverse_id),
-											);
-											match land_unit {
-												Err(_) => (),
-												Ok(_) => {
-													Self::deposit_event(Event::AuctionFinalized(
-														auction_id,
-														high_bidder,
-														high_bid_price,
-													));
-												}
-											}
-										}
-										_ => {} // Future implementation for Spot, Metaverse
-									}
-									<ItemsInAuction<T>>::remove(auction_item.item_id);
-								}
-							}
-						} else {
-							Self::deposit_event(Event::AuctionFinalizedNoBid(auction_id));
-						}
-					}
-				};
+		fn on_idle(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			let cursor_weight = T::DbWeight::get().reads_writes(1, 1);
+			if remaining_weight <= cursor_weight {
+				return 0;
+			}
+
+			// Every finalized auction may cost an extra end block read, plus one for the last block
+			let item_weight = Self::finalize_auction_weight().saturating_add(T::DbWeight::get().reads(1));
+			let max_items =
+				(remaining_weight - cursor_weight).saturating_sub(T::DbWeight::get().reads(1)) / item_weight;
+			if max_items == 0 {
+				return 0;
 			}
+
+			Self::process_ended_auctions(now, max_items.saturated_into())
 		}
 		fn on_runtime_upgrade() -> Weight {
 			Self::upgrade_asset_auction_data_v2();
@@ -745,76 +824,21 @@ pub mod pallet {
 						ListingLevel::Local(metaverse_id) => {
 							ensure!(
 								MetaverseAuthorizedCollection::<T>::contains_key((metaverse_id, class_id))
-									|| T::MetaverseInfoSource::check_ownership(&recipient, &metaverse_id),
+									|| T::MetaverseInfoSource::check_role(
+										&recipient,
+										&metaverse_id,
+										&MetaverseRole::Admin
+									),
 								Error::<T>::NoPermissionToCreateAuction
 							);
 						}
 						_ => {}
 					}
 
-					let start_time = <system::Pallet<T>>::block_number();
-
-					let mut end_time = start_time + T::AuctionTimeToClose::get();
-					if let Some(_end_block) = _end {
-						end_time = _end_block
-					}
-					let auction_id = Self::new_auction(recipient.clone(), initial_amount, start_time, Some(end_time))?;
-					let mut currency_id: FungibleTokenId = FungibleTokenId::NativeToken(0);
-
-					let new_auction_item = AuctionItem {
-						item_id,
-						recipient: recipient.clone(),
-						initial_amount: initial_amount,
-						amount: initial_amount,
-						start_time,
-						end_time,
-						auction_type,
-						listing_level: listing_level.clone(),
-						currency_id,
-					};
-
-					<AuctionItems<T>>::insert(auction_id, new_auction_item);
-
-					Self::deposit_event(Event::NewAuctionItem(
-						auction_id,
-						recipient,
-						listing_level,
-						initial_amount,
-						initial_amount,
-						end_time,
-					));
-					<ItemsInAuction<T>>::insert(item_id, true);
-					Ok(auction_id)
+					Self::insert_auction_item(auction_type, item_id, _end, recipient, initial_amount, listing_level)
 				}
 				ItemId::Spot(_spot_id, _metaverse_id) => {
-					let start_time = <system::Pallet<T>>::block_number();
-					let end_time: T::BlockNumber = start_time + T::AuctionTimeToClose::get();
-					let auction_id = Self::new_auction(recipient.clone(), initial_amount, start_time, Some(end_time))?;
-
-					let new_auction_item = AuctionItem {
-						item_id,
-						recipient: recipient.clone(),
-						initial_amount,
-						amount: initial_amount,
-						start_time,
-						end_time,
-						auction_type,
-						listing_level: listing_level.clone(),
-						currency_id: FungibleTokenId::NativeToken(0),
-					};
-
-					<AuctionItems<T>>::insert(auction_id, new_auction_item);
-
-					Self::deposit_event(Event::NewAuctionItem(
-						auction_id,
-						recipient,
-						listing_level,
-						initial_amount,
-						initial_amount,
-						end_time,
-					));
-					<ItemsInAuction<T>>::insert(item_id, true);
-					Ok(auction_id)
+					Self::insert_auction_item(auction_type, item_id, _end, recipient, initial_amount, listing_level)
 				}
 				ItemId::Estate(_estate_id_) => {
 					// Ensure the _estate_id_ exist/minted
@@ -822,35 +846,24 @@ pub mod pallet {
 						T::EstateHandler::check_estate(_estate_id_)?,
 						Error::<T>::EstateDoesNotExist
 					);
+					// Leased estates can not be sold until the lease is over
+					ensure!(
+						!T::EstateHandler::is_estate_leased(_estate_id_)?,
+						Error::<T>::EstateIsLeased
+					);
+					ensure!(
+						!T::EstateHandler::is_estate_collateralized(_estate_id_)?,
+						Error::<T>::EstateIsCollateralized
+					);
 
-					let start_time = <system::Pallet<T>>::block_number();
-					let end_time: T::BlockNumber = start_time + T::AuctionTimeToClose::get(); // add 7 days block for default auction
-					let auction_id = Self::new_auction(recipient.clone(), initial_amount, start_time, Some(end_time))?;
-
-					let new_auction_item = AuctionItem {
-						item_id,
-						recipient: recipient.clone(),
-						initial_amount,
-						amount: initial_amount,
-						start_time,
-						end_time,
+					Self::insert_auction_item(
 						auction_type,
-						listing_level: ListingLevel::Global,
-						currency_id: FungibleTokenId::NativeToken(0),
-					};
-
-					<AuctionItems<T>>::insert(auction_id, new_auction_item);
-
-					Self::deposit_event(Event::NewAuctionItem(
-						auction_id,
+						item_id,
+						_end,
 						recipient,
-						listing_level,
-						initial_amount,
 						initial_amount,
-						end_time,
-					));
-					<ItemsInAuction<T>>::insert(item_id, true);
-					Ok(auction_id)
+						ListingLevel::Global,
+					)
 				}
 				ItemId::LandUnit(_coordinate_, _metaverse_id_) => {
 					// Ensure the _coordinate_ exist/minted
@@ -858,35 +871,48 @@ pub mod pallet {
 						T::EstateHandler::check_landunit(_metaverse_id_, _coordinate_)?,
 						Error::<T>::LandUnitDoesNotExist
 					);
+					ensure!(
+						!T::EstateHandler::is_land_unit_leased(_metaverse_id_, _coordinate_)?,
+						Error::<T>::LandUnitIsLeased
+					);
+					ensure!(
+						!T::EstateHandler::is_land_unit_collateralized(_metaverse_id_, _coordinate_)?,
+						Error::<T>::LandUnitIsCollateralized
+					);
 
-					let start_time = <system::Pallet<T>>::block_number();
-					let end_time: T::BlockNumber = start_time + T::AuctionTimeToClose::get(); // add 7 days block for default auction
-					let auction_id = Self::new_auction(recipient.clone(), initial_amount, start_time, Some(end_time))?;
-
-					let new_auction_item = AuctionItem {
+					Self::insert_auction_item(
+						auction_type,
 						item_id,
-						recipient: recipient.clone(),
+						_end,
+						recipient,
 						initial_amount,
-						amount: initial_amount,
-						start_time,
-						end_time,
-						auction_type,
-						listing_level: ListingLevel::Global,
-						currency_id: FungibleTokenId::NativeToken(0),
-					};
+						ListingLevel::Global,
+					)
+				}
+				ItemId::Block(undeployed_land_block_id) => {
+					ensure!(
+						T::UndeployedLandBlockHandler::check_undeployed_land_block(
+							&recipient,
+							undeployed_land_block_id
+						)?,
+						Error::<T>::NoPermissionToCreateAuction
+					);
 
-					<AuctionItems<T>>::insert(auction_id, new_auction_item);
+					Self::insert_auction_item(auction_type, item_id, _end, recipient, initial_amount, listing_level)
+				}
+				ItemId::Country(metaverse_id) => {
+					ensure!(
+						T::MetaverseInfoSource::check_ownership(&recipient, &metaverse_id),
+						Error::<T>::NoPermissionToCreateAuction
+					);
+					// Metaverses frozen by the council can not be listed
+					ensure!(
+						!T::MetaverseInfoSource::get_metaverse(metaverse_id)
+							.map_or(false, |metaverse| metaverse.is_frozen),
+						Error::<T>::NoPermissionToCreateAuction
+					);
 
-					Self::deposit_event(Event::NewAuctionItem(
-						auction_id,
-						recipient,
-						listing_level,
-						initial_amount,
-						initial_amount,
-						end_time,
-					));
-					<ItemsInAuction<T>>::insert(item_id, true);
-					Ok(auction_id)
+					Self::insert_auction_item(auction_type, item_id, _end, recipient, initial_amount, listing_level)
 				}
 				_ => Err(Error::<T>::AuctionTypeIsNotSupported.into()),
 			}
@@ -1022,6 +1048,20 @@ pub mod pallet {
 		}
 	}
 
+	impl<T: Config> SalesHistory<BalanceOf<T>> for Pallet<T> {
+		fn last_sale_price(item_id: ItemId) -> Option<BalanceOf<T>> {
+			Self::item_sales_history(item_id).last().map(|record| record.price)
+		}
+
+		fn class_last_sale_price(class_id: ClassId) -> Option<BalanceOf<T>> {
+			Self::class_sales_history(class_id).last().map(|record| record.price)
+		}
+
+		fn class_volume(class_id: ClassId, round: RoundIndex) -> BalanceOf<T> {
+			Self::class_sales_volume(class_id, round)
+		}
+	}
+
 	impl<T: Config> CheckAuctionItemHandler for Pallet<T> {
 		fn check_item_in_auction(item_id: ItemId) -> bool {
 			Self::items_in_auction(item_id) == Some(true)
@@ -1045,6 +1085,224 @@ pub mod pallet {
 	}
 
 	impl<T: Config> Pallet<T> {
+		/// List an item that passed its checks. Every item type ends at `end` when given and
+		/// `AuctionTimeToClose` blocks from now otherwise
+		fn insert_auction_item(
+			auction_type: AuctionType,
+			item_id: ItemId,
+			end: Option<T::BlockNumber>,
+			recipient: T::AccountId,
+			initial_amount: BalanceOf<T>,
+			listing_level: ListingLevel<T::AccountId>,
+		) -> Result<AuctionId, DispatchError> {
+			let start_time = <system::Pallet<T>>::block_number();
+			let end_time = end.unwrap_or(start_time + T::AuctionTimeToClose::get());
+			let auction_id = Self::new_auction(recipient.clone(), initial_amount, start_time, Some(end_time))?;
+
+			let new_auction_item = AuctionItem {
+				item_id,
+				recipient: recipient.clone(),
+				initial_amount,
+				amount: initial_amount,
+				start_time,
+				end_time,
+				auction_type,
+				listing_level: listing_level.clone(),
+				currency_id: FungibleTokenId::NativeToken(0),
+			};
+
+			<AuctionItems<T>>::insert(auction_id, new_auction_item);
+
+			Self::deposit_event(Event::NewAuctionItem(
+				auction_id,
+				recipient,
+				listing_level,
+				initial_amount,
+				initial_amount,
+				end_time,
+			));
+			<ItemsInAuction<T>>::insert(item_id, true);
+			Ok(auction_id)
+		}
+
+		/// Finalize up to `max_items` ended auctions, oldest end block first. At most
+		/// `max_items + 1` end blocks are visited, so a cursor lagging behind catches up over the
+		/// following blocks.
+		fn process_ended_auctions(now: T::BlockNumber, max_items: u32) -> Weight {
+			let previous_cursor = <FinalizationCursor<T>>::get();
+			let mut cursor = previous_cursor.unwrap_or(now);
+			let mut finalized: u32 = 0;
+			let mut visited: u32 = 0;
+
+			while cursor <= now && finalized < max_items && visited <= max_items {
+				visited += 1;
+				let remaining = (max_items - finalized) as usize;
+				// Read one past the remaining budget to know whether this end block is done
+				let ended_auctions: Vec<AuctionId> = <AuctionEndTime<T>>::iter_prefix(&cursor)
+					.map(|(auction_id, _)| auction_id)
+					.take(remaining + 1)
+					.collect();
+				let block_done = ended_auctions.len() <= remaining;
+
+				for auction_id in ended_auctions.into_iter().take(remaining) {
+					<AuctionEndTime<T>>::remove(&cursor, auction_id);
+					Self::finalize_auction(auction_id);
+					finalized += 1;
+				}
+
+				if !block_done {
+					break;
+				}
+				cursor = cursor.saturating_add(One::one());
+			}
+
+			if previous_cursor != Some(cursor) {
+				<FinalizationCursor<T>>::put(cursor);
+			}
+
+			T::DbWeight::get()
+				.reads_writes(1 + visited as Weight, 1)
+				.saturating_add(Self::finalize_auction_weight().saturating_mul(finalized as Weight))
+		}
+
+		/// Upper bound weight of finalizing a single auction, benchmarked on settling a bid for an
+		/// NFT with royalty, the most expensive way an auction ends
+		fn finalize_auction_weight() -> Weight {
+			T::WeightInfo::finalize_auction()
+		}
+
+		pub(crate) fn finalize_auction(auction_id: AuctionId) {
+			if let Some(auction) = <Auctions<T>>::get(&auction_id) {
+				if let Some(auction_item) = <AuctionItems<T>>::get(&auction_id) {
+					if let Some((high_bidder, high_bid_price)) = auction.bid {
+						match Self::settle_auction(auction_id, &auction_item, &high_bidder, high_bid_price) {
+							Ok(_) => {
+								Self::deposit_event(Event::AuctionFinalized(auction_id, high_bidder, high_bid_price));
+							}
+							Err(_) => {
+								// Bid stays reserved and item stays listed until settlement is retried
+								FailedSettlements::<T>::insert(auction_id, 1);
+								Self::deposit_event(Event::AuctionSettlementFailed(auction_id, 1));
+							}
+						}
+					} else {
+						Self::remove_auction(auction_id.clone(), auction_item.item_id);
+						Self::deposit_event(Event::AuctionFinalizedNoBid(auction_id));
+					}
+				}
+			}
+		}
+
+		/// Settle an ended auction as a single storage transaction, a failure leaves the bid
+		/// reserved and the item listed.
+		#[transactional]
+		fn settle_auction(
+			auction_id: AuctionId,
+			auction_item: &AuctionItem<T::AccountId, T::BlockNumber, BalanceOf<T>>,
+			high_bidder: &T::AccountId,
+			high_bid_price: BalanceOf<T>,
+		) -> DispatchResult {
+			<T as Config>::Currency::unreserve(high_bidder, high_bid_price);
+			Self::transfer_auction_item(auction_item, high_bidder, high_bid_price)?;
+			Self::remove_auction(auction_id, auction_item.item_id);
+			Self::record_sale(
+				auction_item.item_id,
+				&auction_item.recipient,
+				high_bidder,
+				high_bid_price,
+			);
+
+			Ok(())
+		}
+
+		/// Pay the seller and hand the item over to the buyer
+		fn transfer_auction_item(
+			auction_item: &AuctionItem<T::AccountId, T::BlockNumber, BalanceOf<T>>,
+			buyer: &T::AccountId,
+			price: BalanceOf<T>,
+		) -> DispatchResult {
+			<T as Config>::Currency::transfer(buyer, &auction_item.recipient, price, ExistenceRequirement::KeepAlive)?;
+
+			match auction_item.item_id {
+				ItemId::NFT(class_id, token_id) => {
+					Self::collect_royalty_fee(
+						&price,
+						&auction_item.recipient,
+						&(class_id, token_id),
+						FungibleTokenId::NativeToken(0),
+					)?;
+					T::NFTHandler::transfer_nft(&auction_item.recipient, buyer, &(class_id, token_id))?;
+				}
+				ItemId::Spot(spot_id, metaverse_id) => {
+					// Continuum only moves spots that are no longer listed
+					<ItemsInAuction<T>>::remove(auction_item.item_id);
+					T::ContinuumHandler::transfer_spot(
+						spot_id,
+						&auction_item.recipient,
+						&(buyer.clone(), metaverse_id),
+					)?;
+				}
+				ItemId::Estate(estate_id) => {
+					T::EstateHandler::transfer_estate(estate_id, &auction_item.recipient, buyer)?;
+					T::EstateHandler::record_land_sale(LandAsset::Estate(estate_id), price.saturated_into());
+				}
+				ItemId::LandUnit(coordinate, metaverse_id) => {
+					T::EstateHandler::transfer_landunit(
+						coordinate,
+						&auction_item.recipient,
+						&(buyer.clone(), metaverse_id),
+					)?;
+					T::EstateHandler::record_land_sale(
+						LandAsset::LandUnit(coordinate, metaverse_id),
+						price.saturated_into(),
+					);
+				}
+				ItemId::Block(undeployed_land_block_id) => {
+					// Listed land blocks and metaverses are locked until they leave the auction
+					<ItemsInAuction<T>>::remove(auction_item.item_id);
+					T::UndeployedLandBlockHandler::transfer_undeployed_land_block(
+						&auction_item.recipient,
+						buyer,
+						undeployed_land_block_id,
+					)?;
+				}
+				ItemId::Country(metaverse_id) => {
+					<ItemsInAuction<T>>::remove(auction_item.item_id);
+					T::MetaverseInfoSource::transfer_metaverse(&auction_item.recipient, buyer, metaverse_id)?;
+				}
+				_ => return Err(Error::<T>::AuctionTypeIsNotSupported.into()),
+			}
+
+			Ok(())
+		}
+
+		/// Keep the last `MaxSalesHistory` sales of the item and its collection
+		fn record_sale(item_id: ItemId, seller: &T::AccountId, buyer: &T::AccountId, price: BalanceOf<T>) {
+			let max_history = T::MaxSalesHistory::get() as usize;
+			let record = SaleRecord {
+				item_id,
+				seller: seller.clone(),
+				buyer: buyer.clone(),
+				price,
+				block_number: <system::Pallet<T>>::block_number(),
+			};
+			// The oldest sale makes room for the new one once the history is full
+			let push_record = |history: &mut SalesHistoryOf<T>| {
+				if !history.is_empty() && history.len() >= max_history {
+					history.remove(0);
+				}
+				let _ = history.try_push(record.clone());
+			};
+
+			ItemSalesHistory::<T>::mutate(item_id, push_record);
+
+			if let ItemId::NFT(class_id, _) = item_id {
+				ClassSalesHistory::<T>::mutate(class_id, push_record);
+				let round = T::RoundHandler::get_current_round_info().current;
+				ClassSalesVolume::<T>::mutate(class_id, round, |volume| *volume = volume.saturating_add(price));
+			}
+		}
+
 		pub fn upgrade_asset_auction_data_v2() -> Weight {
 			log::info!("Start upgrading nft class data v2");
 			let mut num_auction_item = 0;
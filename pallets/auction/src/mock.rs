@@ -9,8 +9,11 @@ use sp_runtime::traits::AccountIdConversion;
 use sp_runtime::{testing::Header, traits::IdentityLookup};
 
 use auction_manager::{CheckAuctionItemHandler, ListingLevel};
-use core_primitives::{MetaverseInfo, MetaverseTrait};
-use primitives::{continuum::Continuum, estate::Estate, Amount, AuctionId, EstateId, FungibleTokenId};
+use core_primitives::{MetaverseInfo, MetaverseRole, MetaverseTrait, RoundTrait, UndeployedLandBlocksTrait};
+use primitives::{
+	continuum::Continuum, estate::Estate, staking::RoundInfo, Amount, AuctionId, EstateId, FungibleTokenId,
+	UndeployedLandBlockId, UndeployedLandBlockType,
+};
 
 use crate as auction;
 
@@ -35,9 +38,15 @@ pub const BOB_METAVERSE_ID: MetaverseId = 2;
 pub const ESTATE_ID_EXIST: EstateId = 0;
 pub const ESTATE_ID_EXIST_1: EstateId = 1;
 pub const ESTATE_ID_NOT_EXIST: EstateId = 99;
+pub const ESTATE_ID_LEASED: EstateId = 2;
+pub const ESTATE_ID_COLLATERALIZED: EstateId = 3;
 pub const LAND_UNIT_EXIST: (i32, i32) = (0, 0);
 pub const LAND_UNIT_EXIST_1: (i32, i32) = (1, 1);
 pub const LAND_UNIT_NOT_EXIST: (i32, i32) = (99, 99);
+pub const LAND_UNIT_LEASED: (i32, i32) = (2, 2);
+pub const LAND_UNIT_COLLATERALIZED: (i32, i32) = (3, 3);
+pub const UNDEPLOYED_LAND_BLOCK_ID_EXIST: UndeployedLandBlockId = 0;
+pub const UNDEPLOYED_LAND_BLOCK_ID_NOT_EXIST: UndeployedLandBlockId = 99;
 
 impl frame_system::Config for Runtime {
 	type Origin = Origin;
@@ -107,7 +116,7 @@ impl Estate<u128> for EstateHandler {
 
 	fn check_estate(estate_id: EstateId) -> Result<bool, DispatchError> {
 		match estate_id {
-			ESTATE_ID_EXIST | ESTATE_ID_EXIST_1 => Ok(true),
+			ESTATE_ID_EXIST | ESTATE_ID_EXIST_1 | ESTATE_ID_LEASED | ESTATE_ID_COLLATERALIZED => Ok(true),
 			ESTATE_ID_NOT_EXIST => Ok(false),
 			_ => Ok(false),
 		}
@@ -115,7 +124,7 @@ impl Estate<u128> for EstateHandler {
 
 	fn check_landunit(_metaverse_id: MetaverseId, coordinate: (i32, i32)) -> Result<bool, DispatchError> {
 		match coordinate {
-			LAND_UNIT_EXIST | LAND_UNIT_EXIST_1 => Ok(true),
+			LAND_UNIT_EXIST | LAND_UNIT_EXIST_1 | LAND_UNIT_LEASED | LAND_UNIT_COLLATERALIZED => Ok(true),
 			LAND_UNIT_NOT_EXIST => Ok(false),
 			_ => Ok(false),
 		}
@@ -128,6 +137,77 @@ impl Estate<u128> for EstateHandler {
 	fn get_total_undeploy_land_units() -> u64 {
 		100
 	}
+
+	fn is_estate_leased(estate_id: EstateId) -> Result<bool, DispatchError> {
+		Ok(estate_id == ESTATE_ID_LEASED)
+	}
+
+	fn is_land_unit_leased(_metaverse_id: MetaverseId, coordinate: (i32, i32)) -> Result<bool, DispatchError> {
+		Ok(coordinate == LAND_UNIT_LEASED)
+	}
+
+	fn is_estate_collateralized(estate_id: EstateId) -> Result<bool, DispatchError> {
+		Ok(estate_id == ESTATE_ID_COLLATERALIZED)
+	}
+
+	fn is_land_unit_collateralized(_metaverse_id: MetaverseId, coordinate: (i32, i32)) -> Result<bool, DispatchError> {
+		Ok(coordinate == LAND_UNIT_COLLATERALIZED)
+	}
+
+	fn record_land_sale(_asset: LandAsset, _price: Balance) {}
+}
+
+pub struct UndeployedLandBlockHandler;
+
+impl UndeployedLandBlocksTrait<AccountId> for UndeployedLandBlockHandler {
+	fn issue_undeployed_land_blocks(
+		_beneficiary: &AccountId,
+		_number_of_land_block: u32,
+		_number_land_units_per_land_block: u32,
+		_undeployed_land_block_type: UndeployedLandBlockType,
+	) -> Result<Vec<UndeployedLandBlockId>, DispatchError> {
+		Ok(vec![UNDEPLOYED_LAND_BLOCK_ID_EXIST])
+	}
+
+	fn transfer_undeployed_land_block(
+		_who: &AccountId,
+		_to: &AccountId,
+		undeployed_land_block_id: UndeployedLandBlockId,
+	) -> Result<UndeployedLandBlockId, DispatchError> {
+		Ok(undeployed_land_block_id)
+	}
+
+	fn burn_undeployed_land_block(
+		undeployed_land_block_id: UndeployedLandBlockId,
+	) -> Result<UndeployedLandBlockId, DispatchError> {
+		Ok(undeployed_land_block_id)
+	}
+
+	fn freeze_undeployed_land_block(
+		undeployed_land_block_id: UndeployedLandBlockId,
+	) -> Result<UndeployedLandBlockId, DispatchError> {
+		Ok(undeployed_land_block_id)
+	}
+
+	fn check_undeployed_land_block(
+		who: &AccountId,
+		undeployed_land_block_id: UndeployedLandBlockId,
+	) -> Result<bool, DispatchError> {
+		match undeployed_land_block_id {
+			UNDEPLOYED_LAND_BLOCK_ID_EXIST => Ok(*who == BOB),
+			_ => Ok(false),
+		}
+	}
+}
+
+pub struct RoundHandler;
+
+impl RoundTrait<BlockNumber> for RoundHandler {
+	// Test with 100 blocks per round
+	fn get_current_round_info() -> RoundInfo<BlockNumber> {
+		let now = System::block_number();
+		RoundInfo::new((now / 100) as u32 + 1, now - now % 100, 100)
+	}
 }
 
 pub struct Handler;
@@ -187,7 +267,10 @@ parameter_types! {
 	pub const MinimumAuctionDuration: u64 = 10;
 	// Test 1% loyalty fee
 	pub const RoyaltyFee: u16 = 100;
-	pub const MaxFinality: u32 = 100;
+	// Test carry-over with 2 auctions finalized per block
+	pub const MaxFinality: u32 = 2;
+	pub const MaxSettlementAttempts: u32 = 3;
+	pub const MaxSalesHistory: u32 = 2;
 }
 
 pub struct MetaverseInfoSource {}
@@ -201,6 +284,12 @@ impl MetaverseTrait<AccountId> for MetaverseInfoSource {
 		}
 	}
 
+	fn check_role(who: &AccountId, metaverse_id: &MetaverseId, role: &MetaverseRole) -> bool {
+		// Bob is an admin of Alice's metaverse
+		Self::check_ownership(who, metaverse_id)
+			|| (*who == BOB && *metaverse_id == ALICE_METAVERSE_ID && *role == MetaverseRole::Admin)
+	}
+
 	fn get_metaverse(_metaverse_id: u64) -> Option<MetaverseInfo<u128>> {
 		None
 	}
@@ -212,6 +301,10 @@ impl MetaverseTrait<AccountId> for MetaverseInfoSource {
 	fn update_metaverse_token(_metaverse_id: u64, _currency_id: FungibleTokenId) -> Result<(), DispatchError> {
 		Ok(())
 	}
+
+	fn transfer_metaverse(_from: &AccountId, _to: &AccountId, _metaverse_id: u64) -> Result<(), DispatchError> {
+		Ok(())
+	}
 }
 
 impl Config for Runtime {
@@ -224,9 +317,14 @@ impl Config for Runtime {
 	type MetaverseInfoSource = MetaverseInfoSource;
 	type MinimumAuctionDuration = MinimumAuctionDuration;
 	type EstateHandler = EstateHandler;
+	type UndeployedLandBlockHandler = UndeployedLandBlockHandler;
 	type RoyaltyFee = RoyaltyFee;
 	type MaxFinality = MaxFinality;
+	type MaxSettlementAttempts = MaxSettlementAttempts;
+	type MaxSalesHistory = MaxSalesHistory;
+	type RoundHandler = RoundHandler;
 	type NFTHandler = NFTModule;
+	type WeightInfo = ();
 }
 
 pub type AdaptedBasicCurrency = currencies::BasicCurrencyAdapter<Runtime, Balances, Amount, BlockNumber>;
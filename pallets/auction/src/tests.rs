@@ -105,6 +105,66 @@ fn create_new_auction_should_fail_for_non_exist_estate() {
 	});
 }
 
+#[test]
+fn create_new_auction_should_fail_for_leased_estate_and_landunit() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			AuctionModule::create_auction(
+				AuctionType::Auction,
+				ItemId::Estate(ESTATE_ID_LEASED),
+				None,
+				ALICE,
+				100,
+				0,
+				ListingLevel::Global
+			),
+			Error::<Runtime>::EstateIsLeased
+		);
+		assert_noop!(
+			AuctionModule::create_auction(
+				AuctionType::Auction,
+				ItemId::LandUnit(LAND_UNIT_LEASED, ALICE_METAVERSE_ID),
+				None,
+				ALICE,
+				100,
+				0,
+				ListingLevel::Global
+			),
+			Error::<Runtime>::LandUnitIsLeased
+		);
+	});
+}
+
+#[test]
+fn create_new_auction_should_fail_for_collateralized_estate_and_landunit() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			AuctionModule::create_auction(
+				AuctionType::Auction,
+				ItemId::Estate(ESTATE_ID_COLLATERALIZED),
+				None,
+				ALICE,
+				100,
+				0,
+				ListingLevel::Global
+			),
+			Error::<Runtime>::EstateIsCollateralized
+		);
+		assert_noop!(
+			AuctionModule::create_auction(
+				AuctionType::Auction,
+				ItemId::LandUnit(LAND_UNIT_COLLATERALIZED, ALICE_METAVERSE_ID),
+				None,
+				ALICE,
+				100,
+				0,
+				ListingLevel::Global
+			),
+			Error::<Runtime>::LandUnitIsCollateralized
+		);
+	});
+}
+
 #[test]
 // Creating auction should work
 fn create_new_auction_should_work_for_valid_landunit() {
@@ -811,3 +871,520 @@ fn list_item_on_buy_now_local_marketplace_should_work() {
 		assert_eq!(AuctionModule::items_in_auction(ItemId::NFT(0, 0)), Some(true))
 	});
 }
+
+#[test]
+// Failed settlement is queued and can be retried by anyone
+fn failed_settlement_should_be_queued_and_retried() {
+	ExtBuilder::default().build().execute_with(|| {
+		let owner = Origin::signed(BOB);
+		let bidder = Origin::signed(ALICE);
+		init_test_nft(owner.clone());
+		assert_ok!(AuctionModule::create_auction(
+			AuctionType::Auction,
+			ItemId::NFT(0, 0),
+			None,
+			BOB,
+			100,
+			0,
+			ListingLevel::Global
+		));
+		assert_ok!(AuctionModule::bid(bidder.clone(), 0, 200));
+
+		// Seller moves the NFT away so the asset transfer fails on settlement
+		assert_ok!(NFTModule::<Runtime>::transfer(owner.clone(), ALICE, (0, 0)));
+		let bob_balance = Balances::free_balance(BOB);
+
+		run_to_block(102);
+		assert_eq!(
+			last_event(),
+			Event::AuctionModule(crate::Event::AuctionSettlementFailed(0, 1))
+		);
+		assert_eq!(AuctionModule::failed_settlements(0), Some(1));
+		// bid is still reserved and item is still listed
+		assert_eq!(Balances::reserved_balance(ALICE), 200);
+		assert_eq!(Balances::free_balance(BOB), bob_balance);
+		assert_eq!(AuctionModule::items_in_auction(ItemId::NFT(0, 0)), Some(true));
+
+		assert_ok!(NFTModule::<Runtime>::transfer(bidder, BOB, (0, 0)));
+		assert_ok!(AuctionModule::retry_auction_settlement(Origin::signed(BOB), 0));
+
+		assert_eq!(
+			last_event(),
+			Event::AuctionModule(crate::Event::AuctionFinalized(0, ALICE, 200))
+		);
+		assert_eq!(AuctionModule::failed_settlements(0), None);
+		assert_eq!(AuctionModule::auctions(0), None);
+		assert_eq!(AuctionModule::items_in_auction(ItemId::NFT(0, 0)), None);
+		assert_eq!(NFTModule::<Runtime>::get_assets_by_owner(ALICE), [(0, 0)]);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		// 200 - 2 (1% of 200 as loyalty fee)
+		assert_eq!(Balances::free_balance(BOB), bob_balance + 198);
+	});
+}
+
+#[test]
+// Failed settlement is refunded after max attempts
+fn failed_settlement_should_refund_after_max_attempts() {
+	ExtBuilder::default().build().execute_with(|| {
+		let owner = Origin::signed(BOB);
+		let bidder = Origin::signed(ALICE);
+		init_test_nft(owner.clone());
+		assert_ok!(AuctionModule::create_auction(
+			AuctionType::Auction,
+			ItemId::NFT(0, 0),
+			None,
+			BOB,
+			100,
+			0,
+			ListingLevel::Global
+		));
+		assert_ok!(AuctionModule::bid(bidder, 0, 200));
+		assert_ok!(NFTModule::<Runtime>::transfer(owner, ALICE, (0, 0)));
+
+		run_to_block(102);
+		assert_ok!(AuctionModule::retry_auction_settlement(Origin::signed(BOB), 0));
+		assert_eq!(
+			last_event(),
+			Event::AuctionModule(crate::Event::AuctionSettlementFailed(0, 2))
+		);
+		assert_eq!(AuctionModule::failed_settlements(0), Some(2));
+
+		assert_ok!(AuctionModule::retry_auction_settlement(Origin::signed(BOB), 0));
+		assert_eq!(
+			last_event(),
+			Event::AuctionModule(crate::Event::AuctionSettlementAbandoned(0, ALICE, 200))
+		);
+		assert_eq!(AuctionModule::failed_settlements(0), None);
+		assert_eq!(AuctionModule::auctions(0), None);
+		assert_eq!(AuctionModule::items_in_auction(ItemId::NFT(0, 0)), None);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::free_balance(ALICE), 100000);
+
+		assert_noop!(
+			AuctionModule::retry_auction_settlement(Origin::signed(BOB), 0),
+			Error::<Runtime>::AuctionSettlementNotFailed
+		);
+	});
+}
+
+#[test]
+// Collection offer escrows funds until cancelled
+fn make_and_cancel_collection_offer_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let offerer = Origin::signed(ALICE);
+
+		assert_noop!(
+			AuctionModule::make_collection_offer(offerer.clone(), CLASS_ID, 100, 2),
+			Error::<Runtime>::ClassDoesNotExist
+		);
+
+		init_test_nft(Origin::signed(BOB));
+		assert_noop!(
+			AuctionModule::make_collection_offer(offerer.clone(), CLASS_ID, 100, 0),
+			Error::<Runtime>::InvalidCollectionOffer
+		);
+
+		assert_ok!(AuctionModule::make_collection_offer(offerer.clone(), CLASS_ID, 100, 2));
+		assert_eq!(
+			last_event(),
+			Event::AuctionModule(crate::Event::CollectionOfferCreated(CLASS_ID, ALICE, 100, 2))
+		);
+		assert_eq!(Balances::reserved_balance(ALICE), 200);
+
+		assert_noop!(
+			AuctionModule::make_collection_offer(offerer.clone(), CLASS_ID, 50, 1),
+			Error::<Runtime>::CollectionOfferAlreadyExists
+		);
+
+		assert_ok!(AuctionModule::cancel_collection_offer(offerer.clone(), CLASS_ID));
+		assert_eq!(
+			last_event(),
+			Event::AuctionModule(crate::Event::CollectionOfferCancelled(CLASS_ID, ALICE))
+		);
+		assert_eq!(AuctionModule::collection_offers(CLASS_ID, ALICE), None);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+
+		assert_noop!(
+			AuctionModule::cancel_collection_offer(offerer, CLASS_ID),
+			Error::<Runtime>::CollectionOfferNotExist
+		);
+	});
+}
+
+#[test]
+// Token holder can fill a collection offer
+fn accept_collection_offer_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let owner = Origin::signed(BOB);
+		let offerer = Origin::signed(ALICE);
+		init_test_nft(owner.clone());
+		let bob_balance = Balances::free_balance(BOB);
+
+		assert_ok!(AuctionModule::make_collection_offer(offerer.clone(), CLASS_ID, 100, 2));
+
+		assert_noop!(
+			AuctionModule::accept_collection_offer(offerer, CLASS_ID, 0, ALICE),
+			Error::<Runtime>::CannotAcceptOwnOffer
+		);
+
+		assert_ok!(AuctionModule::accept_collection_offer(owner, CLASS_ID, 0, ALICE));
+		assert_eq!(
+			last_event(),
+			Event::AuctionModule(crate::Event::CollectionOfferAccepted(CLASS_ID, 0, BOB, ALICE, 100))
+		);
+
+		assert_eq!(NFTModule::<Runtime>::get_assets_by_owner(ALICE), [(0, 0)]);
+		// 100 - 1 (1% of 100 as loyalty fee)
+		assert_eq!(Balances::free_balance(BOB), bob_balance + 99);
+		assert_eq!(Balances::reserved_balance(ALICE), 100);
+		assert_eq!(Balances::free_balance(ALICE), 99800);
+		assert_eq!(
+			AuctionModule::collection_offers(CLASS_ID, ALICE),
+			Some(CollectionOffer {
+				price_per_token: 100,
+				quantity: 1
+			})
+		);
+	});
+}
+
+#[test]
+// Collection offer cannot be filled with a listed token or by non owner
+fn accept_collection_offer_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		let owner = Origin::signed(BOB);
+		init_test_nft(owner.clone());
+
+		assert_ok!(AuctionModule::make_collection_offer(
+			Origin::signed(ALICE),
+			CLASS_ID,
+			100,
+			1
+		));
+
+		assert_noop!(
+			AuctionModule::accept_collection_offer(Origin::signed(3), CLASS_ID, 0, ALICE),
+			Error::<Runtime>::NoPermissionToAcceptOffer
+		);
+
+		assert_ok!(AuctionModule::create_auction(
+			AuctionType::Auction,
+			ItemId::NFT(0, 0),
+			None,
+			BOB,
+			100,
+			0,
+			ListingLevel::Global
+		));
+		assert_noop!(
+			AuctionModule::accept_collection_offer(owner, CLASS_ID, 0, ALICE),
+			Error::<Runtime>::ItemAlreadyInAuction
+		);
+	});
+}
+
+#[test]
+// Undeployed land block can be listed by its owner and sold
+fn auction_undeployed_land_block_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let item_id = ItemId::Block(UNDEPLOYED_LAND_BLOCK_ID_EXIST);
+
+		assert_noop!(
+			AuctionModule::create_new_auction(Origin::signed(ALICE), item_id, 100, 101, ListingLevel::Global),
+			Error::<Runtime>::NoPermissionToCreateAuction
+		);
+		assert_noop!(
+			AuctionModule::create_new_auction(
+				Origin::signed(BOB),
+				ItemId::Block(UNDEPLOYED_LAND_BLOCK_ID_NOT_EXIST),
+				100,
+				101,
+				ListingLevel::Global
+			),
+			Error::<Runtime>::NoPermissionToCreateAuction
+		);
+
+		assert_ok!(AuctionModule::create_new_auction(
+			Origin::signed(BOB),
+			item_id,
+			100,
+			101,
+			ListingLevel::Global
+		));
+		assert_eq!(AuctionModule::items_in_auction(item_id), Some(true));
+
+		assert_ok!(AuctionModule::bid(Origin::signed(ALICE), 0, 200));
+		run_to_block(102);
+
+		assert_eq!(
+			last_event(),
+			Event::AuctionModule(crate::Event::AuctionFinalized(0, ALICE, 200))
+		);
+		assert_eq!(AuctionModule::items_in_auction(item_id), None);
+		assert_eq!(Balances::free_balance(ALICE), 99800);
+		assert_eq!(Balances::free_balance(BOB), 700);
+	});
+}
+
+#[test]
+// Metaverse can be listed by its owner and sold
+fn buy_now_metaverse_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let item_id = ItemId::Country(BOB_METAVERSE_ID);
+
+		assert_noop!(
+			AuctionModule::create_new_buy_now(Origin::signed(ALICE), item_id, 150, 101, ListingLevel::Global),
+			Error::<Runtime>::NoPermissionToCreateAuction
+		);
+
+		assert_ok!(AuctionModule::create_new_buy_now(
+			Origin::signed(BOB),
+			item_id,
+			150,
+			101,
+			ListingLevel::Global
+		));
+		assert_eq!(AuctionModule::items_in_auction(item_id), Some(true));
+
+		assert_ok!(AuctionModule::buy_now(Origin::signed(ALICE), 0, 150));
+		assert_eq!(
+			last_event(),
+			Event::AuctionModule(crate::Event::BuyNowFinalised(0, ALICE, 150))
+		);
+		assert_eq!(AuctionModule::items_in_auction(item_id), None);
+		assert_eq!(Balances::free_balance(ALICE), 99850);
+		assert_eq!(Balances::free_balance(BOB), 650);
+	});
+}
+
+#[test]
+// Auctions over the per block cap are carried over to the next block
+fn finalization_over_cap_should_carry_over() {
+	ExtBuilder::default().build().execute_with(|| {
+		for item_id in [
+			ItemId::Estate(ESTATE_ID_EXIST),
+			ItemId::Estate(ESTATE_ID_EXIST_1),
+			ItemId::LandUnit(LAND_UNIT_EXIST, ALICE_METAVERSE_ID),
+		] {
+			assert_ok!(AuctionModule::create_auction(
+				AuctionType::Auction,
+				item_id,
+				None,
+				BOB,
+				100,
+				0,
+				ListingLevel::Global
+			));
+		}
+
+		run_to_block(101);
+		let carried_over: Vec<AuctionId> = (0..3).filter(|id| AuctionModule::auctions(id).is_some()).collect();
+		assert_eq!(carried_over.len(), 1);
+		assert_eq!(AuctionModule::finalization_cursor(), Some(101));
+
+		run_to_block(102);
+		assert_eq!(AuctionModule::auctions(carried_over[0]), None);
+		assert_eq!(AuctionModule::auction_end_time(101, carried_over[0]), None);
+		assert_eq!(AuctionModule::finalization_cursor(), Some(103));
+		assert_eq!(
+			last_event(),
+			Event::AuctionModule(crate::Event::AuctionFinalizedNoBid(carried_over[0]))
+		);
+	});
+}
+
+#[test]
+// Carried over auctions are finalized with spare block weight
+fn on_idle_should_finalize_carried_over_auctions() {
+	ExtBuilder::default().build().execute_with(|| {
+		for item_id in [
+			ItemId::Estate(ESTATE_ID_EXIST),
+			ItemId::Estate(ESTATE_ID_EXIST_1),
+			ItemId::LandUnit(LAND_UNIT_EXIST, ALICE_METAVERSE_ID),
+		] {
+			assert_ok!(AuctionModule::create_auction(
+				AuctionType::Auction,
+				item_id,
+				None,
+				BOB,
+				100,
+				0,
+				ListingLevel::Global
+			));
+		}
+
+		run_to_block(101);
+		assert_eq!(AuctionModule::finalization_cursor(), Some(101));
+
+		// Not enough weight left for a single auction
+		let item_weight = <() as crate::WeightInfo>::finalize_auction();
+		assert_eq!(AuctionModule::on_idle(101, 0), 0);
+		assert_eq!(AuctionModule::on_idle(101, item_weight - 1), 0);
+		assert_eq!(AuctionModule::finalization_cursor(), Some(101));
+
+		let used_weight = AuctionModule::on_idle(101, 4 * item_weight);
+		assert!(used_weight > 0 && used_weight <= 4 * item_weight);
+		for auction_id in 0..3 {
+			assert_eq!(AuctionModule::auctions(auction_id), None);
+		}
+		assert_eq!(AuctionModule::finalization_cursor(), Some(102));
+	});
+}
+
+#[test]
+// Settled auctions, buy now and offers are kept in sales history
+fn sales_history_should_be_recorded() {
+	ExtBuilder::default().build().execute_with(|| {
+		let item_id = ItemId::NFT(CLASS_ID, 0);
+		init_test_nft(Origin::signed(BOB));
+
+		assert_ok!(AuctionModule::create_auction(
+			AuctionType::Auction,
+			item_id,
+			None,
+			BOB,
+			100,
+			0,
+			ListingLevel::Global
+		));
+		assert_ok!(AuctionModule::bid(Origin::signed(ALICE), 0, 200));
+		run_to_block(102);
+
+		assert_eq!(
+			AuctionModule::item_sales_history(item_id).into_inner(),
+			vec![SaleRecord {
+				item_id,
+				seller: BOB,
+				buyer: ALICE,
+				price: 200,
+				block_number: 101,
+			}]
+		);
+		assert_eq!(
+			<AuctionModule as SalesHistory<Balance>>::last_sale_price(item_id),
+			Some(200)
+		);
+
+		assert_ok!(AuctionModule::make_collection_offer(
+			Origin::signed(BOB),
+			CLASS_ID,
+			100,
+			1
+		));
+		assert_ok!(AuctionModule::accept_collection_offer(
+			Origin::signed(ALICE),
+			CLASS_ID,
+			0,
+			BOB
+		));
+
+		assert_ok!(AuctionModule::create_new_buy_now(
+			Origin::signed(BOB),
+			item_id,
+			50,
+			200,
+			ListingLevel::Global
+		));
+		assert_ok!(AuctionModule::buy_now(Origin::signed(ALICE), 1, 50));
+
+		// Only the last 2 sales are kept
+		let history = AuctionModule::class_sales_history(CLASS_ID);
+		assert_eq!(history.len(), 2);
+		assert_eq!(history[0].price, 100);
+		assert_eq!(history[1].price, 50);
+		assert_eq!(AuctionModule::item_sales_history(item_id), history);
+		assert_eq!(
+			<AuctionModule as SalesHistory<Balance>>::class_last_sale_price(CLASS_ID),
+			Some(50)
+		);
+		// All sales happened in round 2
+		assert_eq!(<AuctionModule as SalesHistory<Balance>>::class_volume(CLASS_ID, 2), 350);
+		assert_eq!(<AuctionModule as SalesHistory<Balance>>::class_volume(CLASS_ID, 1), 0);
+	});
+}
+
+#[test]
+fn metaverse_admin_can_authorise_collection() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			AuctionModule::authorise_metaverse_collection(Origin::signed(ALICE), 0, BOB_METAVERSE_ID),
+			Error::<Runtime>::NoPermissionToAuthoriseCollection
+		);
+
+		assert_ok!(AuctionModule::authorise_metaverse_collection(
+			Origin::signed(BOB),
+			0,
+			ALICE_METAVERSE_ID
+		));
+		assert_eq!(
+			last_event(),
+			Event::AuctionModule(crate::Event::CollectionAuthorizedInMetaverse(0, ALICE_METAVERSE_ID))
+		);
+
+		assert_ok!(AuctionModule::remove_authorise_metaverse_collection(
+			Origin::signed(BOB),
+			0,
+			ALICE_METAVERSE_ID
+		));
+		assert_eq!(
+			last_event(),
+			Event::AuctionModule(crate::Event::CollectionAuthorizationRemoveInMetaverse(
+				0,
+				ALICE_METAVERSE_ID
+			))
+		);
+	});
+}
+
+#[test]
+fn metaverse_admin_can_list_on_local_marketplace() {
+	ExtBuilder::default().build().execute_with(|| {
+		init_test_nft(Origin::signed(ALICE));
+		assert_noop!(
+			AuctionModule::create_new_auction(
+				Origin::signed(ALICE),
+				ItemId::NFT(0, 0),
+				100,
+				102,
+				ListingLevel::Local(BOB_METAVERSE_ID)
+			),
+			Error::<Runtime>::NoPermissionToCreateAuction
+		);
+
+		let item_id = ItemId::Block(UNDEPLOYED_LAND_BLOCK_ID_EXIST);
+		assert_ok!(AuctionModule::create_new_buy_now(
+			Origin::signed(BOB),
+			item_id,
+			100,
+			102,
+			ListingLevel::Local(ALICE_METAVERSE_ID)
+		));
+		assert_eq!(AuctionModule::items_in_auction(item_id), Some(true));
+	});
+}
+
+#[test]
+fn every_item_type_should_end_at_requested_block() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(AuctionModule::create_auction(
+			AuctionType::Auction,
+			ItemId::Spot(0, ALICE_METAVERSE_ID),
+			Some(50),
+			ALICE,
+			100,
+			0,
+			ListingLevel::Global
+		));
+		assert_ok!(AuctionModule::create_auction(
+			AuctionType::Auction,
+			ItemId::Estate(ESTATE_ID_EXIST),
+			Some(60),
+			ALICE,
+			100,
+			0,
+			ListingLevel::Global
+		));
+		assert_eq!(AuctionModule::auctions(0).and_then(|auction| auction.end), Some(50));
+		assert_eq!(AuctionModule::auctions(1).and_then(|auction| auction.end), Some(60));
+	});
+}
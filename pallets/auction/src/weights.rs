@@ -50,6 +50,7 @@ pub trait WeightInfo {
 	fn create_new_buy_now() -> Weight;
 	fn bid() -> Weight;
 	fn buy_now() -> Weight;
+	fn finalize_auction() -> Weight;
 }
 
 pub struct SubstrateWeight<T>(PhantomData<T>);
@@ -74,6 +75,11 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(9 as Weight))
 			.saturating_add(T::DbWeight::get().writes(10 as Weight))
 	}
+	fn finalize_auction() -> Weight {
+		(254_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(13 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
@@ -98,4 +104,9 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(10 as Weight))
 	}
+	fn finalize_auction() -> Weight {
+		(254_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(13 as Weight))
+	}
 }
@@ -0,0 +1,125 @@
+// This file is part of Bit.Country.
+
+// Copyright (C) 2020-2021 Bit.Country.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RPC interface for the continuum pallet map queries.
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use continuum_runtime_api::ContinuumApi as ContinuumRuntimeApi;
+use primitives::continuum::{ContinuumPortal, ContinuumSpotInfo};
+
+#[rpc]
+pub trait ContinuumApi<BlockHash, AccountId, Balance, BlockNumber> {
+	#[rpc(name = "continuum_spotPrice")]
+	fn spot_price(&self, coordinate: (i32, i32), at: Option<BlockHash>) -> Result<Balance>;
+
+	#[rpc(name = "continuum_portals")]
+	fn portals(&self, at: Option<BlockHash>) -> Result<Vec<ContinuumPortal>>;
+
+	#[rpc(name = "continuum_mapRegion")]
+	fn map_region(
+		&self,
+		bottom_left: (i32, i32),
+		top_right: (i32, i32),
+		at: Option<BlockHash>,
+	) -> Result<Vec<ContinuumSpotInfo<AccountId, Balance, BlockNumber>>>;
+}
+
+/// Continuum RPC methods, answered by the runtime API at the requested block
+pub struct Continuum<C, B> {
+	client: Arc<C>,
+	_marker: PhantomData<B>,
+}
+
+impl<C, B> Continuum<C, B> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(Error::RuntimeError.into()),
+		message: "Unable to query continuum runtime api".into(),
+		data: Some(format!("{:?}", err).into()),
+	}
+}
+
+impl<C, Block, AccountId, Balance, BlockNumber> ContinuumApi<<Block as BlockT>::Hash, AccountId, Balance, BlockNumber>
+	for Continuum<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: ContinuumRuntimeApi<Block, AccountId, Balance, BlockNumber>,
+	AccountId: Codec,
+	Balance: Codec,
+	BlockNumber: Codec,
+{
+	fn spot_price(&self, coordinate: (i32, i32), at: Option<<Block as BlockT>::Hash>) -> Result<Balance> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client
+			.runtime_api()
+			.spot_price(&at, coordinate)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn portals(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<ContinuumPortal>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client
+			.runtime_api()
+			.portals(&at)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn map_region(
+		&self,
+		bottom_left: (i32, i32),
+		top_right: (i32, i32),
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<ContinuumSpotInfo<AccountId, Balance, BlockNumber>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client
+			.runtime_api()
+			.map_region(&at, bottom_left, top_right)
+			.map_err(runtime_error_into_rpc_err)
+	}
+}
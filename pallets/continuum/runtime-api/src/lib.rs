@@ -0,0 +1,46 @@
+// This file is part of Bit.Country.
+
+// Copyright (C) 2020-2021 Bit.Country.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for the continuum pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+use primitives::continuum::{ContinuumPortal, ContinuumSpotInfo};
+
+sp_api::decl_runtime_apis! {
+	pub trait ContinuumApi<AccountId, Balance, BlockNumber> where
+		AccountId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+	{
+		/// Buy now price of the continuum spot at a coordinate
+		fn spot_price(coordinate: (i32, i32)) -> Balance;
+		/// Open portals between metaverses
+		fn portals() -> Vec<ContinuumPortal>;
+		/// Spots of a map region with occupant, status, price and any ongoing referendum, for a region
+		/// of at most 10,000 spots within the map bound
+		fn map_region(
+			bottom_left: (i32, i32),
+			top_right: (i32, i32),
+		) -> Vec<ContinuumSpotInfo<AccountId, Balance, BlockNumber>>;
+	}
+}
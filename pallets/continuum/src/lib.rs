@@ -33,13 +33,32 @@
 //!
 //! Continuum Spot Auction Process (rotate every x block):
 //! - Slot Registration (Express of Interest) - metaverse owner can register for their favourite
-//!   slot
-//! - Highest registered slot will move to Auction slots.
+//!   slot once per spot by reserving the EOI deposit
+//! - Highest registered slot will move to Auction slots, deposits on the other slots are refunded.
 //! - The Auction slot will move to Good neighborhood protocol to start voting by neighbor of the
 //!   spot
-//! - Simple majority negative voting applied - the bidder who has more than 51% vote nay will be
-//!   rejected
-//! - The auction will start on pallet_auction.
+//! - Owners of the eight neighbouring spots vote once per bidder, a bidder with a nay share above
+//!   the ban threshold will be rejected
+//! - The auction will start on pallet_auction. Banned bidders forfeit their EOI deposit to the
+//!   treasury, the others get it back.
+//!
+//! Spots are held on a lease of `SpotLeaseSessions` sessions which the owner can renew before it
+//! expires. Unrenewed spots return to the continuum treasury from the session rotation on, at most
+//! `MaxSpotLeaseExpiriesPerBlock` a block, and can be registered for again.
+//!
+//! With a map expansion threshold set, the max bound grows by a ring whenever the share of occupied
+//! spots within it reaches the threshold. The spots of the new ring are released in spiral order
+//! from the origin, at most `MaxSpotReleasesPerBlock` a block.
+//!
+//! Buy now prices scale the base `SpotPrice` with the distance from the origin and the number of
+//! occupied neighbours. The base price rises with every sale above the session target and falls at
+//! the session rotation when sales fell short of it.
+//!
+//! Spot holders can relocate to a free coordinate for a fee, or swap spots with another metaverse
+//! once its owner accepts the offer. The spot keeps its id and lease when it moves.
+//!
+//! Metaverses holding neighbouring spots can open a portal between them. One side proposes the
+//! portal, the other accepts it and either side can remove it again.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -47,22 +66,31 @@
 use codec::{Decode, Encode};
 #[cfg(feature = "std")]
 use frame_support::traits::GenesisBuild;
-use frame_support::traits::{Currency, LockableCurrency, ReservableCurrency};
-use frame_support::{dispatch::DispatchResult, ensure, traits::Get, PalletId};
+use frame_support::traits::{BalanceStatus, Currency, LockableCurrency, ReservableCurrency};
+use frame_support::{
+	dispatch::DispatchResult,
+	ensure,
+	traits::{Get, StorageVersion},
+	transactional, PalletId,
+};
 use frame_system::{ensure_root, ensure_signed};
 use scale_info::TypeInfo;
 use sp_runtime::traits::CheckedAdd;
 use sp_runtime::{
 	traits::{AccountIdConversion, One, Zero},
-	DispatchError, RuntimeDebug,
+	DispatchError, Perbill, RuntimeDebug,
 };
+use sp_std::convert::TryInto;
 use sp_std::vec;
 use sp_std::vec::Vec;
 
 use auction_manager::{Auction, AuctionType, CheckAuctionItemHandler, ListingLevel};
 use core_primitives::MetaverseTrait;
 pub use pallet::*;
-use primitives::{continuum::Continuum, ItemId, MetaverseId, SpotId};
+use primitives::{
+	continuum::{Continuum, ContinuumPortal, ContinuumSpotInfo, SpotReferendum, SpotReferendumTally, SpotStatus},
+	ItemId, MetaverseId, SpotId,
+};
 pub use types::*;
 pub use vote::*;
 
@@ -84,6 +112,9 @@ pub enum ContinuumAuctionSlotStatus {
 	GNPConfirmed,
 }
 
+/// Largest area, in spots, a map region query scans
+pub const MAX_MAP_QUERY_AREA: u32 = 10_000;
+
 /// Information of EOI on Continuum spot
 #[cfg_attr(feature = "std", derive(PartialEq, Eq))]
 #[derive(Encode, Decode, Clone, RuntimeDebug, TypeInfo)]
@@ -92,6 +123,17 @@ pub struct SpotEOI<AccountId> {
 	participants: Vec<AccountId>,
 }
 
+/// Offer to swap a continuum spot for the spot of another metaverse
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct SpotSwapOffer<AccountId> {
+	/// Account that made the offer
+	pub who: AccountId,
+	/// Metaverse holding the offered spot when the offer was made
+	pub metaverse_id: MetaverseId,
+	/// Spot wanted in exchange
+	pub target_spot_id: SpotId,
+}
+
 /// Information of an active auction slot
 #[cfg_attr(feature = "std", derive(PartialEq, Eq))]
 #[derive(Encode, Decode, Clone, RuntimeDebug, TypeInfo)]
@@ -102,6 +144,32 @@ pub struct AuctionSlot<BlockNumber, AccountId> {
 	status: ContinuumAuctionSlotStatus,
 }
 
+pub mod migration_v1 {
+	use codec::{Decode, Encode};
+	use scale_info::TypeInfo;
+	use sp_runtime::RuntimeDebug;
+	use sp_std::vec::Vec;
+
+	use primitives::SpotId;
+
+	use crate::ContinuumSpotTally;
+
+	/// Referendum status before the electorate was kept with it
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	pub struct ReferendumStatus<AccountId, BlockNumber> {
+		pub end: BlockNumber,
+		pub spot_id: SpotId,
+		pub tallies: Vec<ContinuumSpotTally<AccountId>>,
+	}
+
+	/// Referendum info before the electorate was kept with it
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	pub enum ReferendumInfo<AccountId, BlockNumber> {
+		Ongoing(ReferendumStatus<AccountId, BlockNumber>),
+		Finished { approved: bool, end: BlockNumber },
+	}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use frame_support::traits::ExistenceRequirement;
@@ -142,6 +210,42 @@ pub mod pallet {
 			+ LockableCurrency<Self::AccountId, Moment = Self::BlockNumber>;
 		/// Source of Metaverse Network Info
 		type MetaverseInfoSource: MetaverseTrait<Self::AccountId>;
+		/// Share of the neighbours voting nay above which a bidder is banned from the spot auction
+		#[pallet::constant]
+		type NeighbourBanThreshold: Get<Perbill>;
+		/// Number of sessions a continuum spot lease lasts
+		#[pallet::constant]
+		type SpotLeaseSessions: Get<u32>;
+		/// Maximum number of spot leases expired in a single block
+		#[pallet::constant]
+		type MaxSpotLeaseExpiriesPerBlock: Get<u32>;
+		/// Maximum number of spots released in a single block after a map expansion
+		#[pallet::constant]
+		type MaxSpotReleasesPerBlock: Get<u32>;
+		/// Base spot price change per sale above the target and per session without sales
+		#[pallet::constant]
+		type SpotPriceAdjustment: Get<Perbill>;
+		/// Number of buy now sales per session that keeps the base spot price steady
+		#[pallet::constant]
+		type TargetSpotSalesPerSession: Get<u32>;
+		/// Extra multiples of the base price paid at the origin, fading out towards the map edge
+		#[pallet::constant]
+		type CentralSpotPremium: Get<u32>;
+		/// Share of the price added for every occupied neighbouring spot
+		#[pallet::constant]
+		type NeighbourSpotPremium: Get<Perbill>;
+		/// Lowest base spot price
+		#[pallet::constant]
+		type MinimumSpotPrice: Get<BalanceOf<Self>>;
+		/// Flat fee for relocating a spot, on top of any price difference to the new coordinate
+		#[pallet::constant]
+		type SpotRelocationFee: Get<BalanceOf<Self>>;
+		/// Deposit reserved for every expression of interest
+		#[pallet::constant]
+		type EOIDeposit: Get<BalanceOf<Self>>;
+		/// Maximum length of portal metadata
+		#[pallet::constant]
+		type MaxPortalMetadata: Get<u32>;
 	}
 
 	#[pallet::genesis_config]
@@ -180,7 +284,10 @@ pub mod pallet {
 		}
 	}
 
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(PhantomData<T>);
 
@@ -188,13 +295,23 @@ pub mod pallet {
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
 		/// Initialization
 		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let mut weight: Weight = 0;
 			let auction_duration: T::BlockNumber = T::SessionDuration::get();
 			if !auction_duration.is_zero() && (now % auction_duration).is_zero() {
 				Self::rotate_auction_slots(now);
-				20_000_000
-			} else {
-				0
+				Self::expand_map_if_needed();
+				weight = 20_000_000 + T::DbWeight::get().reads_writes(4, 3);
 			}
+
+			weight
+				.saturating_add(Self::expire_spot_leases())
+				.saturating_add(Self::release_ring_spots())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			Self::upgrade_referendum_electorate_v1()
+				.saturating_add(Self::upgrade_spot_leases_v2())
+				.saturating_add(Self::upgrade_occupied_spots_v3())
 		}
 	}
 
@@ -230,6 +347,12 @@ pub mod pallet {
 	#[pallet::getter(fn get_eoi_set)]
 	pub type EOISlots<T: Config> = StorageMap<_, Twox64Concat, T::BlockNumber, Vec<SpotEOI<T::AccountId>>, ValueQuery>;
 
+	/// Deposits reserved for expressions of interest that are still in the process
+	#[pallet::storage]
+	#[pallet::getter(fn get_eoi_deposit)]
+	pub type EOIDeposits<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, SpotId, Twox64Concat, T::AccountId, BalanceOf<T>, OptionQuery>;
+
 	/// Information of Continuum Spot Referendum
 	#[pallet::storage]
 	#[pallet::getter(fn get_continuum_referendum)]
@@ -268,6 +391,57 @@ pub mod pallet {
 	#[pallet::getter(fn initial_spot_price)]
 	pub type SpotPrice<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
 
+	/// Block at which the lease of a continuum spot expires
+	#[pallet::storage]
+	#[pallet::getter(fn get_spot_lease_expiry)]
+	pub type SpotLeaseExpiry<T: Config> = StorageMap<_, Twox64Concat, SpotId, T::BlockNumber, OptionQuery>;
+
+	/// Share of occupied spots within the max bound that expands the map by a ring
+	#[pallet::storage]
+	#[pallet::getter(fn get_map_expansion_threshold)]
+	pub type MapExpansionThreshold<T: Config> = StorageValue<_, Perbill, OptionQuery>;
+
+	/// Open offers to swap a spot, by offered spot
+	#[pallet::storage]
+	#[pallet::getter(fn get_spot_swap_offer)]
+	pub type SpotSwapOffers<T: Config> = StorageMap<_, Twox64Concat, SpotId, SpotSwapOffer<T::AccountId>, OptionQuery>;
+
+	/// Portals proposed by a metaverse to a neighbouring metaverse
+	#[pallet::storage]
+	#[pallet::getter(fn get_portal_proposal)]
+	pub type PortalProposals<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, MetaverseId, Twox64Concat, MetaverseId, ContinuumPortal, OptionQuery>;
+
+	/// Open portals, keyed by the lower metaverse id first
+	#[pallet::storage]
+	#[pallet::getter(fn get_portal)]
+	pub type Portals<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, MetaverseId, Twox64Concat, MetaverseId, ContinuumPortal, OptionQuery>;
+
+	/// Number of buy now sales in the current session
+	#[pallet::storage]
+	#[pallet::getter(fn session_spot_sales)]
+	pub type SessionSpotSales<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// Number of spots currently held by a metaverse
+	#[pallet::storage]
+	#[pallet::getter(fn occupied_spots)]
+	pub type OccupiedSpots<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// Continuum spot leases indexed by the session rotation they expire at
+	#[pallet::storage]
+	pub type SpotLeaseExpiries<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Twox64Concat, SpotId, (), OptionQuery>;
+
+	/// Session rotations with spot leases that are due but not all expired yet, oldest first
+	#[pallet::storage]
+	pub type DueSpotLeaseExpiries<T: Config> = StorageValue<_, Vec<T::BlockNumber>, ValueQuery>;
+
+	/// Bound of the ring being released after a map expansion and the spiral index of its next spot
+	#[pallet::storage]
+	#[pallet::getter(fn spot_release_progress)]
+	pub type SpotReleaseProgress<T: Config> = StorageValue<_, ((i32, i32), u32), OptionQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub (crate) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -289,6 +463,36 @@ pub mod pallet {
 		NewAuctionSlotRotated(T::BlockNumber),
 		/// Finalize vote
 		FinalizedVote(SpotId),
+		/// Neighbour voted on a bidder of a spot [voter, spot_id, bidder, nay]
+		NeighbourVoted(T::AccountId, SpotId, T::AccountId, bool),
+		/// Neighbour removed their vote on a bidder of a spot [voter, spot_id, bidder]
+		NeighbourVoteRemoved(T::AccountId, SpotId, T::AccountId),
+		/// Spot lease renewed [spot_id, new_expiry]
+		SpotLeaseRenewed(SpotId, T::BlockNumber),
+		/// Spot lease expired and the spot returned to the treasury [spot_id, metaverse_id]
+		SpotLeaseExpired(SpotId, MetaverseId),
+		/// Map expansion threshold updated
+		MapExpansionThresholdSet(Option<Perbill>),
+		/// Continuum map expanded by a ring [new_bound, ring_spots]
+		ContinuumMapExpanded((i32, i32), u32),
+		/// Base spot price adjusted
+		SpotPriceUpdated(BalanceOf<T>),
+		/// Spot relocated [spot_id, from, to, neighbouring metaverses]
+		SpotRelocated(SpotId, (i32, i32), (i32, i32), Vec<MetaverseId>),
+		/// Spot swap offered [offered_spot_id, target_spot_id]
+		SpotSwapOffered(SpotId, SpotId),
+		/// Spot swap offer cancelled [offered_spot_id]
+		SpotSwapOfferCancelled(SpotId),
+		/// Spots swapped [offered_spot_id, target_spot_id, neighbouring metaverses]
+		SpotsSwapped(SpotId, SpotId, Vec<MetaverseId>),
+		/// Banned bidder forfeited the EOI deposit [who, spot_id, amount]
+		EOIDepositForfeited(T::AccountId, SpotId, BalanceOf<T>),
+		/// Portal proposed [from_metaverse_id, to_metaverse_id]
+		PortalProposed(MetaverseId, MetaverseId),
+		/// Portal opened [from_metaverse_id, to_metaverse_id]
+		PortalOpened(MetaverseId, MetaverseId),
+		/// Portal or portal proposal removed [metaverse_id, other_metaverse_id]
+		PortalRemoved(MetaverseId, MetaverseId),
 	}
 
 	#[pallet::error]
@@ -327,6 +531,30 @@ pub mod pallet {
 		ContinuumBuyNowIsDisabled,
 		/// Continuum Spot is in auction
 		SpotIsInAuction,
+		/// Voted bidder is not a participant of the referendum
+		CandidateNotFound,
+		/// Already voted on this bidder
+		AlreadyVoted,
+		/// Vote does not exist
+		VoteNotFound,
+		/// Spot is not held on a lease
+		SpotLeaseNotFound,
+		/// Spot lease can only be renewed one lease period ahead
+		SpotLeaseRenewalTooEarly,
+		/// Spot swap offer does not exist
+		SpotSwapOfferNotFound,
+		/// Spots can not be swapped
+		InvalidSpotSwap,
+		/// Portal needs spots of two different metaverses
+		InvalidPortal,
+		/// Spots are not neighbours
+		SpotsAreNotNeighbours,
+		/// Portal already exists
+		PortalAlreadyExists,
+		/// Portal does not exist
+		PortalNotFound,
+		/// Portal metadata is too long
+		PortalMetadataTooLong,
 	}
 
 	#[pallet::call]
@@ -353,7 +581,7 @@ pub mod pallet {
 			}
 
 			let spot_id = Self::check_spot_ownership(maybe_spot_id, coordinate)?;
-			let continuum_price_spot = SpotPrice::<T>::get();
+			let continuum_price_spot = Self::spot_price(coordinate);
 
 			let continuum_treasury = Self::account_id();
 
@@ -369,6 +597,7 @@ pub mod pallet {
 			)?;
 
 			Self::do_transfer_spot(spot_id, &continuum_treasury, &(sender, metaverse_id))?;
+			Self::record_spot_sale();
 
 			Ok(().into())
 		}
@@ -381,7 +610,8 @@ pub mod pallet {
 			Ok(().into())
 		}
 
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5, 3))]
+		#[transactional]
 		/// Register continuum slot interest
 		pub fn register_interest(
 			origin: OriginFor<T>,
@@ -402,6 +632,15 @@ pub mod pallet {
 
 			let spot_id = Self::check_spot_ownership(maybe_spot_id, coordinate)?;
 
+			// One expression of interest per sender until the spot leaves the process
+			ensure!(
+				!EOIDeposits::<T>::contains_key(spot_id, &sender),
+				Error::<T>::EOIAlreadyExists
+			);
+			let deposit = T::EOIDeposit::get();
+			T::Currency::reserve(&sender, deposit)?;
+			EOIDeposits::<T>::insert(spot_id, &sender, deposit);
+
 			// Get current active session
 			let current_active_session_id = CurrentIndex::<T>::get();
 
@@ -457,6 +696,18 @@ pub mod pallet {
 			Ok(().into())
 		}
 
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		// Council set the occupancy that grows the map automatically, None disables expansion
+		pub fn set_map_expansion_threshold(
+			origin: OriginFor<T>,
+			threshold: Option<Perbill>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			MapExpansionThreshold::<T>::set(threshold);
+			Self::deposit_event(Event::MapExpansionThresholdSet(threshold));
+			Ok(().into())
+		}
+
 		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
 		// Council set how many auction can run per period
 		pub fn set_new_auction_rate(origin: OriginFor<T>, new_rate: u8) -> DispatchResultWithPostInfo {
@@ -465,13 +716,273 @@ pub mod pallet {
 			Self::deposit_event(Event::NewMaxAuctionSlotSet(new_rate));
 			Ok(().into())
 		}
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(11, 2))]
+		/// Neighbour votes on a bidder of a spot in the good neighbourhood protocol
 		pub fn vote(origin: OriginFor<T>, id: SpotId, reject: AccountVote<T::AccountId>) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
 			Self::try_vote(&sender, id, reject)?;
 			Ok(().into())
 		}
 
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 2))]
+		/// Remove a vote on a bidder, the tally is only updated while the referendum is ongoing
+		pub fn remove_vote(origin: OriginFor<T>, id: SpotId, candidate: T::AccountId) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			Self::try_remove_vote(&sender, id, candidate)?;
+			Ok(().into())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 5))]
+		/// Renew the lease of a continuum spot by paying the spot price
+		pub fn renew_spot_lease(origin: OriginFor<T>, spot_id: SpotId) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let spot = ContinuumSpots::<T>::try_get(spot_id).map_err(|_| Error::<T>::ContinuumSpotNotFound)?;
+			ensure!(
+				T::MetaverseInfoSource::check_ownership(&sender, &spot.metaverse_id),
+				Error::<T>::NoPermission
+			);
+
+			let expiry = SpotLeaseExpiry::<T>::get(spot_id).ok_or(Error::<T>::SpotLeaseNotFound)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			let lease_duration = Self::spot_lease_duration();
+			ensure!(
+				expiry.saturating_sub(now) <= lease_duration,
+				Error::<T>::SpotLeaseRenewalTooEarly
+			);
+
+			T::Currency::transfer(
+				&sender,
+				&Self::account_id(),
+				Self::spot_lease_price(spot_id),
+				ExistenceRequirement::KeepAlive,
+			)?;
+
+			let new_expiry = expiry + lease_duration;
+			Self::set_spot_lease_expiry(spot_id, new_expiry);
+
+			Self::deposit_event(Event::SpotLeaseRenewed(spot_id, new_expiry));
+			Ok(().into())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(40, 5))]
+		#[transactional]
+		/// Move a held spot to a free coordinate
+		pub fn relocate_spot(
+			origin: OriginFor<T>,
+			spot_id: SpotId,
+			coordinate: (i32, i32),
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let spot = Self::ensure_spot_holder(&sender, spot_id)?;
+			Self::ensure_within_bound(coordinate)?;
+
+			let from = (spot.x, spot.y);
+			ensure!(from != coordinate, Error::<T>::SpotIsNotAvailable);
+			let destination_spot_id = ContinuumCoordinates::<T>::try_get(coordinate).ok();
+			if let Some(destination_spot_id) = destination_spot_id {
+				ensure!(Self::is_spot_free(destination_spot_id), Error::<T>::SpotIsNotAvailable);
+			}
+
+			let fee = T::SpotRelocationFee::get()
+				.saturating_add(Self::spot_price(coordinate).saturating_sub(Self::spot_price(from)));
+			T::Currency::transfer(&sender, &Self::account_id(), fee, ExistenceRequirement::KeepAlive)?;
+
+			// A treasury spot at the destination takes the vacated coordinate
+			match destination_spot_id {
+				Some(destination_spot_id) => Self::move_spot(destination_spot_id, from),
+				None => ContinuumCoordinates::<T>::remove(from),
+			}
+			Self::move_spot(spot_id, coordinate);
+
+			let neighbours = Self::neighbour_metaverses(&[from, coordinate], &[spot.metaverse_id]);
+			Self::deposit_event(Event::SpotRelocated(spot_id, from, coordinate, neighbours));
+			Ok(().into())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 1))]
+		/// Offer to swap a held spot for the spot of another metaverse
+		pub fn offer_spot_swap(
+			origin: OriginFor<T>,
+			spot_id: SpotId,
+			target_spot_id: SpotId,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let spot = Self::ensure_spot_holder(&sender, spot_id)?;
+			ensure!(spot_id != target_spot_id, Error::<T>::InvalidSpotSwap);
+			let target_spot =
+				ContinuumSpots::<T>::try_get(target_spot_id).map_err(|_| Error::<T>::ContinuumSpotNotFound)?;
+			ensure!(target_spot.metaverse_id != 0, Error::<T>::InvalidSpotSwap);
+
+			SpotSwapOffers::<T>::insert(
+				spot_id,
+				SpotSwapOffer {
+					who: sender,
+					metaverse_id: spot.metaverse_id,
+					target_spot_id,
+				},
+			);
+
+			Self::deposit_event(Event::SpotSwapOffered(spot_id, target_spot_id));
+			Ok(().into())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		/// Cancel a spot swap offer
+		pub fn cancel_spot_swap(origin: OriginFor<T>, spot_id: SpotId) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let offer = SpotSwapOffers::<T>::get(spot_id).ok_or(Error::<T>::SpotSwapOfferNotFound)?;
+			ensure!(
+				offer.who == sender || T::MetaverseInfoSource::check_ownership(&sender, &offer.metaverse_id),
+				Error::<T>::NoPermission
+			);
+
+			SpotSwapOffers::<T>::remove(spot_id);
+			Self::deposit_event(Event::SpotSwapOfferCancelled(spot_id));
+			Ok(().into())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(40, 5))]
+		#[transactional]
+		/// Owner of the target spot accepts a swap offer
+		pub fn accept_spot_swap(origin: OriginFor<T>, spot_id: SpotId) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let offer = SpotSwapOffers::<T>::get(spot_id).ok_or(Error::<T>::SpotSwapOfferNotFound)?;
+			let target_spot = Self::ensure_spot_holder(&sender, offer.target_spot_id)?;
+
+			// Offers go stale once the offered spot or its metaverse changes hands
+			let offered_spot = ContinuumSpots::<T>::get(spot_id);
+			ensure!(
+				offered_spot.metaverse_id == offer.metaverse_id
+					&& T::MetaverseInfoSource::check_ownership(&offer.who, &offer.metaverse_id),
+				Error::<T>::InvalidSpotSwap
+			);
+			Self::ensure_spot_not_in_auction(spot_id, &offered_spot)?;
+
+			let offered_coordinate = (offered_spot.x, offered_spot.y);
+			let target_coordinate = (target_spot.x, target_spot.y);
+			Self::move_spot(spot_id, target_coordinate);
+			Self::move_spot(offer.target_spot_id, offered_coordinate);
+			SpotSwapOffers::<T>::remove(spot_id);
+
+			let neighbours = Self::neighbour_metaverses(
+				&[offered_coordinate, target_coordinate],
+				&[offered_spot.metaverse_id, target_spot.metaverse_id],
+			);
+			Self::deposit_event(Event::SpotsSwapped(spot_id, offer.target_spot_id, neighbours));
+			Ok(().into())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 1))]
+		/// Propose a portal from a held spot to the metaverse holding a neighbouring spot
+		pub fn propose_portal(
+			origin: OriginFor<T>,
+			spot_id: SpotId,
+			neighbour_spot_id: SpotId,
+			metadata: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			ensure!(
+				metadata.len() as u32 <= T::MaxPortalMetadata::get(),
+				Error::<T>::PortalMetadataTooLong
+			);
+			let spot = Self::ensure_spot_holder(&sender, spot_id)?;
+			let neighbour_spot =
+				ContinuumSpots::<T>::try_get(neighbour_spot_id).map_err(|_| Error::<T>::ContinuumSpotNotFound)?;
+			ensure!(
+				neighbour_spot.metaverse_id != 0 && neighbour_spot.metaverse_id != spot.metaverse_id,
+				Error::<T>::InvalidPortal
+			);
+			ensure!(
+				Self::are_neighbours(&spot, &neighbour_spot),
+				Error::<T>::SpotsAreNotNeighbours
+			);
+			let (lower, upper) = Self::portal_key(spot.metaverse_id, neighbour_spot.metaverse_id);
+			ensure!(
+				Self::open_portal(lower, upper).is_none(),
+				Error::<T>::PortalAlreadyExists
+			);
+
+			PortalProposals::<T>::insert(
+				spot.metaverse_id,
+				neighbour_spot.metaverse_id,
+				ContinuumPortal {
+					metaverses: (spot.metaverse_id, neighbour_spot.metaverse_id),
+					spots: (spot_id, neighbour_spot_id),
+					metadata,
+				},
+			);
+
+			Self::deposit_event(Event::PortalProposed(spot.metaverse_id, neighbour_spot.metaverse_id));
+			Ok(().into())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 3))]
+		/// Accept a portal proposed to a metaverse of the sender
+		pub fn accept_portal(
+			origin: OriginFor<T>,
+			from_metaverse_id: MetaverseId,
+			metaverse_id: MetaverseId,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			ensure!(
+				T::MetaverseInfoSource::check_ownership(&sender, &metaverse_id),
+				Error::<T>::NoPermission
+			);
+			let portal =
+				PortalProposals::<T>::get(from_metaverse_id, metaverse_id).ok_or(Error::<T>::PortalNotFound)?;
+
+			// Spots may have moved or changed hands since the proposal
+			let from_spot = ContinuumSpots::<T>::get(portal.spots.0);
+			let to_spot = ContinuumSpots::<T>::get(portal.spots.1);
+			ensure!(
+				from_spot.metaverse_id == from_metaverse_id
+					&& to_spot.metaverse_id == metaverse_id
+					&& Self::are_neighbours(&from_spot, &to_spot),
+				Error::<T>::SpotsAreNotNeighbours
+			);
+			let (lower, upper) = Self::portal_key(from_metaverse_id, metaverse_id);
+			ensure!(
+				Self::open_portal(lower, upper).is_none(),
+				Error::<T>::PortalAlreadyExists
+			);
+
+			PortalProposals::<T>::remove(from_metaverse_id, metaverse_id);
+			PortalProposals::<T>::remove(metaverse_id, from_metaverse_id);
+			Portals::<T>::insert(lower, upper, portal);
+
+			Self::deposit_event(Event::PortalOpened(from_metaverse_id, metaverse_id));
+			Ok(().into())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 3))]
+		/// Remove a portal or portal proposal between a metaverse of the sender and another metaverse
+		pub fn remove_portal(
+			origin: OriginFor<T>,
+			metaverse_id: MetaverseId,
+			other_metaverse_id: MetaverseId,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			ensure!(
+				T::MetaverseInfoSource::check_ownership(&sender, &metaverse_id),
+				Error::<T>::NoPermission
+			);
+			let (lower, upper) = Self::portal_key(metaverse_id, other_metaverse_id);
+			ensure!(
+				Portals::<T>::contains_key(lower, upper)
+					|| PortalProposals::<T>::contains_key(metaverse_id, other_metaverse_id)
+					|| PortalProposals::<T>::contains_key(other_metaverse_id, metaverse_id),
+				Error::<T>::PortalNotFound
+			);
+
+			Portals::<T>::remove(lower, upper);
+			PortalProposals::<T>::remove(metaverse_id, other_metaverse_id);
+			PortalProposals::<T>::remove(other_metaverse_id, metaverse_id);
+
+			Self::deposit_event(Event::PortalRemoved(metaverse_id, other_metaverse_id));
+			Ok(().into())
+		}
+
 		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
 		pub fn emergency_shutdown(origin: OriginFor<T>, spot_id: SpotId) -> DispatchResultWithPostInfo {
 			// Only some origins can execute this function
@@ -496,6 +1007,9 @@ impl<T: Config> Pallet<T> {
 	fn rotate_auction_slots(now: T::BlockNumber) -> DispatchResult {
 		// Get current active session
 		let current_active_session_id = CurrentIndex::<T>::get();
+		// Return spots with unrenewed leases to the treasury, from this block on
+		DueSpotLeaseExpiries::<T>::append(now);
+		Self::adjust_spot_price();
 		// Change status of all current active auction slots
 		// Move EOI to Auction Slots
 		Self::eoi_to_auction_slots(current_active_session_id, now)?;
@@ -532,29 +1046,41 @@ impl<T: Config> Pallet<T> {
 	}
 
 	fn finalize_vote(now: T::BlockNumber) -> DispatchResult {
-		let recent_slots = GNPSlots::<T>::get(now).ok_or(Error::<T>::NoActiveReferendum)?;
+		// Referendums end one session after their slots moved to GNP
+		let started_at = now.saturating_sub(T::SessionDuration::get());
+		let recent_slots = GNPSlots::<T>::get(started_at).ok_or(Error::<T>::NoActiveReferendum)?;
+		let ban_threshold = T::NeighbourBanThreshold::get();
 
 		for mut recent_slot in recent_slots.into_iter() {
-			let referendum_info: ReferendumStatus<T::AccountId, T::BlockNumber> =
-				Self::referendum_status(recent_slot.spot_id)?;
-
-			if referendum_info.end == now {
-				let banned_list: Vec<T::AccountId> = referendum_info
-					.tallies
-					.into_iter()
-					.filter(|t| Self::check_approved(t) == true)
-					.map(|tally| tally.who)
-					.collect();
-
-				for banned_account in banned_list {
-					let account_index = recent_slot
-						.participants
-						.iter()
-						.position(|x| *x == banned_account)
-						.unwrap();
-					recent_slot.participants.remove(account_index);
-					recent_slot.status = ContinuumAuctionSlotStatus::GNPConfirmed;
+			let referendum_info = match Self::referendum_status(recent_slot.spot_id) {
+				Ok(status) if status.end == now => status,
+				Ok(_) => continue,
+				// Cancelled referendums hand the deposits back
+				Err(_) => {
+					Self::release_eoi_deposits(recent_slot.spot_id, &recent_slot.participants);
+					continue;
 				}
+			};
+
+			let neighbours = referendum_info.neighbours;
+			let banned_list: Vec<T::AccountId> = referendum_info
+				.tallies
+				.into_iter()
+				.filter(|tally| tally.result(neighbours, ban_threshold))
+				.map(|tally| tally.who)
+				.collect();
+
+			for banned_account in banned_list.iter() {
+				Self::forfeit_eoi_deposit(recent_slot.spot_id, banned_account);
+			}
+			recent_slot
+				.participants
+				.retain(|participant| !banned_list.contains(participant));
+			recent_slot.status = ContinuumAuctionSlotStatus::GNPConfirmed;
+			Self::release_eoi_deposits(recent_slot.spot_id, &recent_slot.participants);
+
+			let approved = !recent_slot.participants.is_empty();
+			if approved {
 				let treasury = Self::account_id();
 				// From treasury spot
 				T::AuctionHandler::create_auction(
@@ -566,8 +1092,10 @@ impl<T: Config> Pallet<T> {
 					now,
 					ListingLevel::NetworkSpot(recent_slot.participants),
 				)?;
-				Self::deposit_event(Event::FinalizedVote(referendum_info.spot_id))
 			}
+
+			ReferendumInfoOf::<T>::insert(recent_slot.spot_id, ReferendumInfo::Finished { approved, end: now });
+			Self::deposit_event(Event::FinalizedVote(referendum_info.spot_id))
 		}
 
 		Ok(())
@@ -579,35 +1107,82 @@ impl<T: Config> Pallet<T> {
 	) -> DispatchResult {
 		for slot in slots {
 			let end = end + T::SessionDuration::get();
-			Self::start_referendum(end, slot.spot_id.clone())?;
+			Self::start_referendum(end, slot.spot_id.clone(), slot.participants)?;
 			Self::deposit_event(Event::NewContinuumReferendumStarted(end, slot.spot_id));
 		}
 		Ok(())
 	}
 
-	fn start_referendum(end: T::BlockNumber, spot_id: SpotId) -> Result<SpotId, DispatchError> {
+	fn start_referendum(
+		end: T::BlockNumber,
+		spot_id: SpotId,
+		participants: Vec<T::AccountId>,
+	) -> Result<SpotId, DispatchError> {
 		let spot = ContinuumSpots::<T>::get(spot_id);
-		let neighbors = spot.find_neighbour();
-		let mut available_neighbors: u8 = 0;
-
-		for (x, y) in neighbors {
-			if ContinuumCoordinates::<T>::contains_key((x, y)) {
-				available_neighbors = available_neighbors.checked_add(One::one()).ok_or("Overflow")?;
-			}
-		}
+		let neighbours: u8 = Self::neighbour_owners(&spot)
+			.len()
+			.try_into()
+			.map_err(|_| Error::<T>::TallyOverflow)?;
 
 		let mut status: ReferendumStatus<T::AccountId, T::BlockNumber> = ReferendumStatus {
 			end,
 			spot_id,
+			neighbours,
 			tallies: Default::default(),
 		};
 
+		for participant in participants {
+			if !status.tallies.iter().any(|tally| tally.who == participant) {
+				status.tallies.push(ContinuumSpotTally::new(participant));
+			}
+		}
+
 		let item: ReferendumInfo<T::AccountId, T::BlockNumber> = ReferendumInfo::Ongoing(status);
 		ReferendumInfoOf::<T>::insert(spot_id, item);
 		Self::deposit_event(Event::NewContinuumReferendumStarted(end, spot_id));
 		Ok(spot_id)
 	}
 
+	/// Distinct owners of the metaverses holding a spot next to the given spot, every owner gets
+	/// one vote however many neighbouring spots their metaverses hold
+	fn neighbour_owners(spot: &ContinuumSpot) -> Vec<T::AccountId> {
+		let mut owners: Vec<T::AccountId> = Self::owned_neighbour_spots(spot)
+			.into_iter()
+			.filter_map(|neighbour_spot| T::MetaverseInfoSource::get_metaverse(neighbour_spot.metaverse_id))
+			.map(|metaverse| metaverse.owner)
+			.collect();
+		owners.sort();
+		owners.dedup();
+		owners
+	}
+
+	/// Neighbouring spots of a spot that are owned by a metaverse
+	fn owned_neighbour_spots(spot: &ContinuumSpot) -> Vec<ContinuumSpot> {
+		spot.find_neighbour()
+			.into_iter()
+			.filter_map(|coordinate| ContinuumCoordinates::<T>::try_get(coordinate).ok())
+			.map(|neighbour_spot_id| ContinuumSpots::<T>::get(neighbour_spot_id))
+			.filter(|neighbour_spot| neighbour_spot.metaverse_id != 0)
+			.collect()
+	}
+
+	fn release_eoi_deposits(spot_id: SpotId, participants: &[T::AccountId]) {
+		for participant in participants {
+			if let Some(deposit) = EOIDeposits::<T>::take(spot_id, participant) {
+				T::Currency::unreserve(participant, deposit);
+			}
+		}
+	}
+
+	fn forfeit_eoi_deposit(spot_id: SpotId, who: &T::AccountId) {
+		if let Some(deposit) = EOIDeposits::<T>::take(spot_id, who) {
+			let forfeited = T::Currency::repatriate_reserved(who, &Self::account_id(), deposit, BalanceStatus::Free)
+				.map(|remaining| deposit.saturating_sub(remaining))
+				.unwrap_or_else(|_| Zero::zero());
+			Self::deposit_event(Event::EOIDepositForfeited(who.clone(), spot_id, forfeited));
+		}
+	}
+
 	fn eoi_to_auction_slots(active_session: T::BlockNumber, now: T::BlockNumber) -> DispatchResult {
 		// Get maximum desired slots
 		let desired_slots = MaxDesiredAuctionSlot::<T>::get();
@@ -616,7 +1191,7 @@ impl<T: Config> Pallet<T> {
 		// Get active EOI and add the top N to new Auction Slots
 		let mut current_eoi_slots: Vec<SpotEOI<T::AccountId>> = EOISlots::<T>::get(active_session);
 
-		current_eoi_slots.sort_by_key(|eoi_slot| eoi_slot.participants.len());
+		current_eoi_slots.sort_by(|a, b| b.participants.len().cmp(&a.participants.len()));
 		// Get highest ranked slot
 		let mut new_valid_auction_slot: Vec<AuctionSlot<T::BlockNumber, T::AccountId>> = Vec::new();
 		let highest_ranked_sorted: Vec<SpotEOI<T::AccountId>> = current_eoi_slots
@@ -624,6 +1199,10 @@ impl<T: Config> Pallet<T> {
 			.map(|x| x.clone())
 			.take(desired_slots as usize)
 			.collect::<Vec<SpotEOI<T::AccountId>>>();
+		// Slots that did not make it refund their deposits
+		for item in current_eoi_slots.iter().skip(desired_slots as usize) {
+			Self::release_eoi_deposits(item.spot_id, &item.participants);
+		}
 		// Add highest ranked EOI to New Active Auction slot
 		for (_x, item) in highest_ranked_sorted.iter().enumerate() {
 			let auction_slot = AuctionSlot {
@@ -645,59 +1224,68 @@ impl<T: Config> Pallet<T> {
 	}
 
 	fn try_vote(who: &T::AccountId, spot_id: SpotId, vote: AccountVote<T::AccountId>) -> DispatchResult {
-		let status = Self::referendum_status(spot_id)?;
+		let mut status = Self::referendum_status(spot_id)?;
+		ensure!(
+			<frame_system::Pallet<T>>::block_number() < status.end,
+			Error::<T>::ReferendumIsInValid
+		);
 
 		let spot = ContinuumSpots::<T>::get(spot_id);
-		let neighbors = spot.find_neighbour();
-		let mut is_neighbour: bool = false;
-
-		for (x, y) in neighbors {
-			// if spot exists
-			let neighbor_spot_id = ContinuumCoordinates::<T>::get((x, y));
-			let continuum_spot = ContinuumSpots::<T>::get(neighbor_spot_id);
-			if T::MetaverseInfoSource::check_ownership(&who, &continuum_spot.metaverse_id) {
-				is_neighbour = true;
-				break;
-			}
-		}
+		ensure!(Self::neighbour_owners(&spot).contains(who), Error::<T>::NoPermission);
 
-		ensure!(is_neighbour, Error::<T>::NoPermission);
+		let candidate = vote.candidate().clone();
+		let tally = status
+			.tallies
+			.iter_mut()
+			.find(|tally| tally.who == candidate)
+			.ok_or(Error::<T>::CandidateNotFound)?;
 
 		VotingOf::<T>::try_mutate(who, |maybe_voting| -> DispatchResult {
-			match maybe_voting {
-				Some(voting) => {
-					let ref mut votes = voting.votes;
-					match votes.binary_search_by_key(&spot_id, |i| i.0) {
-						// Already voted
-						Ok(_i) => {}
-						Err(i) => {
-							// Haven't vote for this spot id
-							// Add votes under user
-							let new_vote: AccountVote<T::AccountId> = vote.clone();
-							let who = new_vote.vote_who();
-							votes.insert(i, (spot_id, vote.clone()));
-
-							// Find existing tally of bidder
-							for mut tally in status.tallies {
-								// Existing vote
-								if tally.who == who.who {
-									tally.add(vote.clone()).ok_or(Error::<T>::TallyOverflow)?
-								}
-							}
-						}
-					}
+			let votes = &mut maybe_voting.get_or_insert_with(Default::default).votes;
+			// Votes are kept sorted by spot and candidate, one vote per candidate
+			match votes.binary_search_by(|(id, v)| (id, v.candidate()).cmp(&(&spot_id, &candidate))) {
+				Ok(_) => Err(Error::<T>::AlreadyVoted.into()),
+				Err(i) => {
+					tally.add(&vote).ok_or(Error::<T>::TallyOverflow)?;
+					votes.insert(i, (spot_id, vote.clone()));
+					Ok(())
 				}
-				None => {
-					// No voting exists
-					let mut new_vote: Vec<(SpotId, AccountVote<T::AccountId>)> = Vec::new();
-					new_vote.push((spot_id, vote.clone()));
-					let vote_o = Voting { votes: new_vote };
-					VotingOf::<T>::insert(who.clone(), vote_o);
+			}
+		})?;
+
+		let nay = vote.vote_who().nay;
+		ReferendumInfoOf::<T>::insert(spot_id, ReferendumInfo::Ongoing(status));
+		Self::deposit_event(Event::NeighbourVoted(who.clone(), spot_id, candidate, nay));
+		Ok(())
+	}
+
+	fn try_remove_vote(who: &T::AccountId, spot_id: SpotId, candidate: T::AccountId) -> DispatchResult {
+		VotingOf::<T>::try_mutate_exists(who, |maybe_voting| -> DispatchResult {
+			let voting = maybe_voting.as_mut().ok_or(Error::<T>::VoteNotFound)?;
+			let i = voting
+				.votes
+				.binary_search_by(|(id, v)| (id, v.candidate()).cmp(&(&spot_id, &candidate)))
+				.map_err(|_| Error::<T>::VoteNotFound)?;
+			let (_, vote) = voting.votes.remove(i);
+
+			// Finished or cancelled referendums only drop the record
+			if let Ok(mut status) = Self::referendum_status(spot_id) {
+				if <frame_system::Pallet<T>>::block_number() < status.end {
+					if let Some(tally) = status.tallies.iter_mut().find(|tally| tally.who == candidate) {
+						tally.remove(&vote).ok_or(Error::<T>::TallyOverflow)?;
+					}
+					ReferendumInfoOf::<T>::insert(spot_id, ReferendumInfo::Ongoing(status));
 				}
 			}
 
+			if voting.votes.is_empty() {
+				*maybe_voting = None;
+			}
 			Ok(())
-		})
+		})?;
+
+		Self::deposit_event(Event::NeighbourVoteRemoved(who.clone(), spot_id, candidate));
+		Ok(())
 	}
 
 	fn referendum_status(spot_id: SpotId) -> Result<ReferendumStatus<T::AccountId, T::BlockNumber>, DispatchError> {
@@ -723,39 +1311,436 @@ impl<T: Config> Pallet<T> {
 		Self::transfer_spot(spot_id, from, to)
 	}
 
-	fn check_approved(tally: &ContinuumSpotTally<T::AccountId>) -> bool {
-		let nay_ratio = tally.turnout.checked_div(tally.nays).unwrap_or(0);
-		let nay_percent = nay_ratio.checked_mul(100).unwrap_or(0);
+	fn spot_lease_duration() -> T::BlockNumber {
+		T::SessionDuration::get() * T::SpotLeaseSessions::get().into()
+	}
+
+	/// Price of renewing the lease of a spot
+	fn spot_lease_price(spot_id: SpotId) -> BalanceOf<T> {
+		let spot = ContinuumSpots::<T>::get(spot_id);
+		Self::spot_price((spot.x, spot.y))
+	}
+
+	/// Buy now price of the spot at a coordinate
+	pub fn spot_price(coordinate: (i32, i32)) -> BalanceOf<T> {
+		let base_price = SpotPrice::<T>::get();
+
+		// Chebyshev distance, so every ring around the origin shares a price
+		let max_bound = MaxBound::<T>::get();
+		let radius = (max_bound.0 as i64).abs().max((max_bound.1 as i64).abs()) as u64;
+		let distance = (coordinate.0 as i64).abs().max((coordinate.1 as i64).abs()) as u64;
+		let centrality = Perbill::from_rational(radius.saturating_sub(distance), radius.max(1));
+		let central_premium = centrality.mul_floor(base_price.saturating_mul(T::CentralSpotPremium::get().into()));
+		let price = base_price.saturating_add(central_premium);
+
+		let spot = ContinuumSpot {
+			x: coordinate.0,
+			y: coordinate.1,
+			metaverse_id: 0,
+		};
+		let neighbours = Self::owned_neighbour_spots(&spot).len() as u32;
+		price.saturating_add(
+			T::NeighbourSpotPremium::get()
+				.mul_floor(price)
+				.saturating_mul(neighbours.into()),
+		)
+	}
+
+	/// Raise the base price once buy now sales pass the session target
+	fn record_spot_sale() {
+		let sales = SessionSpotSales::<T>::mutate(|sales| {
+			*sales = sales.saturating_add(1);
+			*sales
+		});
+
+		if sales > T::TargetSpotSalesPerSession::get() {
+			let base_price = SpotPrice::<T>::get();
+			let new_price = base_price.saturating_add(T::SpotPriceAdjustment::get().mul_ceil(base_price));
+			SpotPrice::<T>::set(new_price);
+			Self::deposit_event(Event::SpotPriceUpdated(new_price));
+		}
+	}
+
+	/// Lower the base price by the share of the session target that was not sold
+	fn adjust_spot_price() {
+		let sales = SessionSpotSales::<T>::take();
+		let target = T::TargetSpotSalesPerSession::get();
+		if sales >= target {
+			return;
+		}
+
+		let shortfall = Perbill::from_rational(target - sales, target);
+		let base_price = SpotPrice::<T>::get();
+		let decrease = T::SpotPriceAdjustment::get().mul_floor(shortfall.mul_floor(base_price));
+		let new_price = base_price.saturating_sub(decrease).max(T::MinimumSpotPrice::get());
+		if new_price != base_price {
+			SpotPrice::<T>::set(new_price);
+			Self::deposit_event(Event::SpotPriceUpdated(new_price));
+		}
+	}
+
+	/// Move the lease expiry of a spot, keeping the expiry index in sync
+	fn set_spot_lease_expiry(spot_id: SpotId, expiry: T::BlockNumber) {
+		if let Some(previous_expiry) = SpotLeaseExpiry::<T>::get(spot_id) {
+			SpotLeaseExpiries::<T>::remove(previous_expiry, spot_id);
+		}
+		SpotLeaseExpiry::<T>::insert(spot_id, expiry);
+		SpotLeaseExpiries::<T>::insert(expiry, spot_id, ());
+	}
+
+	/// Lease of a spot leaving the treasury runs to the end of the current session plus the lease
+	/// sessions, so it always expires on a session rotation
+	fn start_spot_lease(spot_id: SpotId) {
+		let expiry = CurrentIndex::<T>::get() + T::SessionDuration::get() + Self::spot_lease_duration();
+		Self::set_spot_lease_expiry(spot_id, expiry);
+	}
+
+	/// Expire the leases due at past session rotations, at most `MaxSpotLeaseExpiriesPerBlock` of
+	/// them, and return the weight used
+	fn expire_spot_leases() -> Weight {
+		let mut due_expiries = DueSpotLeaseExpiries::<T>::get();
+		if due_expiries.is_empty() {
+			return T::DbWeight::get().reads(1);
+		}
+
+		let max_expiries = T::MaxSpotLeaseExpiriesPerBlock::get() as usize;
+		let mut expired: usize = 0;
+		let mut exhausted: usize = 0;
+		for expiry in due_expiries.iter() {
+			let spot_ids: Vec<SpotId> = SpotLeaseExpiries::<T>::iter_prefix(expiry)
+				.map(|(spot_id, _)| spot_id)
+				.take(max_expiries - expired + 1)
+				.collect();
+			let has_more = spot_ids.len() > max_expiries - expired;
+
+			for spot_id in spot_ids.into_iter().take(max_expiries - expired) {
+				SpotLeaseExpiries::<T>::remove(expiry, spot_id);
+				Self::expire_spot_lease(spot_id, *expiry);
+				expired += 1;
+			}
+
+			if has_more {
+				break;
+			}
+			exhausted += 1;
+		}
+
+		if exhausted > 0 {
+			due_expiries.drain(..exhausted);
+			DueSpotLeaseExpiries::<T>::put(due_expiries);
+		}
+
+		let expired = expired as u64;
+		T::DbWeight::get().reads_writes(2 + exhausted as u64 + 4 * expired, 1 + 4 * expired)
+	}
+
+	/// Return the spot of an unrenewed lease to the treasury
+	fn expire_spot_lease(spot_id: SpotId, expiry: T::BlockNumber) {
+		let metaverse_id = ContinuumSpots::<T>::get(spot_id).metaverse_id;
+		// Listed spots expire at the next rotation instead
+		if T::AuctionHandler::check_item_in_auction(ItemId::Spot(spot_id, metaverse_id)) {
+			Self::set_spot_lease_expiry(spot_id, expiry + T::SessionDuration::get());
+			return;
+		}
+
+		SpotLeaseExpiry::<T>::remove(spot_id);
+		ContinuumSpots::<T>::mutate(spot_id, |spot| spot.metaverse_id = 0);
+		Self::update_occupied_spots(metaverse_id, 0);
+		Self::deposit_event(Event::SpotLeaseExpired(spot_id, metaverse_id));
+	}
 
-		nay_percent > 51
+	/// Keep the occupied spot count in sync when a spot changes metaverse
+	fn update_occupied_spots(previous_metaverse_id: MetaverseId, new_metaverse_id: MetaverseId) {
+		match (previous_metaverse_id == 0, new_metaverse_id == 0) {
+			(true, false) => OccupiedSpots::<T>::mutate(|count| *count = count.saturating_add(1)),
+			(false, true) => OccupiedSpots::<T>::mutate(|count| *count = count.saturating_sub(1)),
+			_ => {}
+		}
+	}
+
+	/// Grow the max bound by a ring once occupancy reaches the expansion threshold, the spots of
+	/// the ring are released by `release_ring_spots` from the next block on
+	fn expand_map_if_needed() {
+		let threshold = match MapExpansionThreshold::<T>::get() {
+			Some(threshold) => threshold,
+			None => return,
+		};
+		// The previous ring is still being released
+		if SpotReleaseProgress::<T>::exists() {
+			return;
+		}
+
+		let max_bound = MaxBound::<T>::get();
+		if max_bound.1 < max_bound.0 {
+			return;
+		}
+		let side = (max_bound.1 as i64 - max_bound.0 as i64 + 1) as u64;
+		let occupancy = Perbill::from_rational(OccupiedSpots::<T>::get() as u64, side.saturating_mul(side));
+		if occupancy < threshold {
+			return;
+		}
+
+		let new_bound = match (max_bound.0.checked_sub(1), max_bound.1.checked_add(1)) {
+			(Some(lower), Some(upper)) => (lower, upper),
+			_ => return,
+		};
+
+		MaxBound::<T>::set(new_bound);
+		SpotReleaseProgress::<T>::put((new_bound, 0));
+		let ring_spots = (4 * (new_bound.1 as i64 - new_bound.0 as i64)).min(u32::MAX as i64) as u32;
+		Self::deposit_event(Event::ContinuumMapExpanded(new_bound, ring_spots));
+	}
+
+	/// Release the next spots of the expanded ring in spiral order, at most
+	/// `MaxSpotReleasesPerBlock` of them, and return the weight used. Spots already created within
+	/// the new bound are skipped.
+	fn release_ring_spots() -> Weight {
+		let (bound, mut next_index) = match SpotReleaseProgress::<T>::get() {
+			Some(progress) => progress,
+			None => return T::DbWeight::get().reads(1),
+		};
+
+		let mut checked: u64 = 0;
+		let mut released: u64 = 0;
+		while checked < T::MaxSpotReleasesPerBlock::get() as u64 {
+			let coordinate = match spiral_ring_coordinate(bound, next_index) {
+				Some(coordinate) => coordinate,
+				None => break,
+			};
+			if !ContinuumCoordinates::<T>::contains_key(coordinate) && Self::create_spot(coordinate).is_ok() {
+				released += 1;
+			}
+			checked += 1;
+			next_index = next_index.saturating_add(1);
+		}
+
+		if spiral_ring_coordinate(bound, next_index).is_some() {
+			SpotReleaseProgress::<T>::put((bound, next_index));
+		} else {
+			SpotReleaseProgress::<T>::kill();
+		}
+
+		T::DbWeight::get().reads_writes(1 + checked + released, 1 + 3 * released)
+	}
+
+	fn ensure_within_bound(coordinate: (i32, i32)) -> DispatchResult {
+		let max_bound = MaxBound::<T>::get();
+		ensure!(
+			(coordinate.0 >= max_bound.0 && max_bound.1 >= coordinate.0)
+				&& (coordinate.1 >= max_bound.0 && max_bound.1 >= coordinate.1),
+			Error::<T>::SpotIsOutOfBound
+		);
+		Ok(())
+	}
+
+	/// Spot held by a metaverse of the sender that is not listed in an auction
+	fn ensure_spot_holder(who: &T::AccountId, spot_id: SpotId) -> Result<ContinuumSpot, DispatchError> {
+		let spot = ContinuumSpots::<T>::try_get(spot_id).map_err(|_| Error::<T>::ContinuumSpotNotFound)?;
+		ensure!(
+			spot.metaverse_id != 0 && T::MetaverseInfoSource::check_ownership(who, &spot.metaverse_id),
+			Error::<T>::NoPermission
+		);
+		Self::ensure_spot_not_in_auction(spot_id, &spot)?;
+		Ok(spot)
+	}
+
+	fn ensure_spot_not_in_auction(spot_id: SpotId, spot: &ContinuumSpot) -> DispatchResult {
+		ensure!(
+			!T::AuctionHandler::check_item_in_auction(ItemId::Spot(spot_id, spot.metaverse_id)),
+			Error::<T>::SpotIsInAuction
+		);
+		Ok(())
+	}
+
+	/// Treasury spot that is not going through the EOI, GNP or auction process
+	fn is_spot_free(spot_id: SpotId) -> bool {
+		let current_session = CurrentIndex::<T>::get();
+		let in_eoi = EOISlots::<T>::get(current_session)
+			.iter()
+			.any(|eoi| eoi.spot_id == spot_id);
+		let in_auction_slot = ActiveAuctionSlots::<T>::get(current_session)
+			.unwrap_or_default()
+			.iter()
+			.any(|slot| slot.spot_id == spot_id);
+		let in_gnp = matches!(ReferendumInfoOf::<T>::get(spot_id), Some(ReferendumInfo::Ongoing(_)));
+
+		ContinuumSpots::<T>::get(spot_id).metaverse_id == 0
+			&& !in_eoi
+			&& !in_auction_slot
+			&& !in_gnp
+			&& !T::AuctionHandler::check_item_in_auction(ItemId::Spot(spot_id, 0))
+	}
+
+	fn move_spot(spot_id: SpotId, coordinate: (i32, i32)) {
+		ContinuumSpots::<T>::mutate(spot_id, |spot| {
+			spot.x = coordinate.0;
+			spot.y = coordinate.1;
+		});
+		ContinuumCoordinates::<T>::insert(coordinate, spot_id);
+	}
+
+	/// Metaverses holding spots next to any of the coordinates
+	fn neighbour_metaverses(coordinates: &[(i32, i32)], excluded: &[MetaverseId]) -> Vec<MetaverseId> {
+		let mut metaverses: Vec<MetaverseId> = Vec::new();
+		for coordinate in coordinates {
+			let spot = ContinuumSpot {
+				x: coordinate.0,
+				y: coordinate.1,
+				metaverse_id: 0,
+			};
+			for neighbour_spot in Self::owned_neighbour_spots(&spot) {
+				if !excluded.contains(&neighbour_spot.metaverse_id)
+					&& !metaverses.contains(&neighbour_spot.metaverse_id)
+				{
+					metaverses.push(neighbour_spot.metaverse_id);
+				}
+			}
+		}
+		metaverses
+	}
+
+	fn are_neighbours(spot: &ContinuumSpot, other_spot: &ContinuumSpot) -> bool {
+		spot.find_neighbour().contains(&(other_spot.x, other_spot.y))
+	}
+
+	fn portal_key(metaverse_id: MetaverseId, other_metaverse_id: MetaverseId) -> (MetaverseId, MetaverseId) {
+		if metaverse_id <= other_metaverse_id {
+			(metaverse_id, other_metaverse_id)
+		} else {
+			(other_metaverse_id, metaverse_id)
+		}
+	}
+
+	/// Portal between two metaverses, unless its spots moved apart or left the metaverses since it
+	/// opened
+	fn open_portal(lower: MetaverseId, upper: MetaverseId) -> Option<ContinuumPortal> {
+		Portals::<T>::get(lower, upper).filter(Self::is_portal_linked)
+	}
+
+	/// Whether the spots of a portal are still neighbours held by the linked metaverses
+	fn is_portal_linked(portal: &ContinuumPortal) -> bool {
+		let spot = ContinuumSpots::<T>::get(portal.spots.0);
+		let other_spot = ContinuumSpots::<T>::get(portal.spots.1);
+		spot.metaverse_id == portal.metaverses.0
+			&& other_spot.metaverse_id == portal.metaverses.1
+			&& Self::are_neighbours(&spot, &other_spot)
+			&& T::MetaverseInfoSource::get_metaverse(portal.metaverses.0).is_some()
+			&& T::MetaverseInfoSource::get_metaverse(portal.metaverses.1).is_some()
+	}
+
+	/// All open portals between metaverses, portals whose spots moved apart or left the linked
+	/// metaverses are left out
+	pub fn portals() -> Vec<ContinuumPortal> {
+		Portals::<T>::iter_values().filter(Self::is_portal_linked).collect()
+	}
+
+	/// Spots of a map region for rendering, clipped to the max bound. Nothing is returned when the
+	/// clipped region is larger than `MAX_MAP_QUERY_AREA`.
+	pub fn map_region(
+		bottom_left: (i32, i32),
+		top_right: (i32, i32),
+	) -> Vec<ContinuumSpotInfo<T::AccountId, BalanceOf<T>, T::BlockNumber>> {
+		let max_bound = MaxBound::<T>::get();
+		let (min_x, max_x) = (bottom_left.0.max(max_bound.0), top_right.0.min(max_bound.1));
+		let (min_y, max_y) = (bottom_left.1.max(max_bound.0), top_right.1.min(max_bound.1));
+		if min_x > max_x || min_y > max_y {
+			return Vec::new();
+		}
+		let width = (max_x as i64) - (min_x as i64) + 1;
+		let height = (max_y as i64) - (min_y as i64) + 1;
+		if width.saturating_mul(height) > MAX_MAP_QUERY_AREA as i64 {
+			return Vec::new();
+		}
+
+		let current_session = CurrentIndex::<T>::get();
+		let eoi_spots: Vec<SpotId> = EOISlots::<T>::get(current_session)
+			.iter()
+			.map(|eoi| eoi.spot_id)
+			.collect();
+		let auction_slot_spots: Vec<SpotId> = ActiveAuctionSlots::<T>::get(current_session)
+			.unwrap_or_default()
+			.iter()
+			.map(|slot| slot.spot_id)
+			.collect();
+
+		let mut spots = Vec::new();
+		for x in min_x..=max_x {
+			for y in min_y..=max_y {
+				let coordinate = (x, y);
+				let spot_id = ContinuumCoordinates::<T>::try_get(coordinate).ok();
+				let mut metaverse_id = None;
+				let mut referendum = None;
+				let mut status = SpotStatus::Free;
+
+				if let Some(spot_id) = spot_id {
+					let spot = ContinuumSpots::<T>::get(spot_id);
+					if spot.metaverse_id != 0 {
+						metaverse_id = Some(spot.metaverse_id);
+					}
+
+					if let Ok(referendum_status) = Self::referendum_status(spot_id) {
+						status = SpotStatus::GoodNeighbourhoodProtocol;
+						referendum = Some(SpotReferendum {
+							end: referendum_status.end,
+							neighbours: referendum_status.neighbours,
+							tallies: referendum_status
+								.tallies
+								.into_iter()
+								.map(|tally| SpotReferendumTally {
+									who: tally.who,
+									nays: tally.nays,
+									turnout: tally.turnout,
+								})
+								.collect(),
+						});
+					} else if T::AuctionHandler::check_item_in_auction(ItemId::Spot(spot_id, spot.metaverse_id)) {
+						status = SpotStatus::InAuction;
+					} else if eoi_spots.contains(&spot_id) || auction_slot_spots.contains(&spot_id) {
+						status = SpotStatus::ExpressionOfInterest;
+					} else if metaverse_id.is_some() {
+						status = SpotStatus::Occupied;
+					}
+				}
+
+				spots.push(ContinuumSpotInfo {
+					coordinate,
+					spot_id,
+					metaverse_id,
+					status,
+					price: Self::spot_price(coordinate),
+					referendum,
+				});
+			}
+		}
+		spots
+	}
+
+	/// Insert a treasury held spot at the coordinate
+	fn create_spot(coordinate: (i32, i32)) -> Result<SpotId, DispatchError> {
+		let spot = ContinuumSpot {
+			x: coordinate.0,
+			y: coordinate.1,
+			metaverse_id: 0,
+		};
+
+		let next_spot_id = NextContinuumSpotId::<T>::try_mutate(|id| -> Result<SpotId, DispatchError> {
+			let current_id = *id;
+			*id = id.checked_add(One::one()).ok_or(Error::<T>::SpotIsNotAvailable)?;
+
+			Ok(current_id)
+		})?;
+		ContinuumSpots::<T>::insert(next_spot_id, spot);
+		ContinuumCoordinates::<T>::insert(coordinate, next_spot_id);
+		Ok(next_spot_id)
 	}
 
 	fn check_spot_ownership(spot_id: Option<SpotId>, coordinate: (i32, i32)) -> Result<SpotId, DispatchError> {
 		match spot_id {
 			None => {
 				// Insert continuum spot as it's empty
-				let max_bound = MaxBound::<T>::get();
-				ensure!(
-					(coordinate.0 >= max_bound.0 && max_bound.1 >= coordinate.0)
-						&& (coordinate.1 >= max_bound.0 && max_bound.1 >= coordinate.1),
-					Error::<T>::SpotIsOutOfBound
-				);
-
-				let spot = ContinuumSpot {
-					x: coordinate.0,
-					y: coordinate.1,
-					metaverse_id: 0,
-				};
-
-				let next_spot_id = NextContinuumSpotId::<T>::try_mutate(|id| -> Result<SpotId, DispatchError> {
-					let current_id = *id;
-					*id = id.checked_add(One::one()).ok_or(Error::<T>::SpotIsNotAvailable)?;
-
-					Ok(current_id)
-				})?;
-				ContinuumSpots::<T>::insert(next_spot_id, spot);
-				ContinuumCoordinates::<T>::insert(coordinate, next_spot_id);
-				Ok(next_spot_id)
+				Self::ensure_within_bound(coordinate)?;
+
+				Self::create_spot(coordinate)
 			}
 			Some(spot_id) => {
 				let spot = ContinuumSpots::<T>::get(spot_id);
@@ -764,6 +1749,78 @@ impl<T: Config> Pallet<T> {
 			}
 		}
 	}
+
+	/// Record the electorate of referendums that started before it was kept with them
+	pub fn upgrade_referendum_electorate_v1() -> Weight {
+		if StorageVersion::get::<Pallet<T>>() >= 1 {
+			return 0;
+		}
+
+		let mut reads: u64 = 0;
+		let mut writes: u64 = 1;
+		ReferendumInfoOf::<T>::translate::<migration_v1::ReferendumInfo<T::AccountId, T::BlockNumber>, _>(|_, info| {
+			reads += 1;
+			writes += 1;
+			let info = match info {
+				migration_v1::ReferendumInfo::Ongoing(status) => {
+					// The spot, its neighbouring spots and their metaverses
+					reads += 25;
+					let spot = ContinuumSpots::<T>::get(status.spot_id);
+					ReferendumInfo::Ongoing(ReferendumStatus {
+						end: status.end,
+						spot_id: status.spot_id,
+						neighbours: Self::neighbour_owners(&spot).len() as u8,
+						tallies: status.tallies,
+					})
+				}
+				migration_v1::ReferendumInfo::Finished { approved, end } => ReferendumInfo::Finished { approved, end },
+			};
+			Some(info)
+		});
+
+		StorageVersion::new(1).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+
+	/// Start a lease for the spots that were held before spots were leased
+	pub fn upgrade_spot_leases_v2() -> Weight {
+		if StorageVersion::get::<Pallet<T>>() >= 2 {
+			return 0;
+		}
+
+		let mut reads: u64 = 1;
+		let mut writes: u64 = 1;
+		for (spot_id, spot) in ContinuumSpots::<T>::iter() {
+			reads += 2;
+			if spot.metaverse_id != 0 && !SpotLeaseExpiry::<T>::contains_key(spot_id) {
+				Self::start_spot_lease(spot_id);
+				writes += 2;
+			}
+		}
+
+		StorageVersion::new(2).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+
+	/// Count the spots that were held before the occupied spots were counted
+	pub fn upgrade_occupied_spots_v3() -> Weight {
+		if StorageVersion::get::<Pallet<T>>() >= 3 {
+			return 0;
+		}
+
+		let mut reads: u64 = 0;
+		let occupied_spots = ContinuumSpots::<T>::iter()
+			.inspect(|_| reads += 1)
+			.filter(|(_, spot)| spot.metaverse_id != 0)
+			.count() as u32;
+		OccupiedSpots::<T>::put(occupied_spots);
+
+		StorageVersion::new(3).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(reads, 2)
+	}
 }
 
 impl<T: Config> Continuum<T::AccountId> for Pallet<T> {
@@ -785,8 +1842,15 @@ impl<T: Config> Continuum<T::AccountId> for Pallet<T> {
 				)
 			}
 			let mut spot = maybe_spot;
+			Self::update_occupied_spots(spot.metaverse_id, to.1);
 			spot.metaverse_id = to.1;
 			Ok(spot_id)
-		})
+		})?;
+
+		// Spots leaving the treasury start a new lease
+		if *from == Self::account_id() {
+			Self::start_spot_lease(spot_id);
+		}
+		Ok(spot_id)
 	}
 }
@@ -22,9 +22,11 @@ use frame_support::{construct_runtime, ord_parameter_types, parameter_types, Pal
 use frame_system::EnsureSignedBy;
 use sp_core::H256;
 use sp_runtime::{testing::Header, traits::IdentityLookup};
+use sp_std::cell::RefCell;
+use sp_std::collections::btree_map::BTreeMap;
 
 use auction_manager::{Auction, AuctionInfo, CheckAuctionItemHandler};
-use core_primitives::{MetaverseInfo, MetaverseTrait};
+use core_primitives::{MetaverseInfo, MetaverseRole, MetaverseTrait};
 use primitives::FungibleTokenId;
 
 use crate as continuum;
@@ -177,22 +179,56 @@ parameter_types! {
 	pub const SessionDuration: BlockNumber = 10;
 	// Default 43200 Blocks
 	pub const SpotAuctionChillingDuration: BlockNumber = 10;
+	pub const NeighbourBanThreshold: Perbill = Perbill::from_percent(50);
+	pub const SpotLeaseSessions: u32 = 2;
+	pub const MaxSpotLeaseExpiriesPerBlock: u32 = 1;
+	pub const MaxSpotReleasesPerBlock: u32 = 10;
+	pub const SpotPriceAdjustment: Perbill = Perbill::from_percent(10);
+	pub const TargetSpotSalesPerSession: u32 = 1;
+	pub const CentralSpotPremium: u32 = 9;
+	pub const NeighbourSpotPremium: Perbill = Perbill::from_percent(10);
+	pub const MinimumSpotPrice: Balance = 90;
+	pub const SpotRelocationFee: Balance = 10;
+	pub const EOIDeposit: Balance = 50;
+	pub const MaxPortalMetadata: u32 = 32;
+}
+
+thread_local! {
+	/// Owners of the metaverses transferred during a test
+	static TRANSFERRED_METAVERSES: RefCell<BTreeMap<MetaverseId, AccountId>> = RefCell::new(BTreeMap::new());
+}
+
+fn metaverse_owner(metaverse_id: MetaverseId) -> Option<AccountId> {
+	if let Some(owner) = TRANSFERRED_METAVERSES.with(|owners| owners.borrow().get(&metaverse_id).cloned()) {
+		return Some(owner);
+	}
+	match metaverse_id {
+		ALICE_METAVERSE_ID => Some(ALICE),
+		BOB_METAVERSE_ID => Some(BOB),
+		CHARLIE_METAVERSE_ID => Some(CHARLIE),
+		_ => None,
+	}
 }
 
 pub struct MetaverseInfoSource {}
 
 impl MetaverseTrait<AccountId> for MetaverseInfoSource {
 	fn check_ownership(who: &AccountId, metaverse_id: &MetaverseId) -> bool {
-		match *who {
-			ALICE => *metaverse_id == ALICE_METAVERSE_ID,
-			BOB => *metaverse_id == BOB_METAVERSE_ID,
-			CHARLIE => *metaverse_id == CHARLIE_METAVERSE_ID,
-			_ => false,
-		}
+		metaverse_owner(*metaverse_id) == Some(*who)
 	}
 
-	fn get_metaverse(_metaverse_id: u64) -> Option<MetaverseInfo<u128>> {
-		None
+	fn check_role(who: &AccountId, metaverse_id: &MetaverseId, _role: &MetaverseRole) -> bool {
+		Self::check_ownership(who, metaverse_id)
+	}
+
+	fn get_metaverse(metaverse_id: u64) -> Option<MetaverseInfo<u128>> {
+		let owner = metaverse_owner(metaverse_id)?;
+		Some(MetaverseInfo {
+			owner,
+			metadata: vec![],
+			currency_id: FungibleTokenId::NativeToken(0),
+			is_frozen: false,
+		})
 	}
 
 	fn get_metaverse_token(_metaverse_id: u64) -> Option<FungibleTokenId> {
@@ -202,6 +238,11 @@ impl MetaverseTrait<AccountId> for MetaverseInfoSource {
 	fn update_metaverse_token(_metaverse_id: u64, _currency_id: FungibleTokenId) -> Result<(), DispatchError> {
 		Ok(())
 	}
+
+	fn transfer_metaverse(_from: &AccountId, to: &AccountId, metaverse_id: u64) -> Result<(), DispatchError> {
+		TRANSFERRED_METAVERSES.with(|owners| owners.borrow_mut().insert(metaverse_id, *to));
+		Ok(())
+	}
 }
 
 impl Config for Runtime {
@@ -214,6 +255,18 @@ impl Config for Runtime {
 	type ContinuumTreasury = ContinuumTreasuryPalletId;
 	type Currency = Balances;
 	type MetaverseInfoSource = MetaverseInfoSource;
+	type NeighbourBanThreshold = NeighbourBanThreshold;
+	type SpotLeaseSessions = SpotLeaseSessions;
+	type MaxSpotLeaseExpiriesPerBlock = MaxSpotLeaseExpiriesPerBlock;
+	type MaxSpotReleasesPerBlock = MaxSpotReleasesPerBlock;
+	type SpotPriceAdjustment = SpotPriceAdjustment;
+	type TargetSpotSalesPerSession = TargetSpotSalesPerSession;
+	type CentralSpotPremium = CentralSpotPremium;
+	type NeighbourSpotPremium = NeighbourSpotPremium;
+	type MinimumSpotPrice = MinimumSpotPrice;
+	type SpotRelocationFee = SpotRelocationFee;
+	type EOIDeposit = EOIDeposit;
+	type MaxPortalMetadata = MaxPortalMetadata;
 }
 
 pub type ContinuumModule = Pallet<Runtime>;
@@ -247,6 +300,8 @@ impl ExtBuilder {
 	}
 
 	pub fn build_with_block_number(self, block_number: u64) -> sp_io::TestExternalities {
+		TRANSFERRED_METAVERSES.with(|owners| owners.borrow_mut().clear());
+
 		let mut t = frame_system::GenesisConfig::default()
 			.build_storage::<Runtime>()
 			.unwrap();
@@ -21,6 +21,7 @@ use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::BlockNumber as MBlockNumber;
 use mock::{Event, *};
+use primitives::continuum::{ContinuumPortal, SpotReferendumTally, SpotStatus};
 
 #[test]
 fn find_neighborhood_spot_should_work() {
@@ -374,3 +375,912 @@ fn buy_now_continuum_should_fail_if_buy_now_setting_is_disabled() {
 		);
 	})
 }
+
+fn start_gnp_with_charlie_as_neighbour() {
+	// Alice & Bob register for slot 0,0 and Charlie owns the neighbouring spot -1,1
+	assert_ok!(ContinuumModule::register_interest(
+		Origin::signed(ALICE),
+		ALICE_METAVERSE_ID,
+		(0, 0)
+	));
+	assert_ok!(ContinuumModule::register_interest(
+		Origin::signed(BOB),
+		BOB_METAVERSE_ID,
+		(0, 0)
+	));
+	assert_ok!(ContinuumModule::set_allow_buy_now(Origin::root(), true));
+	assert_ok!(ContinuumModule::buy_continuum_spot(
+		Origin::signed(CHARLIE),
+		(-1, 1),
+		CHARLIE_METAVERSE_ID
+	));
+	run_to_block(20);
+}
+
+fn nay_on(who: AccountId) -> AccountVote<AccountId> {
+	AccountVote::Standard {
+		vote: Vote { nay: true, who },
+	}
+}
+
+#[test]
+fn spot_tally_result_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let threshold = sp_runtime::Perbill::from_percent(50);
+		let mut tally = ContinuumSpotTally::new(ALICE);
+		// No electorate and no votes should not ban
+		assert!(!tally.result(0, threshold));
+
+		assert_eq!(tally.add(&nay_on(ALICE)), Some(()));
+		assert!(tally.result(1, threshold));
+		assert!(!tally.result(2, threshold));
+		// Turnout above the recorded electorate still bans
+		assert!(tally.result(0, threshold));
+
+		assert_eq!(tally.remove(&nay_on(ALICE)), Some(()));
+		assert_eq!(tally.nays, 0);
+		assert_eq!(tally.turnout, 0);
+		assert_eq!(tally.remove(&nay_on(ALICE)), None);
+	})
+}
+
+#[test]
+fn gnp_vote_should_tally_neighbour_votes_and_finalize() {
+	ExtBuilder::default().build().execute_with(|| {
+		start_gnp_with_charlie_as_neighbour();
+
+		let status = ContinuumModule::referendum_status(0).unwrap();
+		assert_eq!(status.neighbours, 1);
+		assert_eq!(status.tallies.len(), 2);
+
+		assert_ok!(ContinuumModule::vote(Origin::signed(CHARLIE), 0, nay_on(ALICE)));
+		assert_eq!(
+			last_event(),
+			Event::Continuum(crate::Event::NeighbourVoted(CHARLIE, 0, ALICE, true))
+		);
+		assert_ok!(ContinuumModule::vote(
+			Origin::signed(CHARLIE),
+			0,
+			AccountVote::Standard {
+				vote: Vote { nay: false, who: BOB }
+			}
+		));
+
+		assert_noop!(
+			ContinuumModule::vote(Origin::signed(CHARLIE), 0, nay_on(ALICE)),
+			Error::<Runtime>::AlreadyVoted
+		);
+		assert_noop!(
+			ContinuumModule::vote(Origin::signed(CHARLIE), 0, nay_on(CHARLIE)),
+			Error::<Runtime>::CandidateNotFound
+		);
+		assert_noop!(
+			ContinuumModule::vote(Origin::signed(BOB), 0, nay_on(ALICE)),
+			Error::<Runtime>::NoPermission
+		);
+
+		let status = ContinuumModule::referendum_status(0).unwrap();
+		let threshold = sp_runtime::Perbill::from_percent(50);
+		let alice_tally = status.tallies.iter().find(|t| t.who == ALICE).unwrap();
+		assert_eq!((alice_tally.nays, alice_tally.turnout), (1, 1));
+		assert!(alice_tally.result(status.neighbours, threshold));
+		let bob_tally = status.tallies.iter().find(|t| t.who == BOB).unwrap();
+		assert_eq!((bob_tally.nays, bob_tally.turnout), (0, 1));
+		assert!(!bob_tally.result(status.neighbours, threshold));
+		assert_eq!(ContinuumModule::get_voting_info(CHARLIE).unwrap().votes.len(), 2);
+
+		run_to_block(30);
+		assert_eq!(
+			ContinuumModule::get_continuum_referendum(0),
+			Some(ReferendumInfo::Finished {
+				approved: true,
+				end: 30
+			})
+		);
+		assert_noop!(
+			ContinuumModule::vote(Origin::signed(CHARLIE), 0, nay_on(BOB)),
+			Error::<Runtime>::ReferendumIsInValid
+		);
+	})
+}
+
+#[test]
+fn gnp_electorate_should_count_each_neighbour_owner_once() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(ContinuumModule::set_allow_buy_now(Origin::root(), true));
+		assert_ok!(ContinuumModule::buy_continuum_spot(
+			Origin::signed(CHARLIE),
+			(1, 1),
+			CHARLIE_METAVERSE_ID
+		));
+		start_gnp_with_charlie_as_neighbour();
+		let spot_id = ContinuumModule::get_continuum_position((0, 0));
+
+		// Charlie holds two neighbouring spots but only has a single vote
+		let status = ContinuumModule::referendum_status(spot_id).unwrap();
+		assert_eq!(status.neighbours, 1);
+
+		assert_ok!(ContinuumModule::vote(Origin::signed(CHARLIE), spot_id, nay_on(ALICE)));
+		assert_noop!(
+			ContinuumModule::vote(Origin::signed(CHARLIE), spot_id, nay_on(ALICE)),
+			Error::<Runtime>::AlreadyVoted
+		);
+
+		let status = ContinuumModule::referendum_status(spot_id).unwrap();
+		let alice_tally = status.tallies.iter().find(|t| t.who == ALICE).unwrap();
+		assert!(alice_tally.result(status.neighbours, sp_runtime::Perbill::from_percent(50)));
+	})
+}
+
+#[test]
+fn remove_vote_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		start_gnp_with_charlie_as_neighbour();
+
+		assert_noop!(
+			ContinuumModule::remove_vote(Origin::signed(CHARLIE), 0, ALICE),
+			Error::<Runtime>::VoteNotFound
+		);
+
+		assert_ok!(ContinuumModule::vote(Origin::signed(CHARLIE), 0, nay_on(ALICE)));
+		assert_ok!(ContinuumModule::remove_vote(Origin::signed(CHARLIE), 0, ALICE));
+		assert_eq!(
+			last_event(),
+			Event::Continuum(crate::Event::NeighbourVoteRemoved(CHARLIE, 0, ALICE))
+		);
+		assert_eq!(ContinuumModule::get_voting_info(CHARLIE), None);
+		let status = ContinuumModule::referendum_status(0).unwrap();
+		let alice_tally = status.tallies.iter().find(|t| t.who == ALICE).unwrap();
+		assert_eq!((alice_tally.nays, alice_tally.turnout), (0, 0));
+
+		// Removing after the referendum ended only drops the vote record
+		assert_ok!(ContinuumModule::vote(Origin::signed(CHARLIE), 0, nay_on(ALICE)));
+		run_to_block(30);
+		assert_ok!(ContinuumModule::remove_vote(Origin::signed(CHARLIE), 0, ALICE));
+		assert_eq!(ContinuumModule::get_voting_info(CHARLIE), None);
+	})
+}
+
+#[test]
+fn spot_lease_should_expire_and_return_spot_to_treasury() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(ContinuumModule::set_allow_buy_now(Origin::root(), true));
+		assert_ok!(ContinuumModule::buy_continuum_spot(
+			Origin::signed(CHARLIE),
+			(-1, 1),
+			CHARLIE_METAVERSE_ID
+		));
+		// Remainder of the first session plus two lease sessions
+		assert_eq!(ContinuumModule::get_spot_lease_expiry(0), Some(30));
+		assert_eq!(
+			ContinuumModule::get_continuum_spot(0).metaverse_id,
+			CHARLIE_METAVERSE_ID
+		);
+
+		run_to_block(29);
+		assert_eq!(
+			ContinuumModule::get_continuum_spot(0).metaverse_id,
+			CHARLIE_METAVERSE_ID
+		);
+
+		run_to_block(30);
+		assert_eq!(ContinuumModule::get_continuum_spot(0).metaverse_id, 0);
+		assert_eq!(ContinuumModule::get_spot_lease_expiry(0), None);
+		assert!(System::events()
+			.iter()
+			.any(|r| r.event == Event::Continuum(crate::Event::SpotLeaseExpired(0, CHARLIE_METAVERSE_ID))));
+
+		// Spot re-enters the EOI cycle
+		assert_ok!(ContinuumModule::register_interest(
+			Origin::signed(ALICE),
+			ALICE_METAVERSE_ID,
+			(-1, 1)
+		));
+	})
+}
+
+#[test]
+fn spot_leases_should_expire_in_bounded_batches() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(ContinuumModule::set_allow_buy_now(Origin::root(), true));
+		assert_ok!(ContinuumModule::buy_continuum_spot(
+			Origin::signed(CHARLIE),
+			(-1, 1),
+			CHARLIE_METAVERSE_ID
+		));
+		assert_ok!(ContinuumModule::buy_continuum_spot(
+			Origin::signed(BOB),
+			(1, 1),
+			BOB_METAVERSE_ID
+		));
+		assert_eq!(ContinuumModule::get_spot_lease_expiry(0), Some(30));
+		assert_eq!(ContinuumModule::get_spot_lease_expiry(1), Some(30));
+
+		// One lease expires a block
+		run_to_block(30);
+		let held_spots = (0..2)
+			.filter(|spot_id| ContinuumModule::get_continuum_spot(spot_id).metaverse_id != 0)
+			.count();
+		assert_eq!(held_spots, 1);
+		assert_eq!(DueSpotLeaseExpiries::<Runtime>::get(), vec![30]);
+
+		run_to_block(31);
+		assert_eq!(ContinuumModule::get_continuum_spot(0).metaverse_id, 0);
+		assert_eq!(ContinuumModule::get_continuum_spot(1).metaverse_id, 0);
+		assert!(DueSpotLeaseExpiries::<Runtime>::get().is_empty());
+	})
+}
+
+#[test]
+fn renew_spot_lease_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(ContinuumModule::set_allow_buy_now(Origin::root(), true));
+		assert_ok!(ContinuumModule::buy_continuum_spot(
+			Origin::signed(CHARLIE),
+			(-1, 1),
+			CHARLIE_METAVERSE_ID
+		));
+
+		assert_noop!(
+			ContinuumModule::renew_spot_lease(Origin::signed(CHARLIE), 0),
+			Error::<Runtime>::SpotLeaseRenewalTooEarly
+		);
+		assert_noop!(
+			ContinuumModule::renew_spot_lease(Origin::signed(BOB), 0),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			ContinuumModule::renew_spot_lease(Origin::signed(CHARLIE), 1),
+			Error::<Runtime>::ContinuumSpotNotFound
+		);
+
+		run_to_block(15);
+		assert_ok!(ContinuumModule::renew_spot_lease(Origin::signed(CHARLIE), 0));
+		assert_eq!(last_event(), Event::Continuum(crate::Event::SpotLeaseRenewed(0, 50)));
+		assert_eq!(ContinuumModule::get_spot_lease_expiry(0), Some(50));
+		// Renewal costs the current price of the spot
+		assert_eq!(Balances::free_balance(CHARLIE), 98018);
+		assert_eq!(Balances::free_balance(ContinuumModule::account_id()), 1982);
+		assert_noop!(
+			ContinuumModule::renew_spot_lease(Origin::signed(CHARLIE), 0),
+			Error::<Runtime>::SpotLeaseRenewalTooEarly
+		);
+
+		run_to_block(30);
+		assert_eq!(
+			ContinuumModule::get_continuum_spot(0).metaverse_id,
+			CHARLIE_METAVERSE_ID
+		);
+		run_to_block(50);
+		assert_eq!(ContinuumModule::get_continuum_spot(0).metaverse_id, 0);
+	})
+}
+
+#[test]
+fn spiral_ring_should_work() {
+	assert_eq!(spiral_ring((0, 0)), vec![(0, 0)]);
+	assert_eq!(
+		spiral_ring((-1, 1)),
+		vec![(1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1), (0, -1), (1, -1)]
+	);
+	assert_eq!(spiral_ring((-2, 2)).len(), 16);
+	assert_eq!(spiral_ring((1, 0)), vec![]);
+	assert_eq!(spiral_ring_coordinate((-2, 2), 15), Some((2, -2)));
+	assert_eq!(spiral_ring_coordinate((-2, 2), 16), None);
+}
+
+#[test]
+fn map_should_expand_when_occupancy_reaches_threshold() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(ContinuumModule::set_max_bounds(Origin::root(), (-1, 1)));
+		assert_ok!(ContinuumModule::set_allow_buy_now(Origin::root(), true));
+		assert_ok!(ContinuumModule::buy_continuum_spot(
+			Origin::signed(CHARLIE),
+			(-1, 1),
+			CHARLIE_METAVERSE_ID
+		));
+		assert_eq!(ContinuumModule::occupied_spots(), 1);
+
+		// No expansion without a threshold
+		run_to_block(10);
+		assert_eq!(ContinuumModule::get_max_bound(), (-1, 1));
+
+		assert_noop!(
+			ContinuumModule::set_map_expansion_threshold(
+				Origin::signed(ALICE),
+				Some(sp_runtime::Perbill::from_percent(10))
+			),
+			sp_runtime::traits::BadOrigin
+		);
+		assert_ok!(ContinuumModule::set_map_expansion_threshold(
+			Origin::root(),
+			Some(sp_runtime::Perbill::from_percent(10))
+		));
+
+		// One of nine spots occupied
+		run_to_block(20);
+		assert_eq!(ContinuumModule::get_max_bound(), (-2, 2));
+		assert!(System::events()
+			.iter()
+			.any(|r| r.event == Event::Continuum(crate::Event::ContinuumMapExpanded((-2, 2), 16))));
+		// New ring released in spiral order after the existing spot, ten spots a block
+		assert_eq!(ContinuumModule::get_continuum_position((2, -1)), 1);
+		assert_eq!(ContinuumModule::get_continuum_position((2, 2)), 4);
+		assert!(!ContinuumCoordinates::<Runtime>::contains_key((2, -2)));
+		assert_eq!(ContinuumModule::spot_release_progress(), Some(((-2, 2), 10)));
+
+		run_to_block(21);
+		assert_eq!(ContinuumModule::get_continuum_position((2, -2)), 16);
+		assert_eq!(ContinuumModule::get_continuum_spot(16).metaverse_id, 0);
+		assert_eq!(ContinuumModule::spot_release_progress(), None);
+
+		// One of twenty five spots occupied
+		run_to_block(30);
+		assert_eq!(ContinuumModule::get_max_bound(), (-2, 2));
+	})
+}
+
+#[test]
+fn spot_price_should_depend_on_distance_and_neighbours() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Base price 100 with a nine times premium at the origin
+		assert_eq!(ContinuumModule::spot_price((0, 0)), 1000);
+		assert_eq!(ContinuumModule::spot_price((50, -10)), 550);
+		assert_eq!(ContinuumModule::spot_price((-100, 3)), 100);
+
+		assert_ok!(ContinuumModule::set_allow_buy_now(Origin::root(), true));
+		assert_ok!(ContinuumModule::buy_continuum_spot(
+			Origin::signed(CHARLIE),
+			(1, 1),
+			CHARLIE_METAVERSE_ID
+		));
+		assert_eq!(Balances::free_balance(CHARLIE), 100000 - 991);
+
+		// Occupied neighbour adds ten percent
+		assert_eq!(ContinuumModule::spot_price((0, 0)), 1100);
+		assert_eq!(ContinuumModule::spot_price((50, -10)), 550);
+	})
+}
+
+#[test]
+fn spot_price_should_adjust_with_sales() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(ContinuumModule::set_allow_buy_now(Origin::root(), true));
+		assert_ok!(ContinuumModule::buy_continuum_spot(
+			Origin::signed(CHARLIE),
+			(10, 10),
+			CHARLIE_METAVERSE_ID
+		));
+		assert_eq!(ContinuumModule::initial_spot_price(), 100);
+
+		// Sales above the session target raise the base price
+		assert_ok!(ContinuumModule::buy_continuum_spot(
+			Origin::signed(CHARLIE),
+			(20, 20),
+			CHARLIE_METAVERSE_ID
+		));
+		assert_eq!(ContinuumModule::initial_spot_price(), 110);
+		assert_eq!(last_event(), Event::Continuum(crate::Event::SpotPriceUpdated(110)));
+		assert_eq!(ContinuumModule::session_spot_sales(), 2);
+
+		run_to_block(10);
+		assert_eq!(ContinuumModule::initial_spot_price(), 110);
+		assert_eq!(ContinuumModule::session_spot_sales(), 0);
+
+		// Sessions without sales lower it down to the minimum
+		run_to_block(20);
+		assert_eq!(ContinuumModule::initial_spot_price(), 99);
+		run_to_block(30);
+		assert_eq!(ContinuumModule::initial_spot_price(), 90);
+		run_to_block(40);
+		assert_eq!(ContinuumModule::initial_spot_price(), 90);
+	})
+}
+
+#[test]
+fn relocate_spot_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(ContinuumModule::set_allow_buy_now(Origin::root(), true));
+		assert_ok!(ContinuumModule::buy_continuum_spot(
+			Origin::signed(CHARLIE),
+			(50, 50),
+			CHARLIE_METAVERSE_ID
+		));
+		assert_ok!(ContinuumModule::buy_continuum_spot(
+			Origin::signed(ALICE),
+			(60, 60),
+			ALICE_METAVERSE_ID
+		));
+		assert_ok!(ContinuumModule::register_interest(
+			Origin::signed(BOB),
+			BOB_METAVERSE_ID,
+			(70, 70)
+		));
+		assert_eq!(Balances::free_balance(CHARLIE), 99450);
+
+		assert_noop!(
+			ContinuumModule::relocate_spot(Origin::signed(BOB), 0, (61, 61)),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			ContinuumModule::relocate_spot(Origin::signed(CHARLIE), 0, (60, 60)),
+			Error::<Runtime>::SpotIsNotAvailable
+		);
+		assert_noop!(
+			ContinuumModule::relocate_spot(Origin::signed(CHARLIE), 0, (70, 70)),
+			Error::<Runtime>::SpotIsNotAvailable
+		);
+		assert_noop!(
+			ContinuumModule::relocate_spot(Origin::signed(CHARLIE), 0, (200, 0)),
+			Error::<Runtime>::SpotIsOutOfBound
+		);
+
+		// Cheaper destination only costs the flat fee
+		assert_ok!(ContinuumModule::relocate_spot(Origin::signed(CHARLIE), 0, (61, 61)));
+		assert_eq!(
+			last_event(),
+			Event::Continuum(crate::Event::SpotRelocated(
+				0,
+				(50, 50),
+				(61, 61),
+				vec![ALICE_METAVERSE_ID]
+			))
+		);
+		assert_eq!(Balances::free_balance(CHARLIE), 99440);
+		assert_eq!(ContinuumModule::get_continuum_position((61, 61)), 0);
+		assert!(!ContinuumCoordinates::<Runtime>::contains_key((50, 50)));
+		let spot = ContinuumModule::get_continuum_spot(0);
+		assert_eq!((spot.x, spot.y, spot.metaverse_id), (61, 61, CHARLIE_METAVERSE_ID));
+
+		// Moving closer to the origin pays the price difference, base price is 110 after two sales
+		// Treasury spot at the destination takes the vacated coordinate
+		let treasury_spot_id = ContinuumModule::create_spot((5, 5)).unwrap();
+		assert_ok!(ContinuumModule::relocate_spot(Origin::signed(CHARLIE), 0, (5, 5)));
+		assert_eq!(Balances::free_balance(CHARLIE), 99440 - 515);
+		assert_eq!(ContinuumModule::get_continuum_position((5, 5)), 0);
+		assert_eq!(ContinuumModule::get_continuum_position((61, 61)), treasury_spot_id);
+		let treasury_spot = ContinuumModule::get_continuum_spot(treasury_spot_id);
+		assert_eq!((treasury_spot.x, treasury_spot.y), (61, 61));
+	})
+}
+
+#[test]
+fn swap_spots_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(ContinuumModule::set_allow_buy_now(Origin::root(), true));
+		assert_ok!(ContinuumModule::buy_continuum_spot(
+			Origin::signed(CHARLIE),
+			(50, 50),
+			CHARLIE_METAVERSE_ID
+		));
+		assert_ok!(ContinuumModule::buy_continuum_spot(
+			Origin::signed(ALICE),
+			(-50, -50),
+			ALICE_METAVERSE_ID
+		));
+		let treasury_spot_id = ContinuumModule::create_spot((5, 5)).unwrap();
+
+		assert_noop!(
+			ContinuumModule::offer_spot_swap(Origin::signed(ALICE), 0, 1),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			ContinuumModule::offer_spot_swap(Origin::signed(CHARLIE), 0, 0),
+			Error::<Runtime>::InvalidSpotSwap
+		);
+		assert_noop!(
+			ContinuumModule::offer_spot_swap(Origin::signed(CHARLIE), 0, treasury_spot_id),
+			Error::<Runtime>::InvalidSpotSwap
+		);
+		assert_noop!(
+			ContinuumModule::offer_spot_swap(Origin::signed(CHARLIE), 0, 9),
+			Error::<Runtime>::ContinuumSpotNotFound
+		);
+
+		assert_ok!(ContinuumModule::offer_spot_swap(Origin::signed(CHARLIE), 0, 1));
+		assert_eq!(last_event(), Event::Continuum(crate::Event::SpotSwapOffered(0, 1)));
+		assert_noop!(
+			ContinuumModule::accept_spot_swap(Origin::signed(BOB), 0),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			ContinuumModule::cancel_spot_swap(Origin::signed(ALICE), 0),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(ContinuumModule::accept_spot_swap(Origin::signed(ALICE), 0));
+		assert_eq!(last_event(), Event::Continuum(crate::Event::SpotsSwapped(0, 1, vec![])));
+		assert_eq!(ContinuumModule::get_continuum_position((-50, -50)), 0);
+		assert_eq!(ContinuumModule::get_continuum_position((50, 50)), 1);
+		let charlie_spot = ContinuumModule::get_continuum_spot(0);
+		assert_eq!(
+			(charlie_spot.x, charlie_spot.y, charlie_spot.metaverse_id),
+			(-50, -50, CHARLIE_METAVERSE_ID)
+		);
+		assert_eq!(ContinuumModule::get_spot_swap_offer(0), None);
+		assert_noop!(
+			ContinuumModule::accept_spot_swap(Origin::signed(ALICE), 0),
+			Error::<Runtime>::SpotSwapOfferNotFound
+		);
+
+		assert_ok!(ContinuumModule::offer_spot_swap(Origin::signed(CHARLIE), 0, 1));
+		assert_ok!(ContinuumModule::cancel_spot_swap(Origin::signed(CHARLIE), 0));
+		assert_eq!(last_event(), Event::Continuum(crate::Event::SpotSwapOfferCancelled(0)));
+		assert_eq!(ContinuumModule::get_spot_swap_offer(0), None);
+	})
+}
+
+#[test]
+fn spot_swap_offer_should_go_stale_when_metaverse_changes_hands() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(ContinuumModule::set_allow_buy_now(Origin::root(), true));
+		assert_ok!(ContinuumModule::buy_continuum_spot(
+			Origin::signed(CHARLIE),
+			(50, 50),
+			CHARLIE_METAVERSE_ID
+		));
+		assert_ok!(ContinuumModule::buy_continuum_spot(
+			Origin::signed(ALICE),
+			(-50, -50),
+			ALICE_METAVERSE_ID
+		));
+
+		assert_ok!(ContinuumModule::offer_spot_swap(Origin::signed(CHARLIE), 0, 1));
+		assert_eq!(ContinuumModule::get_spot_swap_offer(0).unwrap().who, CHARLIE);
+		assert_ok!(MetaverseInfoSource::transfer_metaverse(
+			&CHARLIE,
+			&BOB,
+			CHARLIE_METAVERSE_ID
+		));
+
+		assert_noop!(
+			ContinuumModule::accept_spot_swap(Origin::signed(ALICE), 0),
+			Error::<Runtime>::InvalidSpotSwap
+		);
+		// The new owner can clear the stale offer
+		assert_ok!(ContinuumModule::cancel_spot_swap(Origin::signed(BOB), 0));
+		assert_eq!(ContinuumModule::get_spot_swap_offer(0), None);
+	})
+}
+
+#[test]
+fn register_interest_should_reserve_deposit_once_per_sender() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(ContinuumModule::register_interest(
+			Origin::signed(ALICE),
+			ALICE_METAVERSE_ID,
+			(0, 0)
+		));
+		assert_eq!(Balances::reserved_balance(ALICE), 50);
+		assert_eq!(ContinuumModule::get_eoi_deposit(0, ALICE), Some(50));
+
+		assert_noop!(
+			ContinuumModule::register_interest(Origin::signed(ALICE), ALICE_METAVERSE_ID, (0, 0)),
+			Error::<Runtime>::EOIAlreadyExists
+		);
+
+		assert_ok!(ContinuumModule::register_interest(
+			Origin::signed(ALICE),
+			ALICE_METAVERSE_ID,
+			(1, 1)
+		));
+		assert_eq!(Balances::reserved_balance(ALICE), 100);
+		assert_eq!(ContinuumModule::get_eoi_set(0)[0].participants, vec![ALICE]);
+	})
+}
+
+#[test]
+fn eoi_deposits_should_be_refunded_or_forfeited() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(ContinuumModule::set_new_auction_rate(Origin::root(), 1));
+		assert_ok!(ContinuumModule::register_interest(
+			Origin::signed(ALICE),
+			ALICE_METAVERSE_ID,
+			(0, 0)
+		));
+		assert_ok!(ContinuumModule::register_interest(
+			Origin::signed(BOB),
+			BOB_METAVERSE_ID,
+			(0, 0)
+		));
+		assert_ok!(ContinuumModule::register_interest(
+			Origin::signed(CHARLIE),
+			CHARLIE_METAVERSE_ID,
+			(5, 5)
+		));
+		assert_ok!(ContinuumModule::set_allow_buy_now(Origin::root(), true));
+		assert_ok!(ContinuumModule::buy_continuum_spot(
+			Origin::signed(CHARLIE),
+			(-1, 1),
+			CHARLIE_METAVERSE_ID
+		));
+		run_to_block(20);
+
+		// Spot with fewer participants was not selected
+		assert_eq!(Balances::reserved_balance(CHARLIE), 0);
+		assert_eq!(Balances::reserved_balance(ALICE), 50);
+		assert_eq!(Balances::reserved_balance(BOB), 50);
+
+		assert_ok!(ContinuumModule::vote(Origin::signed(CHARLIE), 0, nay_on(ALICE)));
+		let treasury_balance = Balances::free_balance(ContinuumModule::account_id());
+
+		run_to_block(30);
+		assert!(System::events()
+			.iter()
+			.any(|r| r.event == Event::Continuum(crate::Event::EOIDepositForfeited(ALICE, 0, 50))));
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::free_balance(ALICE), 100000 - 50);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::free_balance(BOB), 500);
+		assert_eq!(
+			Balances::free_balance(ContinuumModule::account_id()),
+			treasury_balance + 50
+		);
+		assert_eq!(ContinuumModule::get_eoi_deposit(0, ALICE), None);
+	})
+}
+
+#[test]
+fn eoi_deposits_should_be_refunded_on_emergency_shutdown() {
+	ExtBuilder::default().build().execute_with(|| {
+		start_gnp_with_charlie_as_neighbour();
+		assert_eq!(Balances::reserved_balance(ALICE), 50);
+
+		assert_ok!(ContinuumModule::emergency_shutdown(Origin::signed(ALICE), 0));
+		run_to_block(30);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::free_balance(BOB), 500);
+	})
+}
+
+fn buy_portal_spots() {
+	assert_ok!(ContinuumModule::set_allow_buy_now(Origin::root(), true));
+	assert_ok!(ContinuumModule::buy_continuum_spot(
+		Origin::signed(CHARLIE),
+		(0, 0),
+		CHARLIE_METAVERSE_ID
+	));
+	assert_ok!(ContinuumModule::buy_continuum_spot(
+		Origin::signed(ALICE),
+		(0, 1),
+		ALICE_METAVERSE_ID
+	));
+	assert_ok!(ContinuumModule::buy_continuum_spot(
+		Origin::signed(BOB),
+		(100, 100),
+		BOB_METAVERSE_ID
+	));
+}
+
+#[test]
+fn portal_should_open_with_consent_of_both_sides() {
+	ExtBuilder::default().build().execute_with(|| {
+		buy_portal_spots();
+
+		assert_noop!(
+			ContinuumModule::propose_portal(Origin::signed(ALICE), 1, 2, vec![1]),
+			Error::<Runtime>::SpotsAreNotNeighbours
+		);
+		assert_noop!(
+			ContinuumModule::propose_portal(Origin::signed(ALICE), 1, 1, vec![1]),
+			Error::<Runtime>::InvalidPortal
+		);
+		assert_noop!(
+			ContinuumModule::propose_portal(Origin::signed(CHARLIE), 1, 0, vec![1]),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			ContinuumModule::propose_portal(Origin::signed(ALICE), 1, 0, vec![1; 33]),
+			Error::<Runtime>::PortalMetadataTooLong
+		);
+
+		assert_ok!(ContinuumModule::propose_portal(
+			Origin::signed(ALICE),
+			1,
+			0,
+			b"gate".to_vec()
+		));
+		assert_eq!(
+			last_event(),
+			Event::Continuum(crate::Event::PortalProposed(ALICE_METAVERSE_ID, CHARLIE_METAVERSE_ID))
+		);
+		let portal = ContinuumPortal {
+			metaverses: (ALICE_METAVERSE_ID, CHARLIE_METAVERSE_ID),
+			spots: (1, 0),
+			metadata: b"gate".to_vec(),
+		};
+		assert_eq!(
+			ContinuumModule::get_portal_proposal(ALICE_METAVERSE_ID, CHARLIE_METAVERSE_ID),
+			Some(portal.clone())
+		);
+
+		assert_noop!(
+			ContinuumModule::accept_portal(Origin::signed(BOB), ALICE_METAVERSE_ID, CHARLIE_METAVERSE_ID),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			ContinuumModule::accept_portal(Origin::signed(ALICE), CHARLIE_METAVERSE_ID, ALICE_METAVERSE_ID),
+			Error::<Runtime>::PortalNotFound
+		);
+		assert_ok!(ContinuumModule::accept_portal(
+			Origin::signed(CHARLIE),
+			ALICE_METAVERSE_ID,
+			CHARLIE_METAVERSE_ID
+		));
+		assert_eq!(
+			last_event(),
+			Event::Continuum(crate::Event::PortalOpened(ALICE_METAVERSE_ID, CHARLIE_METAVERSE_ID))
+		);
+		assert_eq!(
+			ContinuumModule::get_portal_proposal(ALICE_METAVERSE_ID, CHARLIE_METAVERSE_ID),
+			None
+		);
+		assert_eq!(ContinuumModule::portals(), vec![portal]);
+
+		assert_noop!(
+			ContinuumModule::propose_portal(Origin::signed(CHARLIE), 0, 1, vec![]),
+			Error::<Runtime>::PortalAlreadyExists
+		);
+
+		// Either side can remove the portal
+		assert_ok!(ContinuumModule::remove_portal(
+			Origin::signed(CHARLIE),
+			CHARLIE_METAVERSE_ID,
+			ALICE_METAVERSE_ID
+		));
+		assert_eq!(
+			last_event(),
+			Event::Continuum(crate::Event::PortalRemoved(CHARLIE_METAVERSE_ID, ALICE_METAVERSE_ID))
+		);
+		assert_eq!(ContinuumModule::portals(), vec![]);
+		assert_noop!(
+			ContinuumModule::remove_portal(Origin::signed(ALICE), ALICE_METAVERSE_ID, CHARLIE_METAVERSE_ID),
+			Error::<Runtime>::PortalNotFound
+		);
+	})
+}
+
+#[test]
+fn portal_proposal_should_go_stale_when_spots_move_apart() {
+	ExtBuilder::default().build().execute_with(|| {
+		buy_portal_spots();
+
+		assert_ok!(ContinuumModule::propose_portal(Origin::signed(ALICE), 1, 0, vec![]));
+		assert_ok!(ContinuumModule::relocate_spot(Origin::signed(ALICE), 1, (-90, -90)));
+		assert_noop!(
+			ContinuumModule::accept_portal(Origin::signed(CHARLIE), ALICE_METAVERSE_ID, CHARLIE_METAVERSE_ID),
+			Error::<Runtime>::SpotsAreNotNeighbours
+		);
+
+		// Proposer withdraws the proposal
+		assert_ok!(ContinuumModule::remove_portal(
+			Origin::signed(ALICE),
+			ALICE_METAVERSE_ID,
+			CHARLIE_METAVERSE_ID
+		));
+		assert_eq!(
+			ContinuumModule::get_portal_proposal(ALICE_METAVERSE_ID, CHARLIE_METAVERSE_ID),
+			None
+		);
+	})
+}
+
+#[test]
+fn portal_should_close_when_spots_move_apart() {
+	ExtBuilder::default().build().execute_with(|| {
+		buy_portal_spots();
+
+		assert_ok!(ContinuumModule::propose_portal(Origin::signed(ALICE), 1, 0, vec![]));
+		assert_ok!(ContinuumModule::accept_portal(
+			Origin::signed(CHARLIE),
+			ALICE_METAVERSE_ID,
+			CHARLIE_METAVERSE_ID
+		));
+		assert_eq!(ContinuumModule::portals().len(), 1);
+
+		assert_ok!(ContinuumModule::relocate_spot(Origin::signed(ALICE), 1, (-90, -90)));
+		assert_eq!(ContinuumModule::portals(), vec![]);
+
+		// Moving back next to each other needs a new portal
+		assert_ok!(ContinuumModule::relocate_spot(Origin::signed(CHARLIE), 0, (-90, -89)));
+		assert_ok!(ContinuumModule::propose_portal(
+			Origin::signed(CHARLIE),
+			0,
+			1,
+			b"gate".to_vec()
+		));
+		assert_ok!(ContinuumModule::accept_portal(
+			Origin::signed(ALICE),
+			CHARLIE_METAVERSE_ID,
+			ALICE_METAVERSE_ID
+		));
+		assert_eq!(
+			ContinuumModule::portals(),
+			vec![ContinuumPortal {
+				metaverses: (CHARLIE_METAVERSE_ID, ALICE_METAVERSE_ID),
+				spots: (0, 1),
+				metadata: b"gate".to_vec(),
+			}]
+		);
+	})
+}
+
+#[test]
+fn map_region_should_report_spot_status() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(ContinuumModule::register_interest(
+			Origin::signed(ALICE),
+			ALICE_METAVERSE_ID,
+			(0, 0)
+		));
+		assert_ok!(ContinuumModule::register_interest(
+			Origin::signed(BOB),
+			BOB_METAVERSE_ID,
+			(0, 0)
+		));
+		assert_ok!(ContinuumModule::set_allow_buy_now(Origin::root(), true));
+		assert_ok!(ContinuumModule::buy_continuum_spot(
+			Origin::signed(CHARLIE),
+			(-1, 1),
+			CHARLIE_METAVERSE_ID
+		));
+		run_to_block(10);
+		assert_ok!(ContinuumModule::register_interest(
+			Origin::signed(ALICE),
+			ALICE_METAVERSE_ID,
+			(1, -1)
+		));
+		run_to_block(20);
+		assert_ok!(ContinuumModule::register_interest(
+			Origin::signed(BOB),
+			BOB_METAVERSE_ID,
+			(1, 1)
+		));
+		assert_ok!(ContinuumModule::vote(Origin::signed(CHARLIE), 0, nay_on(ALICE)));
+
+		let region = ContinuumModule::map_region((-1, -1), (1, 1));
+		assert_eq!(region.len(), 9);
+		for spot in region.iter() {
+			assert_eq!(spot.price, ContinuumModule::spot_price(spot.coordinate));
+		}
+		let spot_at = |coordinate: (i32, i32)| region.iter().find(|s| s.coordinate == coordinate).unwrap().clone();
+
+		let gnp_spot = spot_at((0, 0));
+		assert_eq!(gnp_spot.spot_id, Some(0));
+		assert_eq!(gnp_spot.metaverse_id, None);
+		assert_eq!(gnp_spot.status, SpotStatus::GoodNeighbourhoodProtocol);
+		let referendum = gnp_spot.referendum.unwrap();
+		assert_eq!(referendum.end, ContinuumModule::referendum_status(0).unwrap().end);
+		assert_eq!(referendum.neighbours, 1);
+		assert_eq!(referendum.tallies.len(), 2);
+		assert!(referendum.tallies.contains(&SpotReferendumTally {
+			who: ALICE,
+			nays: 1,
+			turnout: 1,
+		}));
+
+		let occupied_spot = spot_at((-1, 1));
+		assert_eq!(occupied_spot.metaverse_id, Some(CHARLIE_METAVERSE_ID));
+		assert_eq!(occupied_spot.status, SpotStatus::Occupied);
+		assert_eq!(occupied_spot.referendum, None);
+
+		// Spots of an active auction slot still collect interest
+		assert_eq!(spot_at((1, -1)).status, SpotStatus::ExpressionOfInterest);
+		assert_eq!(spot_at((1, 1)).status, SpotStatus::ExpressionOfInterest);
+
+		let free_spot = spot_at((0, 1));
+		assert_eq!(free_spot.spot_id, None);
+		assert_eq!(free_spot.metaverse_id, None);
+		assert_eq!(free_spot.status, SpotStatus::Free);
+
+		// Regions beyond the query cap return nothing
+		assert_eq!(ContinuumModule::map_region((-100, -100), (100, 100)), vec![]);
+		assert_eq!(ContinuumModule::map_region((0, 0), (99, 99)).len(), 10_000);
+
+		// Region is clipped to the map bound
+		assert_ok!(ContinuumModule::set_max_bounds(Origin::root(), (-1, 1)));
+		assert_eq!(ContinuumModule::map_region((-5, -5), (5, 5)).len(), 9);
+		assert_eq!(ContinuumModule::map_region((-100, -100), (100, 100)).len(), 9);
+	})
+}
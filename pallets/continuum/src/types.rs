@@ -17,12 +17,12 @@
 
 //! Miscellaneous additional datatypes.
 
-use crate::{AccountVote, Vote};
+use crate::AccountVote;
 use codec::{Decode, Encode};
 use primitives::{MetaverseId, SpotId};
 use scale_info::TypeInfo;
 use sp_runtime::traits::{One, Zero};
-use sp_runtime::{DispatchError, RuntimeDebug};
+use sp_runtime::{DispatchError, Perbill, RuntimeDebug};
 use sp_std::vec;
 use sp_std::vec::Vec;
 
@@ -57,74 +57,106 @@ impl ContinuumSpot {
 	}
 }
 
-/// Info regarding an ongoing referendum.
+/// Coordinates on the outer ring of the square `bound` x `bound`, in spiral order.
+///
+/// The ring starts right above its bottom right corner and runs anti-clockwise, so consecutive
+/// rings continue the spiral that winds out of the origin.
+pub fn spiral_ring(bound: (i32, i32)) -> Vec<(i32, i32)> {
+	let mut ring = Vec::new();
+	while let Some(coordinate) = spiral_ring_coordinate(bound, ring.len() as u32) {
+		ring.push(coordinate);
+	}
+	ring
+}
+
+/// Coordinate at `index` in the spiral order of the outer ring of `bound`, if the ring has it.
+pub fn spiral_ring_coordinate(bound: (i32, i32), index: u32) -> Option<(i32, i32)> {
+	let (lower, upper) = (bound.0 as i64, bound.1 as i64);
+	if upper < lower {
+		return None;
+	}
+	if upper == lower {
+		return if index == 0 { Some(bound) } else { None };
+	}
+
+	let side = upper - lower;
+	let index = index as i64;
+	let offset = index % side;
+	let coordinate = match index / side {
+		0 => (upper, lower + 1 + offset),
+		1 => (upper - 1 - offset, upper),
+		2 => (lower, upper - 1 - offset),
+		3 => (lower + 1 + offset, lower),
+		_ => return None,
+	};
+	Some((coordinate.0 as i32, coordinate.1 as i32))
+}
+
+/// Tally of the neighbour votes cast on a single candidate of a referendum.
 #[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 pub struct ContinuumSpotTally<AccountId> {
-	/// The number of nay votes, expressed in terms of post-conviction lock-vote.
+	/// The number of nay votes.
 	pub(crate) nays: u8,
+	/// The candidate that is being voted on.
 	pub(crate) who: AccountId,
-	/// The amount of funds currently expressing its opinion. Pre-conviction.
+	/// The number of votes cast on the candidate, ayes and nays.
 	pub(crate) turnout: u8,
 }
 
 impl<AccountId> ContinuumSpotTally<AccountId> {
-	/// Create a new tally.
-	pub fn new(vote: Vote<AccountId>) -> Self {
+	/// Create a new, empty tally for a candidate.
+	pub fn new(who: AccountId) -> Self {
 		Self {
-			who: vote.who,
+			who,
 			nays: Zero::zero(),
 			turnout: Zero::zero(),
 		}
 	}
 
 	/// Add an account's vote into the tally.
-	pub fn add(&mut self, vote: AccountVote<AccountId>) -> Option<()> {
+	pub fn add(&mut self, vote: &AccountVote<AccountId>) -> Option<()> {
 		match vote {
-			AccountVote::Standard { vote } => {
-				self.turnout = self.turnout.checked_add(One::one())?;
-				self.nays = self.nays.checked_add(One::one())?;
-				self.who = vote.who;
-			}
+			AccountVote::Standard { vote } => self.increase(!vote.nay),
 		}
-		Some(())
 	}
 
 	/// Remove an account's vote from the tally.
-	pub fn remove(&mut self, vote: AccountVote<AccountId>) -> Option<()> {
+	pub fn remove(&mut self, vote: &AccountVote<AccountId>) -> Option<()> {
 		match vote {
-			AccountVote::Standard { vote: _ } => {
-				self.turnout = self.turnout.checked_add(Zero::zero())?;
-				self.nays = self.nays.checked_add(Zero::zero())?;
-			}
+			AccountVote::Standard { vote } => self.reduce(!vote.nay),
 		}
-		Some(())
 	}
 
 	/// Increment some amount of votes.
 	pub fn increase(&mut self, approve: bool) -> Option<()> {
-		self.turnout = self.turnout.saturating_add(Zero::zero());
-		match approve {
-			false => self.nays = self.nays.saturating_add(Zero::zero()),
-			true => (),
+		self.turnout = self.turnout.checked_add(One::one())?;
+		if !approve {
+			self.nays = self.nays.checked_add(One::one())?;
 		}
 		Some(())
 	}
 
 	/// Decrement some amount of votes.
 	pub fn reduce(&mut self, approve: bool) -> Option<()> {
-		self.turnout = self.turnout.saturating_sub(Zero::zero());
-		match approve {
-			true => (),
-			false => self.nays = self.nays.saturating_add(Zero::zero()),
+		self.turnout = self.turnout.checked_sub(One::one())?;
+		if !approve {
+			self.nays = self.nays.checked_sub(One::one())?;
 		}
 		Some(())
 	}
 
-	pub fn result(&mut self) -> Option<bool> {
-		let total_nay = self.nays.checked_div(self.turnout).unwrap().saturating_mul(100);
-		let approve_threshold = 49;
+	/// Whether the candidate is banned, given the number of neighbours entitled to vote.
+	///
+	/// Votes from neighbours that arrived after the referendum started still count, so the
+	/// electorate is never taken to be smaller than the turnout. Without any electorate nobody
+	/// can be banned.
+	pub fn result(&self, electorate: u8, ban_threshold: Perbill) -> bool {
+		let electorate = electorate.max(self.turnout);
+		if electorate.is_zero() {
+			return false;
+		}
 
-		Some(total_nay > approve_threshold)
+		Perbill::from_rational(self.nays, electorate) > ban_threshold
 	}
 }
 
@@ -135,6 +167,8 @@ pub struct ReferendumStatus<AccountId, BlockNumber> {
 	pub(crate) end: BlockNumber,
 	/// The continuum spot that being voted on.
 	pub(crate) spot_id: SpotId,
+	/// The number of distinct owners of neighbouring spots when the referendum started.
+	pub(crate) neighbours: u8,
 	/// The current tally of votes in this referendum.
 	pub(crate) tallies: Vec<ContinuumSpotTally<AccountId>>,
 }
@@ -150,10 +184,11 @@ pub enum ReferendumInfo<AccountId, BlockNumber> {
 
 impl<AccountId, BlockNumber: Default> ReferendumInfo<AccountId, BlockNumber> {
 	/// Create a new instance.
-	pub fn new(end: BlockNumber, spot_id: SpotId) -> Self {
+	pub fn new(end: BlockNumber, spot_id: SpotId, neighbours: u8) -> Self {
 		let s = ReferendumStatus {
 			end,
 			spot_id,
+			neighbours,
 			tallies: Vec::new(),
 		};
 		ReferendumInfo::Ongoing(s)
@@ -31,4 +31,11 @@ impl<AccountId> AccountVote<AccountId> {
 			AccountVote::Standard { vote } => vote,
 		}
 	}
+
+	/// The candidate this vote is cast on.
+	pub fn candidate(&self) -> &AccountId {
+		match self {
+			AccountVote::Standard { vote } => &vote.who,
+		}
+	}
 }
@@ -115,6 +115,10 @@ impl MetaverseTrait<AccountId> for MetaverseInfoSource {
 		}
 	}
 
+	fn check_role(who: &AccountId, metaverse_id: &MetaverseId, _role: &MetaverseRole) -> bool {
+		Self::check_ownership(who, metaverse_id)
+	}
+
 	fn get_country(metaverse_id: MetaverseId) -> Option<MetaverseInfo<AccountId>> {
 		None
 	}
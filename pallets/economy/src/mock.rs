@@ -144,6 +144,30 @@ impl Estate<u128> for EstateHandler {
 	fn get_total_undeploy_land_units() -> u64 {
 		10
 	}
+
+	fn is_estate_leased(_estate_id: EstateId) -> Result<bool, DispatchError> {
+		Ok(false)
+	}
+
+	fn is_land_unit_leased(
+		_metaverse_id: primitives::MetaverseId,
+		_coordinate: (i32, i32),
+	) -> Result<bool, DispatchError> {
+		Ok(false)
+	}
+
+	fn is_estate_collateralized(_estate_id: EstateId) -> Result<bool, DispatchError> {
+		Ok(false)
+	}
+
+	fn is_land_unit_collateralized(
+		_metaverse_id: primitives::MetaverseId,
+		_coordinate: (i32, i32),
+	) -> Result<bool, DispatchError> {
+		Ok(false)
+	}
+
+	fn record_land_sale(_asset: primitives::estate::LandAsset, _price: Balance) {}
 }
 
 pub struct MetaverseStakingHandler;
@@ -0,0 +1,179 @@
+// This file is part of Bit.Country.
+
+// Copyright (C) 2020-2021 Bit.Country.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RPC interface for the estate pallet map queries.
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use estate_runtime_api::EstateApi as EstateRuntimeApi;
+use primitives::estate::{EstateInfo, LandAsset, LandUnitOwnership};
+use primitives::{EstateId, MetaverseId};
+
+#[rpc]
+pub trait EstateApi<BlockHash, AccountId> {
+	#[rpc(name = "estate_landUnitOwnersInArea")]
+	fn land_unit_owners_in_area(
+		&self,
+		metaverse_id: MetaverseId,
+		bottom_left: (i32, i32),
+		top_right: (i32, i32),
+		at: Option<BlockHash>,
+	) -> Result<Vec<LandUnitOwnership<AccountId>>>;
+
+	#[rpc(name = "estate_estateByCoordinate")]
+	fn estate_by_coordinate(
+		&self,
+		metaverse_id: MetaverseId,
+		coordinate: (i32, i32),
+		at: Option<BlockHash>,
+	) -> Result<Option<(EstateId, EstateInfo)>>;
+
+	#[rpc(name = "estate_estatesByOwner")]
+	fn estates_by_owner(&self, owner: AccountId, at: Option<BlockHash>) -> Result<Vec<(EstateId, EstateInfo)>>;
+
+	#[rpc(name = "estate_freeCoordinates")]
+	fn free_coordinates(
+		&self,
+		metaverse_id: MetaverseId,
+		bottom_left: (i32, i32),
+		top_right: (i32, i32),
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> Result<Vec<(i32, i32)>>;
+
+	#[rpc(name = "estate_neighbours")]
+	fn neighbours(&self, asset: LandAsset, at: Option<BlockHash>) -> Result<Vec<LandUnitOwnership<AccountId>>>;
+}
+
+/// Estate RPC methods, answered by the runtime API at the requested block
+pub struct Estate<C, B> {
+	client: Arc<C>,
+	_marker: PhantomData<B>,
+}
+
+impl<C, B> Estate<C, B> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(Error::RuntimeError.into()),
+		message: "Unable to query estate runtime api".into(),
+		data: Some(format!("{:?}", err).into()),
+	}
+}
+
+impl<C, Block, AccountId> EstateApi<<Block as BlockT>::Hash, AccountId> for Estate<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: EstateRuntimeApi<Block, AccountId>,
+	AccountId: Codec,
+{
+	fn land_unit_owners_in_area(
+		&self,
+		metaverse_id: MetaverseId,
+		bottom_left: (i32, i32),
+		top_right: (i32, i32),
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<LandUnitOwnership<AccountId>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client
+			.runtime_api()
+			.land_unit_owners_in_area(&at, metaverse_id, bottom_left, top_right)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn estate_by_coordinate(
+		&self,
+		metaverse_id: MetaverseId,
+		coordinate: (i32, i32),
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<(EstateId, EstateInfo)>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client
+			.runtime_api()
+			.estate_by_coordinate(&at, metaverse_id, coordinate)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn estates_by_owner(
+		&self,
+		owner: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<(EstateId, EstateInfo)>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client
+			.runtime_api()
+			.estates_by_owner(&at, owner)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn free_coordinates(
+		&self,
+		metaverse_id: MetaverseId,
+		bottom_left: (i32, i32),
+		top_right: (i32, i32),
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<(i32, i32)>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client
+			.runtime_api()
+			.free_coordinates(&at, metaverse_id, bottom_left, top_right, limit)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn neighbours(
+		&self,
+		asset: LandAsset,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<LandUnitOwnership<AccountId>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client
+			.runtime_api()
+			.neighbours(&at, asset)
+			.map_err(runtime_error_into_rpc_err)
+	}
+}
@@ -0,0 +1,55 @@
+// This file is part of Bit.Country.
+
+// Copyright (C) 2020-2021 Bit.Country.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for the estate pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+use primitives::estate::{EstateInfo, LandAsset, LandUnitOwnership};
+use primitives::{EstateId, MetaverseId};
+
+sp_api::decl_runtime_apis! {
+	pub trait EstateApi<AccountId> where
+		AccountId: Codec,
+	{
+		/// Owners of the minted land units inside a bounding box of at most 10,000 land units
+		fn land_unit_owners_in_area(
+			metaverse_id: MetaverseId,
+			bottom_left: (i32, i32),
+			top_right: (i32, i32),
+		) -> Vec<LandUnitOwnership<AccountId>>;
+		/// Estate which contains a land unit
+		fn estate_by_coordinate(metaverse_id: MetaverseId, coordinate: (i32, i32)) -> Option<(EstateId, EstateInfo)>;
+		/// Estates owned by an account
+		fn estates_by_owner(owner: AccountId) -> Vec<(EstateId, EstateInfo)>;
+		/// Coordinates inside a bounding box and the metaverse max bounds with no land unit minted,
+		/// at most `MAX_FREE_COORDINATES` of them
+		fn free_coordinates(
+			metaverse_id: MetaverseId,
+			bottom_left: (i32, i32),
+			top_right: (i32, i32),
+			limit: u32,
+		) -> Vec<(i32, i32)>;
+		/// Minted land units touching a land unit or estate
+		fn neighbours(asset: LandAsset) -> Vec<LandUnitOwnership<AccountId>>;
+	}
+}
@@ -18,22 +18,32 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use frame_support::pallet_prelude::*;
-use frame_support::{dispatch::DispatchResult, ensure, traits::Get, transactional, PalletId};
+use frame_support::{
+	dispatch::DispatchResult,
+	ensure,
+	traits::{Currency, ExistenceRequirement, Get, StorageVersion},
+	transactional, PalletId,
+};
 use frame_system::pallet_prelude::*;
 use frame_system::{ensure_root, ensure_signed};
+use orml_traits::MultiCurrency;
 use scale_info::TypeInfo;
 use sp_runtime::{
-	traits::{AccountIdConversion, One, Saturating},
-	DispatchError,
+	traits::{AccountIdConversion, One, SaturatedConversion, Saturating, Zero},
+	DispatchError, Perbill,
 };
-use sp_std::vec::Vec;
+use sp_std::{collections::btree_set::BTreeSet, vec::Vec};
 
 use auction_manager::{Auction, CheckAuctionItemHandler};
 use core_primitives::*;
 pub use pallet::*;
-use primitives::estate::EstateInfo;
+use primitives::estate::{
+	BuilderGrant, EstateInfo, EstateShapeRule, LandAsset, LandSale, LandTaxConfig, LandUnitOwnership, LeaseInfo,
+	LeaseOffer, LoanInfo, LoanRequest, ParcelContent, SelfAssessment,
+};
 use primitives::{
-	estate::Estate, EstateId, ItemId, MetaverseId, UndeployedLandBlock, UndeployedLandBlockId, UndeployedLandBlockType,
+	estate::Estate, EstateId, FungibleTokenId, Hash, ItemId, MetaverseId, UndeployedLandBlock, UndeployedLandBlockId,
+	UndeployedLandBlockType,
 };
 pub use rate::{MintingRateInfo, Range};
 pub use weights::WeightInfo;
@@ -50,21 +60,40 @@ mod tests;
 
 pub mod weights;
 
+pub mod migration_v2 {
+	use codec::{Decode, Encode};
+	use scale_info::TypeInfo;
+	use sp_runtime::RuntimeDebug;
+
+	/// Self-assessment before the tax terms were kept with it
+	#[derive(Eq, PartialEq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+	pub struct SelfAssessment<AccountId, Balance, BlockNumber> {
+		pub owner: AccountId,
+		pub price: Balance,
+		pub paid_until: BlockNumber,
+	}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::traits::{Currency, Imbalance, ReservableCurrency};
-	use sp_runtime::traits::{CheckedAdd, CheckedSub, Zero};
+	use frame_support::traits::{Imbalance, ReservableCurrency};
+	use sp_runtime::traits::{CheckedAdd, CheckedSub, SaturatedConversion};
+	use sp_runtime::Perbill;
 
 	use primitives::estate::EstateInfo;
-	use primitives::staking::{Bond, RoundInfo, StakeSnapshot};
+	use primitives::staking::{Bond, DelayedPayout, RoundInfo, SnapshotProgress, StakeSnapshot};
 	use primitives::{RoundIndex, UndeployedLandBlockId};
 
-	use crate::rate::{round_issuance_range, MintingRateInfo};
+	use crate::rate::{round_issuance_range, round_minting_rate, MintingRateInfo};
 
 	use super::*;
 
+	/// The current storage version
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(PhantomData<T>);
 
@@ -91,15 +120,46 @@ pub mod pallet {
 		type MinimumStake: Get<BalanceOf<Self>>;
 		#[pallet::constant]
 		type RewardPaymentDelay: Get<u32>;
+		/// Maximum number of estate stake snapshots paid out, and of estate stakes snapshot, per
+		/// block
+		#[pallet::constant]
+		type MaxEstatePayoutsPerBlock: Get<u32>;
+		/// Multi currency used to pay rent in social tokens
+		type FungibleTokenCurrency: MultiCurrency<
+			Self::AccountId,
+			CurrencyId = FungibleTokenId,
+			Balance = BalanceOf<Self>,
+		>;
+		/// Blocks a tenant can be late on rent before the lease can be terminated
+		#[pallet::constant]
+		type LeaseGracePeriod: Get<Self::BlockNumber>;
+		/// Maximum length of a parcel content URI
+		#[pallet::constant]
+		type MaxContentUriLength: Get<u32>;
+		/// Treasury whose sub-account of each metaverse holds the metaverse fund
+		#[pallet::constant]
+		type MetaverseFundTreasury: Get<PalletId>;
+		/// Blocks an owner can be late on land tax before the asset can be reclaimed
+		#[pallet::constant]
+		type LandTaxGracePeriod: Get<Self::BlockNumber>;
+		/// Maximum number of price zones in a primary land sale
+		#[pallet::constant]
+		type MaxLandSaleZones: Get<u32>;
 	}
 
-	type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub(crate) type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
 	/// Get max bound
 	#[pallet::storage]
 	#[pallet::getter(fn get_max_bounds)]
 	pub type MaxBounds<T: Config> = StorageMap<_, Blake2_128Concat, MetaverseId, (i32, i32), ValueQuery>;
 
+	/// Shape rule estates of the metaverse must follow
+	#[pallet::storage]
+	#[pallet::getter(fn get_estate_shape_rule)]
+	pub type EstateShapeRules<T: Config> = StorageMap<_, Blake2_128Concat, MetaverseId, EstateShapeRule, ValueQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn all_land_units_count)]
 	pub(super) type AllLandUnitsCount<T: Config> = StorageValue<_, u64, ValueQuery>;
@@ -130,6 +190,17 @@ pub mod pallet {
 	pub type EstateOwner<T: Config> =
 		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, EstateId, (), OptionQuery>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn get_owner_of_estate)]
+	/// Owner of each estate, the reverse of `EstateOwner`
+	pub type OwnerOfEstate<T: Config> = StorageMap<_, Twox64Concat, EstateId, T::AccountId, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn get_land_unit_estate)]
+	/// Estate each land unit belongs to, for land units which are part of an estate
+	pub type LandUnitEstate<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, MetaverseId, Twox64Concat, (i32, i32), EstateId, OptionQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn next_undeployed_land_block_id)]
 	pub(super) type NextUndeployedLandBlockId<T: Config> = StorageValue<_, UndeployedLandBlockId, ValueQuery>;
@@ -182,12 +253,116 @@ pub mod pallet {
 		StakeSnapshot<T::AccountId, BalanceOf<T>>,
 	>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn stake_snapshot_progress)]
+	/// Stake snapshot of the current round, while it is still being taken
+	pub type StakeSnapshotProgress<T: Config> = StorageValue<_, SnapshotProgress<T::AccountId>, OptionQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn estate_stake)]
 	/// Estate staking
 	pub type EstateStake<T: Config> =
 		StorageDoubleMap<_, Twox64Concat, EstateId, Twox64Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn delayed_payouts)]
+	/// Rewards issued for a round which are waiting to be paid out to its stakers
+	pub type DelayedPayouts<T: Config> =
+		StorageMap<_, Twox64Concat, RoundIndex, DelayedPayout<BalanceOf<T>>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_payout_round)]
+	/// Oldest round that still has stakers to be paid
+	pub type NextPayoutRound<T: Config> = StorageValue<_, RoundIndex, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn lease_offers)]
+	/// Lease terms offered by landlords, waiting for the tenant to accept
+	pub type LeaseOffers<T: Config> =
+		StorageMap<_, Blake2_128Concat, LandAsset, LeaseOffer<T::AccountId, BalanceOf<T>, T::BlockNumber>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn parcel_content)]
+	/// Scene built on an estate or land unit
+	pub type ParcelContents<T: Config> =
+		StorageMap<_, Blake2_128Concat, LandAsset, ParcelContent<T::AccountId, T::BlockNumber>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn builder_grant)]
+	/// Accounts allowed to update the content of an estate or land unit
+	pub type BuilderGrants<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		LandAsset,
+		Blake2_128Concat,
+		T::AccountId,
+		BuilderGrant<T::AccountId, T::BlockNumber>,
+		OptionQuery,
+	>;
+
+	/// Land tax settings of metaverses which opted in to self-assessed land tax
+	#[pallet::storage]
+	#[pallet::getter(fn get_land_tax_config)]
+	pub type LandTaxConfigs<T: Config> =
+		StorageMap<_, Blake2_128Concat, MetaverseId, LandTaxConfig<T::BlockNumber>, OptionQuery>;
+
+	/// Block since which the land tax of a metaverse has been enabled without a break
+	#[pallet::storage]
+	#[pallet::getter(fn land_tax_enabled_since)]
+	pub type LandTaxEnabledSince<T: Config> = StorageMap<_, Blake2_128Concat, MetaverseId, T::BlockNumber, ValueQuery>;
+
+	/// Self-assessed prices of estates and land units
+	#[pallet::storage]
+	#[pallet::getter(fn get_self_assessment)]
+	pub type SelfAssessments<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		LandAsset,
+		SelfAssessment<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn leases)]
+	/// Active leases of estates and land units
+	pub type Leases<T: Config> =
+		StorageMap<_, Blake2_128Concat, LandAsset, LeaseInfo<T::AccountId, BalanceOf<T>, T::BlockNumber>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn loan_requests)]
+	/// Loans owners ask for against their estates and land units
+	pub type LoanRequests<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		LandAsset,
+		LoanRequest<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn loans)]
+	/// Funded loans, keyed by the estate or land unit locked as collateral
+	pub type Loans<T: Config> =
+		StorageMap<_, Blake2_128Concat, LandAsset, LoanInfo<T::AccountId, BalanceOf<T>, T::BlockNumber>, OptionQuery>;
+
+	/// Open primary land sale of each metaverse
+	#[pallet::storage]
+	#[pallet::getter(fn get_land_sale)]
+	pub type LandSales<T: Config> =
+		StorageMap<_, Blake2_128Concat, MetaverseId, LandSale<BalanceOf<T>, T::BlockNumber>, OptionQuery>;
+
+	/// Accounts which can buy during the whitelist phase of a land sale
+	#[pallet::storage]
+	#[pallet::getter(fn land_sale_whitelist)]
+	pub type LandSaleWhitelist<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, MetaverseId, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+	/// Land units each account has bought in the open land sale of a metaverse
+	#[pallet::storage]
+	#[pallet::getter(fn land_sale_purchases)]
+	pub type LandSalePurchases<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, MetaverseId, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig {
 		pub minting_rate_config: MintingRateInfo,
@@ -273,6 +448,62 @@ pub mod pallet {
 		EstateStakeLeft(T::AccountId, EstateId),
 		/// Account Id, Balance
 		StakingRewarded(T::AccountId, BalanceOf<T>),
+		/// Round, Total Reward, Total Staked
+		StakingRewardIssued(RoundIndex, BalanceOf<T>, BalanceOf<T>),
+		/// Lease Asset, Landlord Account Id, Tenant Account Id, Rent Per Period
+		LeaseOffered(LandAsset, T::AccountId, T::AccountId, BalanceOf<T>),
+		/// Lease Asset
+		LeaseOfferCancelled(LandAsset),
+		/// Lease Asset, Landlord Account Id, Tenant Account Id, End Block
+		LeaseStarted(LandAsset, T::AccountId, T::AccountId, T::BlockNumber),
+		/// Lease Asset, Tenant Account Id, Rent, Paid Until Block
+		RentPaid(LandAsset, T::AccountId, BalanceOf<T>, T::BlockNumber),
+		/// Lease Asset, Landlord Account Id, Tenant Account Id
+		LeaseTerminated(LandAsset, T::AccountId, T::AccountId),
+		/// Metaverse Id, Estate Shape Rule
+		EstateShapeRuleSet(MetaverseId, EstateShapeRule),
+		/// Land Asset, Builder Account Id, Expiry Block
+		BuilderGranted(LandAsset, T::AccountId, Option<T::BlockNumber>),
+		/// Land Asset, Builder Account Id
+		BuilderRevoked(LandAsset, T::AccountId),
+		/// Land Asset, Updater Account Id, Content Version, Manifest Hash
+		ParcelContentUpdated(LandAsset, T::AccountId, u32, Hash),
+		/// Kept Estate Id, Owner Account Id, Merged Estate Ids
+		EstatesMerged(EstateId, T::AccountId, Vec<EstateId>),
+		/// Split Estate Id, Owner Account Id, New Estate Ids
+		EstateSplit(EstateId, T::AccountId, Vec<EstateId>),
+		/// Owner Account Id, Split Undeployed Land Block Id, New Undeployed Land Block Ids
+		UndeployedLandBlockSplit(T::AccountId, UndeployedLandBlockId, Vec<UndeployedLandBlockId>),
+		/// Owner Account Id, Kept Undeployed Land Block Id, Merged Undeployed Land Block Ids
+		UndeployedLandBlocksMerged(T::AccountId, UndeployedLandBlockId, Vec<UndeployedLandBlockId>),
+		/// Metaverse Id, Land Tax Config
+		LandTaxConfigSet(MetaverseId, Option<LandTaxConfig<T::BlockNumber>>),
+		/// Land Asset, Owner Account Id, Price
+		LandSelfAssessed(LandAsset, T::AccountId, BalanceOf<T>),
+		/// Land Asset, Owner Account Id, Tax, Paid Until Block
+		LandTaxPaid(LandAsset, T::AccountId, BalanceOf<T>, T::BlockNumber),
+		/// Land Asset, From Account Id, To Account Id, Price
+		LandForceBought(LandAsset, T::AccountId, T::AccountId, BalanceOf<T>),
+		/// Land Asset, Previous Owner Account Id, Metaverse Fund Account Id
+		LandReclaimed(LandAsset, T::AccountId, T::AccountId),
+		/// Land Asset, Borrower Account Id, Lender Account Id, Principal
+		LoanRequested(LandAsset, T::AccountId, T::AccountId, BalanceOf<T>),
+		/// Land Asset
+		LoanRequestCancelled(LandAsset),
+		/// Land Asset, Borrower Account Id, Lender Account Id, Due Block
+		LoanFunded(LandAsset, T::AccountId, T::AccountId, T::BlockNumber),
+		/// Land Asset, Borrower Account Id, Lender Account Id, Repayment
+		LoanRepaid(LandAsset, T::AccountId, T::AccountId, BalanceOf<T>),
+		/// Land Asset, Borrower Account Id, Lender Account Id
+		CollateralClaimed(LandAsset, T::AccountId, T::AccountId),
+		/// Metaverse Id
+		LandSaleOpened(MetaverseId),
+		/// Metaverse Id
+		LandSaleClosed(MetaverseId),
+		/// Metaverse Id, Account Ids, Whitelisted
+		LandSaleWhitelistUpdated(MetaverseId, Vec<T::AccountId>, bool),
+		/// Buyer Account Id, Metaverse Id, Coordinates, Total Price
+		LandBought(T::AccountId, MetaverseId, Vec<(i32, i32)>, BalanceOf<T>),
 	}
 
 	#[pallet::error]
@@ -304,6 +535,7 @@ pub mod pallet {
 		LandUnitNotInAuction,
 		EstateAlreadyInAuction,
 		LandUnitAlreadyInAuction,
+		UndeployedLandBlockAlreadyInAuction,
 		EstateDoesNotExist,
 		LandUnitDoesNotExist,
 		OnlyFrozenUndeployedLandBlockCanBeDestroyed,
@@ -311,6 +543,82 @@ pub mod pallet {
 		Overflow,
 		EstateStakeAlreadyLeft,
 		AccountHasNoStake,
+		// Estate is leased out
+		EstateIsLeased,
+		// Land unit is leased out
+		LandUnitIsLeased,
+		// Lease has already been offered for the asset
+		LeaseOfferAlreadyExists,
+		LeaseOfferNotFound,
+		LeaseNotFound,
+		// Rent, period and term length must be non zero
+		InvalidLeaseTerms,
+		// All rent of the lease term has been paid
+		RentAlreadyPaidInFull,
+		// Rent is overdue past the grace period
+		RentOverdue,
+		// Lease is still running and its rent is up to date
+		LeaseIsActive,
+		// Estates to merge must belong to the same metaverse
+		EstatesNotInSameMetaverse,
+		// No other estate, or the estate itself, given to merge
+		InvalidEstateMerge,
+		// Split must create at least one estate and leave land units in the split estate
+		InvalidEstateSplit,
+		// Land units do not follow the estate shape rule of the metaverse
+		InvalidEstateShape,
+		// Content URI is longer than allowed
+		ContentUriTooLong,
+		BuilderGrantNotFound,
+		// Builder grant expiry is in the past
+		InvalidBuilderGrantExpiry,
+		// Content version can not be increased further
+		ContentVersionOverflow,
+		// Undeployed land block has an approved account
+		UndeployedLandBlockIsApproved,
+		// Split must create at least one land block and leave land units in the split land block
+		InvalidUndeployedLandBlockSplit,
+		// No other land block, or the land block itself, given to merge
+		InvalidUndeployedLandBlockMerge,
+		// Undeployed land blocks to merge must be of the same type
+		UndeployedLandBlockTypeMismatch,
+		// Metaverse has not opted in to land tax
+		LandTaxNotEnabled,
+		// Tax period must not be zero
+		InvalidLandTaxConfig,
+		// Self-assessed price must not be zero
+		InvalidLandPrice,
+		SelfAssessmentNotFound,
+		// Land tax of the current period has been paid
+		LandTaxAlreadyPaid,
+		// Land tax is overdue past the grace period
+		LandTaxOverdue,
+		// Land tax is not overdue past the grace period
+		LandTaxNotOverdue,
+		// Self-assessed price is above the price the buyer accepts
+		LandPriceAboveLimit,
+		// Estate is locked as loan collateral
+		EstateIsCollateralized,
+		// Land unit is locked as loan collateral
+		LandUnitIsCollateralized,
+		// Loan has already been requested for the asset
+		LoanRequestAlreadyExists,
+		LoanRequestNotFound,
+		LoanNotFound,
+		// Principal must not be zero or above the repayment, and the duration must not be zero
+		InvalidLoanTerms,
+		// Loan is not due yet
+		LoanNotDue,
+		// Land sale area or zones are outside the metaverse bounds, or a price is zero
+		InvalidLandSale,
+		LandSaleAlreadyOpen,
+		LandSaleNotFound,
+		// Coordinate is outside the area on sale
+		LandUnitNotForSale,
+		// Land sale is in its whitelist phase and the buyer is not whitelisted
+		NotWhitelistedForLandSale,
+		// Buyer would exceed the land units one account can buy in the sale
+		LandSalePurchaseLimitExceeded,
 	}
 
 	#[pallet::call]
@@ -331,6 +639,22 @@ pub mod pallet {
 			Ok(().into())
 		}
 
+		/// Set the shape rule new and reshaped estates of the metaverse must follow
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		pub fn set_estate_shape_rule(
+			origin: OriginFor<T>,
+			metaverse_id: MetaverseId,
+			rule: EstateShapeRule,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			EstateShapeRules::<T>::insert(metaverse_id, rule);
+
+			Self::deposit_event(Event::<T>::EstateShapeRuleSet(metaverse_id, rule));
+
+			Ok(().into())
+		}
+
 		#[pallet::weight(T::WeightInfo::mint_land())]
 		#[transactional]
 		pub fn mint_land(
@@ -400,6 +724,8 @@ pub mod pallet {
 				!T::AuctionHandler::check_item_in_auction(ItemId::LandUnit(coordinate, metaverse_id)),
 				Error::<T>::LandUnitAlreadyInAuction
 			);
+			Self::ensure_not_leased(LandAsset::LandUnit(coordinate, metaverse_id))?;
+			Self::ensure_not_collateralized(LandAsset::LandUnit(coordinate, metaverse_id))?;
 
 			Self::do_transfer_landunit(coordinate, &who, &to, metaverse_id)?;
 			Ok(().into())
@@ -416,6 +742,8 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			ensure_root(origin)?;
 
+			Self::ensure_valid_estate_shape(metaverse_id, &coordinates)?;
+
 			// Generate new estate id
 			let new_estate_id = Self::get_new_estate_id()?;
 
@@ -445,6 +773,8 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			ensure_root(origin)?;
 
+			Self::ensure_valid_estate_shape(metaverse_id, &coordinates)?;
+
 			// Generate new estate id
 			let new_estate_id = Self::get_new_estate_id()?;
 
@@ -452,8 +782,22 @@ pub mod pallet {
 			let estate_account_id: T::AccountId = T::LandTreasury::get().into_sub_account(new_estate_id);
 
 			// Mint land units
-			for coordinate in coordinates.clone() {
+			for (index, coordinate) in coordinates.clone().into_iter().enumerate() {
+				// Listed, leased and collateralized land units can not join an estate
+				ensure!(
+					!T::AuctionHandler::check_item_in_auction(ItemId::LandUnit(coordinate, metaverse_id)),
+					Error::<T>::LandUnitAlreadyInAuction
+				);
+				Self::ensure_not_leased(LandAsset::LandUnit(coordinate, metaverse_id))?;
+				Self::ensure_not_collateralized(LandAsset::LandUnit(coordinate, metaverse_id))?;
+
 				Self::mint_land_unit(metaverse_id, &estate_account_id, coordinate, true)?;
+				Self::merge_self_assessment(
+					new_estate_id,
+					LandAsset::LandUnit(coordinate, metaverse_id),
+					&beneficiary,
+					index == 0,
+				);
 			}
 
 			// Update estate information
@@ -476,6 +820,8 @@ pub mod pallet {
 				!T::AuctionHandler::check_item_in_auction(ItemId::Estate(estate_id)),
 				Error::<T>::EstateAlreadyInAuction
 			);
+			Self::ensure_not_leased(LandAsset::Estate(estate_id))?;
+			Self::ensure_not_collateralized(LandAsset::Estate(estate_id))?;
 
 			Self::do_transfer_estate(estate_id, &who, &to)?;
 
@@ -511,6 +857,9 @@ pub mod pallet {
 						Error::<T>::UndeployedLandBlockFreezed
 					);
 
+					Self::ensure_undeployed_land_block_not_in_auction(undeployed_land_block_id)?;
+					Self::ensure_valid_estate_shape(metaverse_id, &coordinates)?;
+
 					let land_units_to_mint = coordinates.len() as u32;
 					ensure!(
 						undeployed_land_block_record.number_land_units > land_units_to_mint,
@@ -593,25 +942,9 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			ensure_root(origin)?;
 
-			UndeployedLandBlocks::<T>::try_mutate_exists(
-				&undeployed_land_block_id,
-				|undeployed_land_block| -> DispatchResultWithPostInfo {
-					let mut undeployed_land_block_record = undeployed_land_block
-						.as_mut()
-						.ok_or(Error::<T>::UndeployedLandBlockNotFound)?;
-
-					ensure!(
-						undeployed_land_block_record.is_frozen == true,
-						Error::<T>::UndeployedLandBlockNotFrozen
-					);
-
-					undeployed_land_block_record.is_frozen = false;
+			Self::do_unfreeze_undeployed_land_block(undeployed_land_block_id)?;
 
-					Self::deposit_event(Event::<T>::UndeployedLandBlockUnfreezed(undeployed_land_block_id));
-
-					Ok(().into())
-				},
-			)
+			Ok(().into())
 		}
 
 		/// Transfer raw land block
@@ -670,6 +1003,8 @@ pub mod pallet {
 						Error::<T>::UndeployedLandBlockAlreadyFreezed
 					);
 
+					Self::ensure_undeployed_land_block_not_in_auction(undeployed_land_block_id)?;
+
 					undeployed_land_block_record.approved = Some(to.clone());
 
 					Self::deposit_event(Event::<T>::UndeployedLandBlockApproved(
@@ -720,6 +1055,125 @@ pub mod pallet {
 			)
 		}
 
+		/// Split land units off an undeployed land block into new land blocks with the given
+		/// land unit counts. New land blocks keep the owner and type of the split land block.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 3) * (land_unit_counts.len() as u64 + 1))]
+		#[transactional]
+		pub fn split_undeployed_land_block(
+			origin: OriginFor<T>,
+			undeployed_land_block_id: UndeployedLandBlockId,
+			land_unit_counts: Vec<u32>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				!land_unit_counts.is_empty(),
+				Error::<T>::InvalidUndeployedLandBlockSplit
+			);
+
+			let mut undeployed_land_block_record = UndeployedLandBlocks::<T>::get(undeployed_land_block_id)
+				.ok_or(Error::<T>::UndeployedLandBlockNotFound)?;
+			Self::ensure_undeployed_land_block_can_be_reshaped(&who, &undeployed_land_block_record)?;
+
+			let mut new_undeployed_land_block_ids: Vec<UndeployedLandBlockId> = Vec::new();
+
+			for number_land_units in land_unit_counts {
+				ensure!(number_land_units > 0, Error::<T>::InvalidUndeployedLandBlockSplit);
+
+				undeployed_land_block_record.number_land_units = undeployed_land_block_record
+					.number_land_units
+					.checked_sub(number_land_units)
+					.ok_or(Error::<T>::UndeployedLandBlockDoesNotHaveEnoughLandUnits)?;
+
+				let new_undeployed_land_block_id = Self::get_new_undeployed_land_block_id()?;
+				let new_undeployed_land_block = UndeployedLandBlock {
+					id: new_undeployed_land_block_id,
+					number_land_units,
+					undeployed_land_block_type: undeployed_land_block_record.undeployed_land_block_type,
+					approved: None,
+					is_frozen: false,
+					owner: who.clone(),
+				};
+
+				UndeployedLandBlocks::<T>::insert(new_undeployed_land_block_id, new_undeployed_land_block);
+				UndeployedLandBlocksOwner::<T>::insert(who.clone(), new_undeployed_land_block_id, ());
+				new_undeployed_land_block_ids.push(new_undeployed_land_block_id);
+			}
+
+			// The split land block must keep at least one land unit
+			ensure!(
+				undeployed_land_block_record.number_land_units > 0,
+				Error::<T>::InvalidUndeployedLandBlockSplit
+			);
+
+			UndeployedLandBlocks::<T>::insert(undeployed_land_block_id, undeployed_land_block_record);
+
+			Self::deposit_event(Event::<T>::UndeployedLandBlockSplit(
+				who,
+				undeployed_land_block_id,
+				new_undeployed_land_block_ids,
+			));
+
+			Ok(().into())
+		}
+
+		/// Merge other undeployed land blocks of the same owner and type into an existing
+		/// undeployed land block. The other land blocks are destroyed.
+		#[pallet::weight(
+			10_000 + T::DbWeight::get().reads_writes(2, 2) * (other_undeployed_land_block_ids.len() as u64 + 1)
+		)]
+		#[transactional]
+		pub fn merge_undeployed_land_blocks(
+			origin: OriginFor<T>,
+			undeployed_land_block_id: UndeployedLandBlockId,
+			other_undeployed_land_block_ids: Vec<UndeployedLandBlockId>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				!other_undeployed_land_block_ids.is_empty(),
+				Error::<T>::InvalidUndeployedLandBlockMerge
+			);
+
+			let mut undeployed_land_block_record = UndeployedLandBlocks::<T>::get(undeployed_land_block_id)
+				.ok_or(Error::<T>::UndeployedLandBlockNotFound)?;
+			Self::ensure_undeployed_land_block_can_be_reshaped(&who, &undeployed_land_block_record)?;
+
+			for other_undeployed_land_block_id in other_undeployed_land_block_ids.iter() {
+				ensure!(
+					*other_undeployed_land_block_id != undeployed_land_block_id,
+					Error::<T>::InvalidUndeployedLandBlockMerge
+				);
+
+				// Taking the record also rejects the same land block given twice
+				let other_undeployed_land_block = UndeployedLandBlocks::<T>::take(other_undeployed_land_block_id)
+					.ok_or(Error::<T>::UndeployedLandBlockNotFound)?;
+				Self::ensure_undeployed_land_block_can_be_reshaped(&who, &other_undeployed_land_block)?;
+				ensure!(
+					other_undeployed_land_block.undeployed_land_block_type
+						== undeployed_land_block_record.undeployed_land_block_type,
+					Error::<T>::UndeployedLandBlockTypeMismatch
+				);
+
+				undeployed_land_block_record.number_land_units = undeployed_land_block_record
+					.number_land_units
+					.checked_add(other_undeployed_land_block.number_land_units)
+					.ok_or(Error::<T>::Overflow)?;
+
+				UndeployedLandBlocksOwner::<T>::remove(&who, other_undeployed_land_block_id);
+			}
+
+			UndeployedLandBlocks::<T>::insert(undeployed_land_block_id, undeployed_land_block_record);
+
+			Self::deposit_event(Event::<T>::UndeployedLandBlocksMerged(
+				who,
+				undeployed_land_block_id,
+				other_undeployed_land_block_ids,
+			));
+
+			Ok(().into())
+		}
+
 		/// Dissolve estate to land units
 		#[pallet::weight(T::WeightInfo::dissolve_estate())]
 		#[transactional]
@@ -730,6 +1184,8 @@ pub mod pallet {
 				!T::AuctionHandler::check_item_in_auction(ItemId::Estate(estate_id)),
 				Error::<T>::EstateAlreadyInAuction
 			);
+			Self::ensure_not_leased(LandAsset::Estate(estate_id))?;
+			Self::ensure_not_collateralized(LandAsset::Estate(estate_id))?;
 
 			let estate_info = Estates::<T>::get(estate_id).ok_or(Error::<T>::EstateDoesNotExist)?;
 
@@ -739,9 +1195,25 @@ pub mod pallet {
 
 				// Reset estate ownership
 				*estate_by_owner = None;
+				OwnerOfEstate::<T>::remove(estate_id);
 
 				// Remove estate
 				Estates::<T>::remove(&estate_id);
+				Self::clear_estate_content(estate_id);
+
+				// Each land unit takes its share of the self-assessment of the estate
+				let mut land_unit_count = estate_info.land_units.len() as u32;
+				for land_unit in estate_info.land_units.iter() {
+					Self::split_self_assessment(
+						estate_id,
+						LandAsset::LandUnit(*land_unit, estate_info.metaverse_id),
+						1,
+						land_unit_count,
+						&who,
+					);
+					land_unit_count = land_unit_count.saturating_sub(1);
+				}
+				SelfAssessments::<T>::remove(LandAsset::Estate(estate_id));
 
 				// Update total estates
 				let total_estates_count = Self::all_estates_count();
@@ -752,6 +1224,7 @@ pub mod pallet {
 
 				// Update land units relationship
 				for land_unit in estate_info.land_units.clone() {
+					LandUnitEstate::<T>::remove(estate_info.metaverse_id, land_unit);
 					LandUnits::<T>::try_mutate_exists(
 						&estate_info.metaverse_id,
 						&land_unit,
@@ -783,6 +1256,8 @@ pub mod pallet {
 				!T::AuctionHandler::check_item_in_auction(ItemId::Estate(estate_id)),
 				Error::<T>::EstateAlreadyInAuction
 			);
+			Self::ensure_not_leased(LandAsset::Estate(estate_id))?;
+			Self::ensure_not_collateralized(LandAsset::Estate(estate_id))?;
 
 			let estate_info: EstateInfo = Estates::<T>::get(estate_id).ok_or(Error::<T>::EstateDoesNotExist)?;
 
@@ -798,6 +1273,8 @@ pub mod pallet {
 					Self::get_land_units(estate_info.metaverse_id, land_unit) == Some(who.clone()),
 					Error::<T>::LandUnitDoesNotExist
 				);
+				Self::ensure_not_leased(LandAsset::LandUnit(land_unit, estate_info.metaverse_id))?;
+				Self::ensure_not_collateralized(LandAsset::LandUnit(land_unit, estate_info.metaverse_id))?;
 			}
 
 			// Mutate estates
@@ -805,12 +1282,21 @@ pub mod pallet {
 				// Append new coordinates to estate
 				let mut mut_estate_info = maybe_estate_info.as_mut().ok_or(Error::<T>::EstateDoesNotExist)?;
 				mut_estate_info.land_units.append(&mut land_units.clone());
+				Self::ensure_valid_estate_shape(mut_estate_info.metaverse_id, &mut_estate_info.land_units)?;
 
 				// Mutate land unit ownership
 				let estate_account_id: T::AccountId = T::LandTreasury::get().into_sub_account(estate_id);
 
 				// Mutate land unit ownership
 				for land_unit in land_units.clone() {
+					// Land units are assessed as part of the estate from now on
+					Self::merge_self_assessment(
+						estate_id,
+						LandAsset::LandUnit(land_unit, mut_estate_info.metaverse_id),
+						&who,
+						false,
+					);
+					LandUnitEstate::<T>::insert(mut_estate_info.metaverse_id, land_unit, estate_id);
 					LandUnits::<T>::try_mutate_exists(
 						&mut_estate_info.metaverse_id,
 						&land_unit,
@@ -846,6 +1332,8 @@ pub mod pallet {
 				!T::AuctionHandler::check_item_in_auction(ItemId::Estate(estate_id)),
 				Error::<T>::EstateAlreadyInAuction
 			);
+			Self::ensure_not_leased(LandAsset::Estate(estate_id))?;
+			Self::ensure_not_collateralized(LandAsset::Estate(estate_id))?;
 
 			let estate_info: EstateInfo = Estates::<T>::get(estate_id).ok_or(Error::<T>::EstateDoesNotExist)?;
 
@@ -862,9 +1350,21 @@ pub mod pallet {
 				// Mutate land unit ownership
 				for land_unit in land_units.clone() {
 					// Remove coordinates from estate
-					let index = mut_estate_info.land_units.iter().position(|x| *x == land_unit).unwrap();
+					let index = mut_estate_info
+						.land_units
+						.iter()
+						.position(|x| *x == land_unit)
+						.ok_or(Error::<T>::LandUnitDoesNotExist)?;
+					Self::split_self_assessment(
+						estate_id,
+						LandAsset::LandUnit(land_unit, mut_estate_info.metaverse_id),
+						1,
+						mut_estate_info.land_units.len() as u32,
+						&who,
+					);
 					mut_estate_info.land_units.remove(index);
 
+					LandUnitEstate::<T>::remove(mut_estate_info.metaverse_id, land_unit);
 					LandUnits::<T>::try_mutate_exists(
 						&mut_estate_info.metaverse_id,
 						&land_unit,
@@ -876,6 +1376,8 @@ pub mod pallet {
 					);
 				}
 
+				Self::ensure_valid_estate_shape(mut_estate_info.metaverse_id, &mut_estate_info.land_units)?;
+
 				Self::deposit_event(Event::<T>::LandUnitsRemoved(
 					estate_id.clone(),
 					who.clone(),
@@ -886,6 +1388,156 @@ pub mod pallet {
 			})
 		}
 
+		/// Merge other estates into an existing estate. The land units and stake of the other
+		/// estates move to the kept estate and the other estates are destroyed.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(10, 10) * (other_estate_ids.len() as u64 + 1))]
+		#[transactional]
+		pub fn merge_estates(
+			origin: OriginFor<T>,
+			estate_id: EstateId,
+			other_estate_ids: Vec<EstateId>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!other_estate_ids.is_empty(), Error::<T>::InvalidEstateMerge);
+
+			let mut estate_info: EstateInfo = Estates::<T>::get(estate_id).ok_or(Error::<T>::EstateDoesNotExist)?;
+			Self::ensure_estate_can_be_reshaped(&who, estate_id)?;
+
+			let estate_account_id: T::AccountId = T::LandTreasury::get().into_sub_account(estate_id);
+
+			for other_estate_id in other_estate_ids.clone() {
+				ensure!(other_estate_id != estate_id, Error::<T>::InvalidEstateMerge);
+
+				// Merged estates are removed as we go, so a duplicated id is caught here
+				let other_estate_info: EstateInfo =
+					Estates::<T>::get(other_estate_id).ok_or(Error::<T>::EstateDoesNotExist)?;
+				ensure!(
+					other_estate_info.metaverse_id == estate_info.metaverse_id,
+					Error::<T>::EstatesNotInSameMetaverse
+				);
+				Self::ensure_estate_can_be_reshaped(&who, other_estate_id)?;
+
+				for land_unit in other_estate_info.land_units {
+					LandUnits::<T>::insert(estate_info.metaverse_id, land_unit, estate_account_id.clone());
+					LandUnitEstate::<T>::insert(estate_info.metaverse_id, land_unit, estate_id);
+					estate_info.land_units.push(land_unit);
+				}
+
+				Self::move_estate_stake(other_estate_id, estate_id);
+				Self::merge_self_assessment(estate_id, LandAsset::Estate(other_estate_id), &who, false);
+
+				Estates::<T>::remove(other_estate_id);
+				EstateOwner::<T>::remove(&who, other_estate_id);
+				OwnerOfEstate::<T>::remove(other_estate_id);
+				LeaseOffers::<T>::remove(LandAsset::Estate(other_estate_id));
+				LoanRequests::<T>::remove(LandAsset::Estate(other_estate_id));
+				Self::clear_estate_content(other_estate_id);
+			}
+
+			Self::ensure_valid_estate_shape(estate_info.metaverse_id, &estate_info.land_units)?;
+			Estates::<T>::insert(estate_id, estate_info);
+
+			let total_estates_count = Self::all_estates_count();
+			let new_total_estates_count = total_estates_count
+				.checked_sub(other_estate_ids.len() as u64)
+				.ok_or("Overflow subtracting merged estates from total estates")?;
+			AllEstatesCount::<T>::put(new_total_estates_count);
+
+			Self::deposit_event(Event::<T>::EstatesMerged(estate_id, who, other_estate_ids));
+
+			Ok(().into())
+		}
+
+		/// Split land units off an estate into new estates, one per group of land units. The
+		/// split estate keeps its id and its remaining land units. Stake is split between the
+		/// estates pro rata by land unit count.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5, 5) * (land_unit_groups.len() as u64 + 1))]
+		#[transactional]
+		pub fn split_estate(
+			origin: OriginFor<T>,
+			estate_id: EstateId,
+			land_unit_groups: Vec<Vec<(i32, i32)>>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!land_unit_groups.is_empty(), Error::<T>::InvalidEstateSplit);
+
+			let mut estate_info: EstateInfo = Estates::<T>::get(estate_id).ok_or(Error::<T>::EstateDoesNotExist)?;
+			Self::ensure_estate_can_be_reshaped(&who, estate_id)?;
+
+			// Stakers who left get their bond back, the others keep track of the stake not split off
+			let land_unit_count = estate_info.land_units.len() as u32;
+			let mut stakes: Vec<(T::AccountId, BalanceOf<T>, BalanceOf<T>)> = Vec::new();
+			for (staker, amount) in EstateStake::<T>::iter_prefix(estate_id).collect::<Vec<_>>() {
+				if Self::exit_queue(&staker, estate_id).is_some() {
+					Self::return_exiting_stake(&staker, estate_id);
+				} else {
+					stakes.push((staker, amount, amount));
+				}
+			}
+
+			let mut new_estate_ids: Vec<EstateId> = Vec::new();
+
+			for land_units in land_unit_groups {
+				ensure!(!land_units.is_empty(), Error::<T>::InvalidEstateSplit);
+
+				let new_estate_id = Self::get_new_estate_id()?;
+
+				let share = Perbill::from_rational(land_units.len() as u32, land_unit_count);
+				for (staker, amount, remaining) in stakes.iter_mut() {
+					let split_amount = (share * *amount).min(*remaining);
+					if !split_amount.is_zero() {
+						*remaining = remaining.saturating_sub(split_amount);
+						EstateStake::<T>::insert(new_estate_id, &*staker, split_amount);
+					}
+				}
+
+				Self::split_self_assessment(
+					estate_id,
+					LandAsset::Estate(new_estate_id),
+					land_units.len() as u32,
+					estate_info.land_units.len() as u32,
+					&who,
+				);
+
+				let new_estate_account_id: T::AccountId = T::LandTreasury::get().into_sub_account(new_estate_id);
+
+				for land_unit in land_units.iter() {
+					let index = estate_info
+						.land_units
+						.iter()
+						.position(|x| x == land_unit)
+						.ok_or(Error::<T>::LandUnitDoesNotExist)?;
+					estate_info.land_units.remove(index);
+
+					LandUnits::<T>::insert(estate_info.metaverse_id, land_unit, new_estate_account_id.clone());
+				}
+
+				Self::ensure_valid_estate_shape(estate_info.metaverse_id, &land_units)?;
+				Self::update_estate_information(new_estate_id, estate_info.metaverse_id, &who, land_units)?;
+				new_estate_ids.push(new_estate_id);
+			}
+
+			// The split estate must keep at least one land unit
+			ensure!(!estate_info.land_units.is_empty(), Error::<T>::InvalidEstateSplit);
+			Self::ensure_valid_estate_shape(estate_info.metaverse_id, &estate_info.land_units)?;
+
+			for (staker, _, remaining) in stakes {
+				if remaining.is_zero() {
+					EstateStake::<T>::remove(estate_id, &staker);
+				} else {
+					EstateStake::<T>::insert(estate_id, &staker, remaining);
+				}
+			}
+
+			Estates::<T>::insert(estate_id, estate_info);
+
+			Self::deposit_event(Event::<T>::EstateSplit(estate_id, who, new_estate_ids));
+
+			Ok(().into())
+		}
+
 		/// Bond native token to the estate
 		#[pallet::weight(T::WeightInfo::bond_more())]
 		#[transactional]
@@ -990,94 +1642,938 @@ pub mod pallet {
 
 			Ok(().into())
 		}
-	}
 
-	impl<T: Config> Pallet<T> {
-		fn pay_stakers(next: RoundIndex) {
-			// payout is next - duration rounds ago => next - duration > 0 else return early
-			let duration = T::RewardPaymentDelay::get();
-			if next <= duration {
-				return;
-			}
-			let round_to_payout = next - duration;
+		/// Offer a lease of an estate or land unit to a tenant. Rent is due every `period`
+		/// blocks for `periods` periods.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 1))]
+		pub fn offer_lease(
+			origin: OriginFor<T>,
+			asset: LandAsset,
+			tenant: T::AccountId,
+			currency_id: FungibleTokenId,
+			rent: BalanceOf<T>,
+			period: T::BlockNumber,
+			periods: u32,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
 
-			// issue BIT for rewards distribution
-			let total_staked = <Staked<T>>::get(round_to_payout);
-			let total_issuance = Self::compute_issuance(total_staked);
+			ensure!(Self::is_land_asset_owner(&who, &asset), Error::<T>::NoPermission);
+			ensure!(who != tenant, Error::<T>::NoPermission);
+			ensure!(
+				!rent.is_zero() && !period.is_zero() && periods > 0,
+				Error::<T>::InvalidLeaseTerms
+			);
+			Self::ensure_not_in_auction(&asset)?;
+			Self::ensure_not_leased(asset)?;
+			Self::ensure_not_collateralized(asset)?;
+			ensure!(
+				!LeaseOffers::<T>::contains_key(asset),
+				Error::<T>::LeaseOfferAlreadyExists
+			);
 
-			let mut left_issuance = total_issuance;
+			LeaseOffers::<T>::insert(
+				asset,
+				LeaseOffer {
+					landlord: who.clone(),
+					tenant: tenant.clone(),
+					currency_id,
+					rent,
+					period,
+					periods,
+				},
+			);
 
-			// a local fn to transfer rewards to the account specified
-			let mint = |amt: BalanceOf<T>, to: T::AccountId| {
-				if let Ok(imb) = T::Currency::deposit_into_existing(&to, amt) {
-					Self::deposit_event(Event::StakingRewarded(to.clone(), imb.peek()));
-				}
-			};
+			Self::deposit_event(Event::<T>::LeaseOffered(asset, who, tenant, rent));
 
-			for (estate_id, stake_snapshot) in <AtStake<T>>::drain_prefix(round_to_payout) {
-				for Bond { staker: owner, amount } in stake_snapshot.stakers {
-					// TODO: TBD on the rewards amount
-					let amount_due = amount;
-					mint(1u32.into(), owner);
-				}
-			}
+			Ok(().into())
 		}
 
-		/// Clear exit queue. return stake to account
-		fn clear_exit_queue(now: RoundIndex) {
-			for (account_id, estate_id, val) in <ExitQueue<T>>::drain() {
-				let staked_amount = <EstateStake<T>>::get(estate_id, &account_id);
+		/// Withdraw a lease offer. Either the landlord or the tenant can cancel it.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn cancel_lease_offer(origin: OriginFor<T>, asset: LandAsset) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
 
-				// return stake to account
-				T::Currency::unreserve(&account_id, staked_amount);
+			let offer = LeaseOffers::<T>::get(asset).ok_or(Error::<T>::LeaseOfferNotFound)?;
+			ensure!(offer.landlord == who || offer.tenant == who, Error::<T>::NoPermission);
 
-				<EstateStake<T>>::remove(estate_id, &account_id);
-			}
+			LeaseOffers::<T>::remove(asset);
+
+			Self::deposit_event(Event::<T>::LeaseOfferCancelled(asset));
+
+			Ok(().into())
 		}
 
-		fn update_stake_snapshot(next: RoundIndex) -> BalanceOf<T> {
-			let mut total = BalanceOf::<T>::zero();
+		/// Accept a lease offer and pay the rent of the first period
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(6, 4))]
+		#[transactional]
+		pub fn accept_lease(origin: OriginFor<T>, asset: LandAsset) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
 
-			for estate_id in <Estates<T>>::iter_keys() {
-				let mut total_bond = BalanceOf::<T>::zero();
-				let mut stakers: Vec<Bond<T::AccountId, BalanceOf<T>>> = Vec::new();
+			let offer = LeaseOffers::<T>::get(asset).ok_or(Error::<T>::LeaseOfferNotFound)?;
+			ensure!(offer.tenant == who, Error::<T>::NoPermission);
 
-				for (account_id, amount) in <EstateStake<T>>::iter_prefix(estate_id) {
-					stakers.push(Bond {
-						staker: account_id.clone(),
-						amount,
-					});
+			// The property might have changed hands since the offer was made
+			ensure!(
+				Self::is_land_asset_owner(&offer.landlord, &asset),
+				Error::<T>::NoPermission
+			);
+			Self::ensure_not_in_auction(&asset)?;
+			Self::ensure_not_collateralized(asset)?;
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			let lease = LeaseInfo {
+				landlord: offer.landlord,
+				tenant: offer.tenant,
+				currency_id: offer.currency_id,
+				rent: offer.rent,
+				period: offer.period,
+				end_block: now.saturating_add(offer.period.saturating_mul(offer.periods.into())),
+				paid_until: now.saturating_add(offer.period),
+			};
 
-					total += amount;
-					total_bond += amount;
-				}
-				if stakers.len() > 0 {
-					<AtStake<T>>::insert(next, estate_id, StakeSnapshot { stakers, total_bond });
-				}
-			}
+			Self::transfer_rent(&lease)?;
+
+			LeaseOffers::<T>::remove(asset);
+			Leases::<T>::insert(asset, lease.clone());
 
-			<TotalStake<T>>::put(total);
-			total
+			Self::deposit_event(Event::<T>::LeaseStarted(
+				asset,
+				lease.landlord,
+				lease.tenant.clone(),
+				lease.end_block,
+			));
+			Self::deposit_event(Event::<T>::RentPaid(asset, lease.tenant, lease.rent, lease.paid_until));
+
+			Ok(().into())
 		}
 
-		fn compute_issuance(staked: BalanceOf<T>) -> BalanceOf<T> {
-			//TODO: need to decide on how much BIT need to be issued per session
-			staked.saturating_add(staked)
+		/// Pay the rent of the next period of the lease
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 3))]
+		#[transactional]
+		pub fn pay_rent(origin: OriginFor<T>, asset: LandAsset) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			Leases::<T>::try_mutate(asset, |maybe_lease| -> DispatchResultWithPostInfo {
+				let lease = maybe_lease.as_mut().ok_or(Error::<T>::LeaseNotFound)?;
+				ensure!(lease.tenant == who, Error::<T>::NoPermission);
+				ensure!(lease.paid_until < lease.end_block, Error::<T>::RentAlreadyPaidInFull);
+				ensure!(!Self::is_rent_overdue(lease), Error::<T>::RentOverdue);
+
+				Self::transfer_rent(lease)?;
+				lease.paid_until = lease.paid_until.saturating_add(lease.period).min(lease.end_block);
+
+				Self::deposit_event(Event::<T>::RentPaid(asset, who.clone(), lease.rent, lease.paid_until));
+
+				Ok(().into())
+			})
 		}
-	}
-}
 
-impl<T: Config> Pallet<T> {
-	fn get_new_estate_id() -> Result<EstateId, DispatchError> {
-		let estate_id = NextEstateId::<T>::try_mutate(|id| -> Result<EstateId, DispatchError> {
-			let current_id = *id;
-			*id = id.checked_add(One::one()).ok_or(Error::<T>::NoAvailableEstateId)?;
-			Ok(current_id)
-		})?;
-		Ok(estate_id)
-	}
+		/// Terminate a lease once its term has ended or its rent is overdue past the grace
+		/// period. Usage rights return to the landlord.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn terminate_lease(origin: OriginFor<T>, asset: LandAsset) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
 
-	fn mint_land_unit(
+			let lease = Leases::<T>::get(asset).ok_or(Error::<T>::LeaseNotFound)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(
+				now >= lease.end_block || Self::is_rent_overdue(&lease),
+				Error::<T>::LeaseIsActive
+			);
+
+			Leases::<T>::remove(asset);
+
+			Self::deposit_event(Event::<T>::LeaseTerminated(asset, lease.landlord, lease.tenant));
+
+			Ok(().into())
+		}
+
+		/// Allow an account to update the content of an estate or land unit, optionally until
+		/// a given block. Only the account with usage rights can grant.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 1))]
+		pub fn grant_builder(
+			origin: OriginFor<T>,
+			asset: LandAsset,
+			builder: T::AccountId,
+			expires_at: Option<T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Self::has_usage_rights(&who, &asset), Error::<T>::NoPermission);
+			if let Some(expiry) = expires_at {
+				ensure!(
+					expiry > <frame_system::Pallet<T>>::block_number(),
+					Error::<T>::InvalidBuilderGrantExpiry
+				);
+			}
+
+			BuilderGrants::<T>::insert(
+				asset,
+				&builder,
+				BuilderGrant {
+					granted_by: who,
+					expires_at,
+				},
+			);
+
+			Self::deposit_event(Event::<T>::BuilderGranted(asset, builder, expires_at));
+
+			Ok(().into())
+		}
+
+		/// Revoke builder rights. The account with usage rights or the builder itself can revoke.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 1))]
+		pub fn revoke_builder(
+			origin: OriginFor<T>,
+			asset: LandAsset,
+			builder: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				BuilderGrants::<T>::contains_key(asset, &builder),
+				Error::<T>::BuilderGrantNotFound
+			);
+			ensure!(
+				who == builder || Self::has_usage_rights(&who, &asset),
+				Error::<T>::NoPermission
+			);
+
+			BuilderGrants::<T>::remove(asset, &builder);
+
+			Self::deposit_event(Event::<T>::BuilderRevoked(asset, builder));
+
+			Ok(().into())
+		}
+
+		/// Point an estate or land unit to a new scene manifest
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 1))]
+		pub fn set_parcel_content(
+			origin: OriginFor<T>,
+			asset: LandAsset,
+			manifest_hash: Hash,
+			uri: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				uri.len() as u32 <= T::MaxContentUriLength::get(),
+				Error::<T>::ContentUriTooLong
+			);
+			ensure!(Self::can_build(&who, &asset), Error::<T>::NoPermission);
+
+			let version = ParcelContents::<T>::get(asset).map_or(Ok(1), |content| {
+				content.version.checked_add(1).ok_or(Error::<T>::ContentVersionOverflow)
+			})?;
+
+			ParcelContents::<T>::insert(
+				asset,
+				ParcelContent {
+					manifest_hash,
+					uri,
+					version,
+					updated_by: who.clone(),
+					updated_at: <frame_system::Pallet<T>>::block_number(),
+				},
+			);
+
+			Self::deposit_event(Event::<T>::ParcelContentUpdated(asset, who, version, manifest_hash));
+
+			Ok(().into())
+		}
+
+		/// Opt a metaverse in to self-assessed land tax, update its settings or opt out with
+		/// `None`. Only the metaverse owner can set it.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn set_land_tax_config(
+			origin: OriginFor<T>,
+			metaverse_id: MetaverseId,
+			config: Option<LandTaxConfig<T::BlockNumber>>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				T::MetaverseInfoSource::check_ownership(&who, &metaverse_id),
+				Error::<T>::NoPermission
+			);
+
+			match config {
+				Some(land_tax_config) => {
+					ensure!(!land_tax_config.tax_period.is_zero(), Error::<T>::InvalidLandTaxConfig);
+					// Land tax does not fall due for the time it was disabled
+					if !LandTaxConfigs::<T>::contains_key(metaverse_id) {
+						LandTaxEnabledSince::<T>::insert(metaverse_id, <frame_system::Pallet<T>>::block_number());
+					}
+					LandTaxConfigs::<T>::insert(metaverse_id, land_tax_config);
+				}
+				None => {
+					LandTaxConfigs::<T>::remove(metaverse_id);
+					LandTaxEnabledSince::<T>::remove(metaverse_id);
+				}
+			}
+
+			Self::deposit_event(Event::<T>::LandTaxConfigSet(metaverse_id, config));
+
+			Ok(().into())
+		}
+
+		/// Declare the price of an estate or land unit in a metaverse with land tax. Land is only
+		/// assessed once its owner declares a price, sales then carry the sale price over.
+		/// Changing the price does not change the tax paid.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5, 1))]
+		#[transactional]
+		pub fn self_assess(origin: OriginFor<T>, asset: LandAsset, price: BalanceOf<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Self::is_land_asset_owner(&who, &asset), Error::<T>::NoPermission);
+			ensure!(!price.is_zero(), Error::<T>::InvalidLandPrice);
+
+			let metaverse_id = Self::land_asset_metaverse(&asset)?;
+			ensure!(
+				LandTaxConfigs::<T>::contains_key(metaverse_id),
+				Error::<T>::LandTaxNotEnabled
+			);
+
+			match SelfAssessments::<T>::get(asset) {
+				Some(mut assessment) => {
+					ensure!(
+						!Self::is_land_tax_overdue(metaverse_id, &assessment),
+						Error::<T>::LandTaxOverdue
+					);
+					assessment.price = price;
+					SelfAssessments::<T>::insert(asset, assessment);
+				}
+				None => SelfAssessments::<T>::insert(asset, Self::new_self_assessment(metaverse_id, &who, price)),
+			}
+
+			Self::deposit_event(Event::<T>::LandSelfAssessed(asset, who, price));
+
+			Ok(().into())
+		}
+
+		/// Pay the land tax of the next period, on the terms in force when the previous period
+		/// was paid. Tax can be paid once the paid period has started, so at most one period
+		/// ahead.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5, 3))]
+		#[transactional]
+		pub fn pay_land_tax(origin: OriginFor<T>, asset: LandAsset) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let metaverse_id = Self::land_asset_metaverse(&asset)?;
+			let land_tax_config = LandTaxConfigs::<T>::get(metaverse_id).ok_or(Error::<T>::LandTaxNotEnabled)?;
+			let enabled_since = LandTaxEnabledSince::<T>::get(metaverse_id);
+
+			SelfAssessments::<T>::try_mutate(asset, |maybe_assessment| -> DispatchResultWithPostInfo {
+				let assessment = maybe_assessment.as_mut().ok_or(Error::<T>::SelfAssessmentNotFound)?;
+				ensure!(assessment.owner == who, Error::<T>::NoPermission);
+
+				// Tax starts over when the land tax is enabled again, on the new terms
+				if assessment.paid_until <= enabled_since {
+					assessment.paid_until = enabled_since;
+					assessment.tax_rate = land_tax_config.tax_rate;
+					assessment.tax_period = land_tax_config.tax_period;
+				}
+
+				let now = <frame_system::Pallet<T>>::block_number();
+				let paid_until = assessment.paid_until.saturating_add(assessment.tax_period);
+				ensure!(
+					paid_until <= now.saturating_add(assessment.tax_period),
+					Error::<T>::LandTaxAlreadyPaid
+				);
+
+				let tax = assessment.tax_rate * assessment.price;
+				Self::transfer_land_tax(&who, metaverse_id, tax)?;
+				assessment.paid_until = paid_until;
+				assessment.tax_rate = land_tax_config.tax_rate;
+				assessment.tax_period = land_tax_config.tax_period;
+
+				Self::deposit_event(Event::<T>::LandTaxPaid(asset, who.clone(), tax, paid_until));
+
+				Ok(().into())
+			})
+		}
+
+		/// Buy an estate or land unit at its self-assessed price. Land its owner never assessed
+		/// is not on sale. `max_price` protects the buyer from a price raised before the purchase
+		/// is included.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(6, 4))]
+		#[transactional]
+		pub fn force_buy_land(
+			origin: OriginFor<T>,
+			asset: LandAsset,
+			max_price: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let metaverse_id = Self::land_asset_metaverse(&asset)?;
+			ensure!(
+				LandTaxConfigs::<T>::contains_key(metaverse_id),
+				Error::<T>::LandTaxNotEnabled
+			);
+
+			let assessment = SelfAssessments::<T>::get(asset).ok_or(Error::<T>::SelfAssessmentNotFound)?;
+			ensure!(assessment.price <= max_price, Error::<T>::LandPriceAboveLimit);
+			Self::ensure_not_in_auction(&asset)?;
+			Self::ensure_not_leased(asset)?;
+			Self::ensure_not_collateralized(asset)?;
+
+			T::Currency::transfer(
+				&who,
+				&assessment.owner,
+				assessment.price,
+				ExistenceRequirement::KeepAlive,
+			)?;
+			Self::do_transfer_land_asset(&asset, &assessment.owner, &who)?;
+
+			Self::deposit_event(Event::<T>::LandForceBought(
+				asset,
+				assessment.owner,
+				who,
+				assessment.price,
+			));
+
+			Ok(().into())
+		}
+
+		/// Hand an estate or land unit whose land tax is overdue past the grace period over to
+		/// the metaverse fund. Anyone can reclaim it.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5, 3))]
+		#[transactional]
+		pub fn reclaim_land(origin: OriginFor<T>, asset: LandAsset) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			let metaverse_id = Self::land_asset_metaverse(&asset)?;
+			ensure!(
+				LandTaxConfigs::<T>::contains_key(metaverse_id),
+				Error::<T>::LandTaxNotEnabled
+			);
+
+			let assessment = SelfAssessments::<T>::get(asset).ok_or(Error::<T>::SelfAssessmentNotFound)?;
+			ensure!(
+				Self::is_land_tax_overdue(metaverse_id, &assessment),
+				Error::<T>::LandTaxNotOverdue
+			);
+			Self::ensure_not_in_auction(&asset)?;
+			Self::ensure_not_leased(asset)?;
+			Self::ensure_not_collateralized(asset)?;
+
+			let metaverse_fund_account = Self::metaverse_fund_account(metaverse_id);
+			Self::do_transfer_land_asset(&asset, &assessment.owner, &metaverse_fund_account)?;
+			// The metaverse fund keeps the land on sale at the declared price
+			SelfAssessments::<T>::insert(
+				asset,
+				Self::new_self_assessment(metaverse_id, &metaverse_fund_account, assessment.price),
+			);
+
+			Self::deposit_event(Event::<T>::LandReclaimed(
+				asset,
+				assessment.owner,
+				metaverse_fund_account,
+			));
+
+			Ok(().into())
+		}
+
+		/// Ask a lender for a loan against an estate or land unit. The asset is locked as
+		/// collateral once the lender funds the loan.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5, 1))]
+		pub fn request_loan(
+			origin: OriginFor<T>,
+			asset: LandAsset,
+			lender: T::AccountId,
+			currency_id: FungibleTokenId,
+			principal: BalanceOf<T>,
+			repayment: BalanceOf<T>,
+			duration: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Self::is_land_asset_owner(&who, &asset), Error::<T>::NoPermission);
+			ensure!(who != lender, Error::<T>::NoPermission);
+			ensure!(
+				!principal.is_zero() && principal <= repayment && !duration.is_zero(),
+				Error::<T>::InvalidLoanTerms
+			);
+			Self::ensure_not_in_auction(&asset)?;
+			Self::ensure_not_leased(asset)?;
+			Self::ensure_not_collateralized(asset)?;
+			ensure!(
+				!LoanRequests::<T>::contains_key(asset),
+				Error::<T>::LoanRequestAlreadyExists
+			);
+
+			LoanRequests::<T>::insert(
+				asset,
+				LoanRequest {
+					borrower: who.clone(),
+					lender: lender.clone(),
+					currency_id,
+					principal,
+					repayment,
+					duration,
+				},
+			);
+
+			Self::deposit_event(Event::<T>::LoanRequested(asset, who, lender, principal));
+
+			Ok(().into())
+		}
+
+		/// Withdraw a loan request. Either the borrower or the lender can cancel it.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn cancel_loan_request(origin: OriginFor<T>, asset: LandAsset) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let request = LoanRequests::<T>::get(asset).ok_or(Error::<T>::LoanRequestNotFound)?;
+			ensure!(
+				request.borrower == who || request.lender == who,
+				Error::<T>::NoPermission
+			);
+
+			LoanRequests::<T>::remove(asset);
+
+			Self::deposit_event(Event::<T>::LoanRequestCancelled(asset));
+
+			Ok(().into())
+		}
+
+		/// Fund a loan request. The principal goes to the borrower and the asset stays locked
+		/// until the loan is repaid or the collateral is claimed.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(6, 4))]
+		#[transactional]
+		pub fn fund_loan(origin: OriginFor<T>, asset: LandAsset) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let request = LoanRequests::<T>::get(asset).ok_or(Error::<T>::LoanRequestNotFound)?;
+			ensure!(request.lender == who, Error::<T>::NoPermission);
+
+			// The collateral might have changed hands or been encumbered since the request
+			ensure!(
+				Self::is_land_asset_owner(&request.borrower, &asset),
+				Error::<T>::NoPermission
+			);
+			Self::ensure_not_in_auction(&asset)?;
+			Self::ensure_not_leased(asset)?;
+			Self::ensure_not_collateralized(asset)?;
+
+			Self::transfer_fungible(request.currency_id, &who, &request.borrower, request.principal)?;
+
+			let due_block = <frame_system::Pallet<T>>::block_number().saturating_add(request.duration);
+			LoanRequests::<T>::remove(asset);
+			Loans::<T>::insert(
+				asset,
+				LoanInfo {
+					borrower: request.borrower.clone(),
+					lender: who.clone(),
+					currency_id: request.currency_id,
+					repayment: request.repayment,
+					due_block,
+				},
+			);
+
+			Self::deposit_event(Event::<T>::LoanFunded(asset, request.borrower, who, due_block));
+
+			Ok(().into())
+		}
+
+		/// Repay a loan and unlock its collateral. A loan can be repaid after its due block
+		/// as long as the lender has not claimed the collateral.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 3))]
+		#[transactional]
+		pub fn repay_loan(origin: OriginFor<T>, asset: LandAsset) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let loan = Loans::<T>::get(asset).ok_or(Error::<T>::LoanNotFound)?;
+			ensure!(loan.borrower == who, Error::<T>::NoPermission);
+
+			Self::transfer_fungible(loan.currency_id, &who, &loan.lender, loan.repayment)?;
+			Loans::<T>::remove(asset);
+
+			Self::deposit_event(Event::<T>::LoanRepaid(asset, who, loan.lender, loan.repayment));
+
+			Ok(().into())
+		}
+
+		/// Take over the collateral of a loan which was not repaid by its due block
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 4))]
+		#[transactional]
+		pub fn claim_collateral(origin: OriginFor<T>, asset: LandAsset) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let loan = Loans::<T>::get(asset).ok_or(Error::<T>::LoanNotFound)?;
+			ensure!(loan.lender == who, Error::<T>::NoPermission);
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() > loan.due_block,
+				Error::<T>::LoanNotDue
+			);
+
+			Loans::<T>::remove(asset);
+			Self::do_transfer_land_asset(&asset, &loan.borrower, &who)?;
+
+			Self::deposit_event(Event::<T>::CollateralClaimed(asset, loan.borrower, who));
+
+			Ok(().into())
+		}
+
+		/// Open a primary sale of land units within the metaverse bounds. Only the metaverse
+		/// owner can open it and proceeds go to the metaverse fund.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 1))]
+		pub fn open_land_sale(
+			origin: OriginFor<T>,
+			metaverse_id: MetaverseId,
+			sale: LandSale<BalanceOf<T>, T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				T::MetaverseInfoSource::check_ownership(&who, &metaverse_id),
+				Error::<T>::NoPermission
+			);
+			ensure!(
+				!LandSales::<T>::contains_key(metaverse_id),
+				Error::<T>::LandSaleAlreadyOpen
+			);
+			Self::ensure_valid_land_sale(metaverse_id, &sale)?;
+
+			LandSales::<T>::insert(metaverse_id, sale);
+
+			Self::deposit_event(Event::<T>::LandSaleOpened(metaverse_id));
+
+			Ok(().into())
+		}
+
+		/// Close the primary land sale of a metaverse, clearing its whitelist and purchases
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 3))]
+		pub fn close_land_sale(origin: OriginFor<T>, metaverse_id: MetaverseId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				T::MetaverseInfoSource::check_ownership(&who, &metaverse_id),
+				Error::<T>::NoPermission
+			);
+			ensure!(LandSales::<T>::contains_key(metaverse_id), Error::<T>::LandSaleNotFound);
+
+			LandSales::<T>::remove(metaverse_id);
+			let _ = LandSaleWhitelist::<T>::remove_prefix(metaverse_id, None);
+			let _ = LandSalePurchases::<T>::remove_prefix(metaverse_id, None);
+
+			Self::deposit_event(Event::<T>::LandSaleClosed(metaverse_id));
+
+			Ok(().into())
+		}
+
+		/// Add accounts to or remove them from the whitelist of the land sale
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, accounts.len() as u64))]
+		pub fn update_land_sale_whitelist(
+			origin: OriginFor<T>,
+			metaverse_id: MetaverseId,
+			accounts: Vec<T::AccountId>,
+			whitelisted: bool,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				T::MetaverseInfoSource::check_ownership(&who, &metaverse_id),
+				Error::<T>::NoPermission
+			);
+			ensure!(LandSales::<T>::contains_key(metaverse_id), Error::<T>::LandSaleNotFound);
+
+			for account in accounts.iter() {
+				if whitelisted {
+					LandSaleWhitelist::<T>::insert(metaverse_id, account, ());
+				} else {
+					LandSaleWhitelist::<T>::remove(metaverse_id, account);
+				}
+			}
+
+			Self::deposit_event(Event::<T>::LandSaleWhitelistUpdated(
+				metaverse_id,
+				accounts,
+				whitelisted,
+			));
+
+			Ok(().into())
+		}
+
+		/// Buy land units in the primary land sale of a metaverse
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 2) * (coordinates.len() as u64 + 1))]
+		#[transactional]
+		pub fn buy_land(
+			origin: OriginFor<T>,
+			metaverse_id: MetaverseId,
+			coordinates: Vec<(i32, i32)>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!coordinates.is_empty(), Error::<T>::LandUnitNotForSale);
+
+			let sale = LandSales::<T>::get(metaverse_id).ok_or(Error::<T>::LandSaleNotFound)?;
+
+			if let Some(whitelist_until) = sale.whitelist_until {
+				ensure!(
+					<frame_system::Pallet<T>>::block_number() > whitelist_until
+						|| LandSaleWhitelist::<T>::contains_key(metaverse_id, &who),
+					Error::<T>::NotWhitelistedForLandSale
+				);
+			}
+
+			let purchased = LandSalePurchases::<T>::get(metaverse_id, &who)
+				.checked_add(coordinates.len() as u32)
+				.ok_or(Error::<T>::Overflow)?;
+			if let Some(max_units_per_account) = sale.max_units_per_account {
+				ensure!(
+					purchased <= max_units_per_account,
+					Error::<T>::LandSalePurchaseLimitExceeded
+				);
+			}
+
+			let mut total_price: BalanceOf<T> = Zero::zero();
+			for coordinate in coordinates.iter() {
+				ensure!(sale.contains(*coordinate), Error::<T>::LandUnitNotForSale);
+				total_price = total_price.saturating_add(sale.price_of(*coordinate));
+				Self::mint_land_unit(metaverse_id, &who, *coordinate, false)?;
+			}
+			Self::set_total_land_unit(coordinates.len() as u64, false)?;
+
+			T::Currency::transfer(
+				&who,
+				&Self::metaverse_fund_account(metaverse_id),
+				total_price,
+				ExistenceRequirement::KeepAlive,
+			)?;
+			LandSalePurchases::<T>::insert(metaverse_id, &who, purchased);
+
+			Self::deposit_event(Event::<T>::LandBought(who, metaverse_id, coordinates, total_price));
+
+			Ok(().into())
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			let mut weight = T::DbWeight::get().reads(2);
+			let mut round = <Round<T>>::get();
+
+			// A round lasts until the snapshot of its stake is complete
+			if round.should_update(n) && <StakeSnapshotProgress<T>>::get().is_none() {
+				round.update(n);
+				<Round<T>>::put(round);
+
+				<StakeSnapshotProgress<T>>::put(SnapshotProgress {
+					round: round.current,
+					last_stake: None,
+					exits_cleared: false,
+				});
+
+				Self::prepare_staking_payouts(round.current);
+				let round_issuance_per_round = round_issuance_range::<T>(<MintingRateConfig<T>>::get());
+				Self::deposit_event(Event::NewRound(n, round.current, round_issuance_per_round.max));
+
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(7, 5));
+			}
+
+			weight = weight.saturating_add(Self::update_stake_snapshot());
+
+			weight.saturating_add(Self::handle_delayed_payouts())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			Self::upgrade_estate_index_v1().saturating_add(Self::upgrade_self_assessments_v2())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Issue rewards for the round whose payment delay has just elapsed. Stakers are paid
+		/// lazily by `handle_delayed_payouts` in the following blocks
+		fn prepare_staking_payouts(now: RoundIndex) {
+			// payout is now - duration rounds ago => now - duration > 0 else return early
+			let duration = T::RewardPaymentDelay::get();
+			if now <= duration {
+				return;
+			}
+			let round_to_payout = now - duration;
+
+			let total_staked = <Staked<T>>::take(round_to_payout);
+			let round_issuance = Self::compute_issuance(total_staked);
+
+			<DelayedPayouts<T>>::insert(
+				round_to_payout,
+				DelayedPayout {
+					round_issuance,
+					total_staked,
+				},
+			);
+
+			if <NextPayoutRound<T>>::get().is_none() {
+				<NextPayoutRound<T>>::put(round_to_payout);
+			}
+
+			Self::deposit_event(Event::StakingRewardIssued(
+				round_to_payout,
+				round_issuance,
+				total_staked,
+			));
+		}
+
+		/// Pay out at most `MaxEstatePayoutsPerBlock` estate snapshots of the oldest unpaid round
+		fn handle_delayed_payouts() -> Weight {
+			let db_weight = T::DbWeight::get();
+			let round_to_payout = match <NextPayoutRound<T>>::get() {
+				Some(round) => round,
+				None => return db_weight.reads(1),
+			};
+
+			let payout = match <DelayedPayouts<T>>::get(round_to_payout) {
+				Some(payout) => payout,
+				None => {
+					<NextPayoutRound<T>>::kill();
+					return db_weight.reads_writes(2, 1);
+				}
+			};
+
+			let mut weight = db_weight.reads(2);
+			let mut estates_paid = 0u32;
+			let mut snapshots = <AtStake<T>>::drain_prefix(round_to_payout);
+
+			while estates_paid < T::MaxEstatePayoutsPerBlock::get() {
+				match snapshots.next() {
+					Some((_estate_id, stake_snapshot)) => {
+						weight = weight.saturating_add(Self::pay_estate_stakers(&payout, stake_snapshot));
+						estates_paid += 1;
+					}
+					None => {
+						// All stakers of the round are paid, move on to the next round
+						<DelayedPayouts<T>>::remove(round_to_payout);
+						let next_round = round_to_payout.saturating_add(1);
+						if <DelayedPayouts<T>>::contains_key(next_round) {
+							<NextPayoutRound<T>>::put(next_round);
+						} else {
+							<NextPayoutRound<T>>::kill();
+						}
+						Self::deposit_event(Event::StakersPaid(round_to_payout));
+
+						return weight.saturating_add(db_weight.reads_writes(2, 2));
+					}
+				}
+			}
+
+			weight
+		}
+
+		/// Mint each staker of the snapshot its share of the round rewards
+		fn pay_estate_stakers(
+			payout: &DelayedPayout<BalanceOf<T>>,
+			stake_snapshot: StakeSnapshot<T::AccountId, BalanceOf<T>>,
+		) -> Weight {
+			let stakers_count = stake_snapshot.stakers.len() as u64;
+
+			for Bond { staker, amount } in stake_snapshot.stakers {
+				let amount_due = Perbill::from_rational(amount, payout.total_staked) * payout.round_issuance;
+				if amount_due.is_zero() {
+					continue;
+				}
+
+				if let Ok(imbalance) = T::Currency::deposit_into_existing(&staker, amount_due) {
+					Self::deposit_event(Event::StakingRewarded(staker, imbalance.peek()));
+				}
+			}
+
+			T::DbWeight::get().reads_writes(stakers_count.saturating_add(1), stakers_count.saturating_add(1))
+		}
+
+		/// Snapshot at most `MaxEstatePayoutsPerBlock` estate stakes for the current round,
+		/// resuming after the last stake visited. Stakers who left get their bond back instead.
+		fn update_stake_snapshot() -> Weight {
+			let db_weight = T::DbWeight::get();
+			let mut progress = match <StakeSnapshotProgress<T>>::get() {
+				Some(progress) => progress,
+				None => return 0,
+			};
+
+			let max_stakes = T::MaxEstatePayoutsPerBlock::get() as usize;
+			// Read one past the cap to know whether the snapshot is complete
+			let stakes: Vec<(EstateId, T::AccountId, BalanceOf<T>)> = match progress.last_stake {
+				Some((ref estate_id, ref account_id)) => {
+					<EstateStake<T>>::iter_from(<EstateStake<T>>::hashed_key_for(estate_id, account_id))
+						.take(max_stakes + 1)
+						.collect()
+				}
+				None => <EstateStake<T>>::iter().take(max_stakes + 1).collect(),
+			};
+			let snapshot_complete = stakes.len() <= max_stakes;
+
+			let mut round_stake = BalanceOf::<T>::zero();
+			let mut visited = 0u64;
+			for (estate_id, account_id, amount) in stakes.into_iter().take(max_stakes) {
+				visited += 1;
+				if <ExitQueue<T>>::contains_key(&account_id, estate_id) {
+					Self::return_exiting_stake(&account_id, estate_id);
+					progress.exits_cleared = true;
+				} else {
+					<AtStake<T>>::mutate(progress.round, estate_id, |snapshot| {
+						let snapshot = snapshot.get_or_insert_with(|| StakeSnapshot {
+							stakers: Vec::new(),
+							total_bond: BalanceOf::<T>::zero(),
+						});
+						snapshot.stakers.push(Bond {
+							staker: account_id.clone(),
+							amount,
+						});
+						snapshot.total_bond = snapshot.total_bond.saturating_add(amount);
+					});
+					round_stake = round_stake.saturating_add(amount);
+				}
+				progress.last_stake = Some((estate_id, account_id));
+			}
+
+			let total_staked = <Staked<T>>::mutate(progress.round, |staked| {
+				*staked = staked.saturating_add(round_stake);
+				*staked
+			});
+
+			if snapshot_complete {
+				<StakeSnapshotProgress<T>>::kill();
+				if progress.exits_cleared {
+					Self::deposit_event(Event::ExitQueueCleared(progress.round));
+				}
+				Self::deposit_event(Event::StakeSnapshotUpdated(progress.round, total_staked));
+			} else {
+				<StakeSnapshotProgress<T>>::put(progress);
+			}
+
+			db_weight
+				.reads_writes(3, 2)
+				.saturating_add(db_weight.reads_writes(visited.saturating_mul(3), visited.saturating_mul(3)))
+		}
+
+		/// Return the bond of a staker who left the estate
+		pub(crate) fn return_exiting_stake(staker: &T::AccountId, estate_id: EstateId) {
+			let amount = <EstateStake<T>>::take(estate_id, staker);
+			<ExitQueue<T>>::remove(staker, estate_id);
+			T::Currency::unreserve(staker, amount);
+			<TotalStake<T>>::mutate(|total| *total = total.saturating_sub(amount));
+		}
+
+		/// Round rewards in the native token, the stake backing the round times the round minting
+		/// rate. The round issuance range counts land units and does not size rewards.
+		fn compute_issuance(staked: BalanceOf<T>) -> BalanceOf<T> {
+			round_minting_rate::<T>(<MintingRateConfig<T>>::get()) * staked
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn get_new_estate_id() -> Result<EstateId, DispatchError> {
+		let estate_id = NextEstateId::<T>::try_mutate(|id| -> Result<EstateId, DispatchError> {
+			let current_id = *id;
+			*id = id.checked_add(One::one()).ok_or(Error::<T>::NoAvailableEstateId)?;
+			Ok(current_id)
+		})?;
+		Ok(estate_id)
+	}
+
+	fn mint_land_unit(
 		metaverse_id: MetaverseId,
 		beneficiary: &T::AccountId,
 		coordinate: (i32, i32),
@@ -1132,8 +2628,12 @@ impl<T: Config> Pallet<T> {
 		};
 
 		Estates::<T>::insert(new_estate_id, estate_info);
+		for coordinate in coordinates.iter() {
+			LandUnitEstate::<T>::insert(metaverse_id, coordinate, new_estate_id);
+		}
 
 		EstateOwner::<T>::insert(beneficiary.clone(), new_estate_id, {});
+		OwnerOfEstate::<T>::insert(new_estate_id, beneficiary.clone());
 
 		Self::deposit_event(Event::<T>::NewEstateMinted(
 			new_estate_id.clone(),
@@ -1182,6 +2682,8 @@ impl<T: Config> Pallet<T> {
 					Error::<T>::UndeployedLandBlockIsNotTransferable
 				);
 
+				Self::ensure_undeployed_land_block_not_in_auction(undeployed_land_block_id)?;
+
 				undeployed_land_block_record.owner = to.clone();
 
 				UndeployedLandBlocksOwner::<T>::remove(who.clone(), &undeployed_land_block_id);
@@ -1208,6 +2710,7 @@ impl<T: Config> Pallet<T> {
 			undeployed_land_block_info.is_frozen,
 			Error::<T>::OnlyFrozenUndeployedLandBlockCanBeDestroyed
 		);
+		Self::ensure_undeployed_land_block_not_in_auction(undeployed_land_block_id)?;
 		Self::set_total_undeployed_land_unit(undeployed_land_block_info.number_land_units as u64, true)?;
 		UndeployedLandBlocksOwner::<T>::remove(undeployed_land_block_info.owner, &undeployed_land_block_id);
 		UndeployedLandBlocks::<T>::remove(&undeployed_land_block_id);
@@ -1241,6 +2744,30 @@ impl<T: Config> Pallet<T> {
 		)
 	}
 
+	fn do_unfreeze_undeployed_land_block(
+		undeployed_land_block_id: UndeployedLandBlockId,
+	) -> Result<UndeployedLandBlockId, DispatchError> {
+		UndeployedLandBlocks::<T>::try_mutate_exists(
+			&undeployed_land_block_id,
+			|undeployed_land_block| -> Result<UndeployedLandBlockId, DispatchError> {
+				let mut undeployed_land_block_record = undeployed_land_block
+					.as_mut()
+					.ok_or(Error::<T>::UndeployedLandBlockNotFound)?;
+
+				ensure!(
+					undeployed_land_block_record.is_frozen == true,
+					Error::<T>::UndeployedLandBlockNotFrozen
+				);
+
+				undeployed_land_block_record.is_frozen = false;
+
+				Self::deposit_event(Event::<T>::UndeployedLandBlockUnfreezed(undeployed_land_block_id));
+
+				Ok(undeployed_land_block_id)
+			},
+		)
+	}
+
 	fn do_issue_undeployed_land_blocks(
 		beneficiary: &T::AccountId,
 		number_of_land_block: u32,
@@ -1279,6 +2806,57 @@ impl<T: Config> Pallet<T> {
 		Ok(undeployed_land_block_ids)
 	}
 
+	/// Index the land units and owners of existing estates by estate
+	pub fn upgrade_estate_index_v1() -> Weight {
+		if StorageVersion::get::<Pallet<T>>() >= 1 {
+			return 0;
+		}
+
+		let mut reads: u64 = 0;
+		let mut writes: u64 = 1;
+		for (owner, estate_id, _) in EstateOwner::<T>::iter() {
+			OwnerOfEstate::<T>::insert(estate_id, owner);
+			reads += 1;
+			writes += 1;
+		}
+		for (estate_id, estate_info) in Estates::<T>::iter() {
+			reads += 1;
+			for land_unit in estate_info.land_units {
+				LandUnitEstate::<T>::insert(estate_info.metaverse_id, land_unit, estate_id);
+				writes += 1;
+			}
+		}
+
+		StorageVersion::new(1).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+
+	/// Keep the land tax terms with each self-assessment. Only land whose owner declared a price
+	/// has one, land without stays unassessed until its owner declares a price.
+	pub fn upgrade_self_assessments_v2() -> Weight {
+		if StorageVersion::get::<Pallet<T>>() >= 2 {
+			return 0;
+		}
+
+		let mut reads: u64 = 0;
+		let mut writes: u64 = 1;
+		SelfAssessments::<T>::translate(
+			|asset, assessment_v1: migration_v2::SelfAssessment<T::AccountId, BalanceOf<T>, T::BlockNumber>| {
+				reads += 2;
+				writes += 1;
+				let metaverse_id = Self::land_asset_metaverse(&asset).ok()?;
+				let mut assessment = Self::new_self_assessment(metaverse_id, &assessment_v1.owner, assessment_v1.price);
+				assessment.paid_until = assessment_v1.paid_until;
+				Some(assessment)
+			},
+		);
+
+		StorageVersion::new(2).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+
 	fn do_transfer_estate(
 		estate_id: EstateId,
 		from: &T::AccountId,
@@ -1295,6 +2873,8 @@ impl<T: Config> Pallet<T> {
 
 				*estate_by_owner = None;
 				EstateOwner::<T>::insert(to.clone(), estate_id.clone(), ());
+				OwnerOfEstate::<T>::insert(estate_id, to.clone());
+				Self::update_self_assessment_owner(LandAsset::Estate(estate_id), to);
 
 				Self::deposit_event(Event::<T>::TransferredEstate(
 					estate_id.clone(),
@@ -1327,6 +2907,7 @@ impl<T: Config> Pallet<T> {
 				ensure!(from != to, Error::<T>::AlreadyOwnTheLandUnit);
 
 				*land_unit_owner = Some(to.clone());
+				Self::update_self_assessment_owner(LandAsset::LandUnit(coordinate, metaverse_id), to);
 
 				// Update
 				Self::deposit_event(Event::<T>::TransferredLandUnit(
@@ -1341,6 +2922,390 @@ impl<T: Config> Pallet<T> {
 		)
 	}
 
+	/// Check the land units follow the estate shape rule of the metaverse
+	fn ensure_valid_estate_shape(metaverse_id: MetaverseId, land_units: &[(i32, i32)]) -> DispatchResult {
+		let is_valid = match Self::get_estate_shape_rule(metaverse_id) {
+			EstateShapeRule::Unrestricted => true,
+			EstateShapeRule::FourConnected => Self::is_connected(land_units, false),
+			EstateShapeRule::EightConnected => Self::is_connected(land_units, true),
+			EstateShapeRule::Rectangular => Self::is_rectangular(land_units),
+		};
+		ensure!(is_valid, Error::<T>::InvalidEstateShape);
+		Ok(())
+	}
+
+	fn is_connected(land_units: &[(i32, i32)], include_diagonals: bool) -> bool {
+		let mut remaining: BTreeSet<(i32, i32)> = land_units.iter().cloned().collect();
+		let start = match land_units.first() {
+			Some(land_unit) => *land_unit,
+			None => return true,
+		};
+
+		// Flood fill from the first land unit, every land unit must be reached
+		remaining.remove(&start);
+		let mut to_visit: Vec<(i32, i32)> = Vec::new();
+		to_visit.push(start);
+		while let Some((x, y)) = to_visit.pop() {
+			for dx in -1i32..=1 {
+				for dy in -1i32..=1 {
+					let is_edge_neighbour = (dx == 0) != (dy == 0);
+					let is_corner_neighbour = dx != 0 && dy != 0;
+					if !is_edge_neighbour && !(include_diagonals && is_corner_neighbour) {
+						continue;
+					}
+
+					let neighbour = (x.saturating_add(dx), y.saturating_add(dy));
+					if remaining.remove(&neighbour) {
+						to_visit.push(neighbour);
+					}
+				}
+			}
+		}
+
+		remaining.is_empty()
+	}
+
+	fn is_rectangular(land_units: &[(i32, i32)]) -> bool {
+		let unique_land_units: BTreeSet<(i32, i32)> = land_units.iter().cloned().collect();
+		if unique_land_units.is_empty() {
+			return true;
+		}
+
+		let min_x = unique_land_units.iter().map(|(x, _)| *x).min().unwrap_or_default() as i64;
+		let max_x = unique_land_units.iter().map(|(x, _)| *x).max().unwrap_or_default() as i64;
+		let min_y = unique_land_units.iter().map(|(_, y)| *y).min().unwrap_or_default() as i64;
+		let max_y = unique_land_units.iter().map(|(_, y)| *y).max().unwrap_or_default() as i64;
+
+		// Distinct land units fill their bounding box only when there are as many as its area
+		let area = (max_x - min_x + 1).saturating_mul(max_y - min_y + 1);
+		unique_land_units.len() as i64 == area
+	}
+
+	fn ensure_estate_can_be_reshaped(who: &T::AccountId, estate_id: EstateId) -> DispatchResult {
+		ensure!(
+			Self::get_estate_owner(who, &estate_id) == Some(()),
+			Error::<T>::NoPermission
+		);
+		ensure!(
+			!T::AuctionHandler::check_item_in_auction(ItemId::Estate(estate_id)),
+			Error::<T>::EstateAlreadyInAuction
+		);
+		Self::ensure_not_leased(LandAsset::Estate(estate_id))?;
+		Self::ensure_not_collateralized(LandAsset::Estate(estate_id))
+	}
+
+	/// Frozen land blocks, land blocks listed in an auction and land blocks with an approved
+	/// account keep their land unit count.
+	fn ensure_undeployed_land_block_can_be_reshaped(
+		who: &T::AccountId,
+		undeployed_land_block: &UndeployedLandBlock<T::AccountId>,
+	) -> DispatchResult {
+		ensure!(undeployed_land_block.owner == *who, Error::<T>::NoPermission);
+		ensure!(!undeployed_land_block.is_frozen, Error::<T>::UndeployedLandBlockFreezed);
+		ensure!(
+			undeployed_land_block.approved.is_none(),
+			Error::<T>::UndeployedLandBlockIsApproved
+		);
+		Self::ensure_undeployed_land_block_not_in_auction(undeployed_land_block.id)
+	}
+
+	/// Listed land blocks are locked by the auction until it settles or is cancelled
+	fn ensure_undeployed_land_block_not_in_auction(undeployed_land_block_id: UndeployedLandBlockId) -> DispatchResult {
+		ensure!(
+			!T::AuctionHandler::check_item_in_auction(ItemId::Block(undeployed_land_block_id)),
+			Error::<T>::UndeployedLandBlockAlreadyInAuction
+		);
+		Ok(())
+	}
+
+	/// Move all stake bonded to one estate over to another. Stakers who left either estate get
+	/// their bond back instead of waiting for the next round.
+	fn move_estate_stake(from_estate_id: EstateId, to_estate_id: EstateId) {
+		for (staker, amount) in EstateStake::<T>::iter_prefix(from_estate_id).collect::<Vec<_>>() {
+			if Self::exit_queue(&staker, from_estate_id).is_some() {
+				Self::return_exiting_stake(&staker, from_estate_id);
+				continue;
+			}
+			if Self::exit_queue(&staker, to_estate_id).is_some() {
+				Self::return_exiting_stake(&staker, to_estate_id);
+			}
+
+			EstateStake::<T>::remove(from_estate_id, &staker);
+			EstateStake::<T>::mutate(to_estate_id, &staker, |staked| *staked = staked.saturating_add(amount));
+		}
+	}
+
+	fn is_land_asset_owner(who: &T::AccountId, asset: &LandAsset) -> bool {
+		match *asset {
+			LandAsset::Estate(estate_id) => EstateOwner::<T>::contains_key(who, estate_id),
+			LandAsset::LandUnit(coordinate, metaverse_id) => {
+				LandUnits::<T>::get(metaverse_id, coordinate).as_ref() == Some(who)
+			}
+		}
+	}
+
+	fn ensure_not_in_auction(asset: &LandAsset) -> DispatchResult {
+		match *asset {
+			LandAsset::Estate(estate_id) => ensure!(
+				!T::AuctionHandler::check_item_in_auction(ItemId::Estate(estate_id)),
+				Error::<T>::EstateAlreadyInAuction
+			),
+			LandAsset::LandUnit(coordinate, metaverse_id) => ensure!(
+				!T::AuctionHandler::check_item_in_auction(ItemId::LandUnit(coordinate, metaverse_id)),
+				Error::<T>::LandUnitAlreadyInAuction
+			),
+		}
+		Ok(())
+	}
+
+	fn ensure_not_leased(asset: LandAsset) -> DispatchResult {
+		if Leases::<T>::contains_key(asset) {
+			return match asset {
+				LandAsset::Estate(_) => Err(Error::<T>::EstateIsLeased.into()),
+				LandAsset::LandUnit(_, _) => Err(Error::<T>::LandUnitIsLeased.into()),
+			};
+		}
+		Ok(())
+	}
+
+	/// The area on sale and its zones must lie within the metaverse bounds and carry a price
+	fn ensure_valid_land_sale(
+		metaverse_id: MetaverseId,
+		sale: &LandSale<BalanceOf<T>, T::BlockNumber>,
+	) -> DispatchResult {
+		ensure!(MaxBounds::<T>::contains_key(metaverse_id), Error::<T>::NoMaxBoundSet);
+		let max_bound = MaxBounds::<T>::get(metaverse_id);
+
+		ensure!(
+			sale.zones.len() as u32 <= T::MaxLandSaleZones::get(),
+			Error::<T>::InvalidLandSale
+		);
+		ensure!(
+			sale.bottom_left.0 <= sale.top_right.0
+				&& sale.bottom_left.1 <= sale.top_right.1
+				&& sale.bottom_left.0 >= max_bound.0
+				&& sale.bottom_left.1 >= max_bound.0
+				&& sale.top_right.0 <= max_bound.1
+				&& sale.top_right.1 <= max_bound.1
+				&& !sale.unit_price.is_zero(),
+			Error::<T>::InvalidLandSale
+		);
+		for zone in sale.zones.iter() {
+			ensure!(
+				zone.bottom_left.0 <= zone.top_right.0
+					&& zone.bottom_left.1 <= zone.top_right.1
+					&& sale.contains(zone.bottom_left)
+					&& sale.contains(zone.top_right)
+					&& !zone.unit_price.is_zero(),
+				Error::<T>::InvalidLandSale
+			);
+		}
+		Ok(())
+	}
+
+	fn ensure_not_collateralized(asset: LandAsset) -> DispatchResult {
+		if Loans::<T>::contains_key(asset) {
+			return match asset {
+				LandAsset::Estate(_) => Err(Error::<T>::EstateIsCollateralized.into()),
+				LandAsset::LandUnit(_, _) => Err(Error::<T>::LandUnitIsCollateralized.into()),
+			};
+		}
+		Ok(())
+	}
+
+	fn is_rent_overdue(lease: &LeaseInfo<T::AccountId, BalanceOf<T>, T::BlockNumber>) -> bool {
+		let now = <frame_system::Pallet<T>>::block_number();
+		lease.paid_until < lease.end_block && now > lease.paid_until.saturating_add(T::LeaseGracePeriod::get())
+	}
+
+	fn transfer_rent(lease: &LeaseInfo<T::AccountId, BalanceOf<T>, T::BlockNumber>) -> DispatchResult {
+		Self::transfer_fungible(lease.currency_id, &lease.tenant, &lease.landlord, lease.rent)
+	}
+
+	/// Transfer native tokens through `Currency` and social tokens through
+	/// `FungibleTokenCurrency`
+	fn transfer_fungible(
+		currency_id: FungibleTokenId,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		if currency_id == FungibleTokenId::NativeToken(0) {
+			T::Currency::transfer(from, to, amount, ExistenceRequirement::KeepAlive)
+		} else {
+			T::FungibleTokenCurrency::transfer(currency_id, from, to, amount)
+		}
+	}
+
+	/// Whether the account can update the content of the asset, either through its usage
+	/// rights, a builder grant which is still in effect or the builder role of the metaverse
+	/// when the metaverse owner holds the asset
+	pub fn can_build(who: &T::AccountId, asset: &LandAsset) -> bool {
+		if Self::has_usage_rights(who, asset) || Self::is_metaverse_builder(who, asset) {
+			return true;
+		}
+
+		match BuilderGrants::<T>::get(asset, who) {
+			Some(grant) => {
+				let now = <frame_system::Pallet<T>>::block_number();
+				grant.expires_at.map_or(true, |expiry| now <= expiry)
+					&& Self::has_usage_rights(&grant.granted_by, asset)
+			}
+			None => false,
+		}
+	}
+
+	/// Builders of a metaverse can build on the land its owner holds
+	fn is_metaverse_builder(who: &T::AccountId, asset: &LandAsset) -> bool {
+		let metaverse_id = match Self::land_asset_metaverse(asset) {
+			Ok(metaverse_id) => metaverse_id,
+			Err(_) => return false,
+		};
+
+		T::MetaverseInfoSource::check_role(who, &metaverse_id, &MetaverseRole::Builder)
+			&& T::MetaverseInfoSource::get_metaverse(metaverse_id)
+				.map_or(false, |metaverse| Self::has_usage_rights(&metaverse.owner, asset))
+	}
+
+	/// Remove the content and builder grants of an estate which no longer exists. Its
+	/// self-assessment is handed on to the land it is reshaped into.
+	fn clear_estate_content(estate_id: EstateId) {
+		let asset = LandAsset::Estate(estate_id);
+		ParcelContents::<T>::remove(asset);
+		let _ = BuilderGrants::<T>::remove_prefix(asset, None);
+	}
+
+	/// Whether the account can build on and use the asset. The tenant holds these rights
+	/// until the lease term ends, the owner otherwise, even before the lease is terminated.
+	pub fn has_usage_rights(who: &T::AccountId, asset: &LandAsset) -> bool {
+		match Leases::<T>::get(asset) {
+			Some(lease) if <frame_system::Pallet<T>>::block_number() < lease.end_block => lease.tenant == *who,
+			_ => Self::is_land_asset_owner(who, asset),
+		}
+	}
+
+	/// The self-assessed price and tax duty stay with the asset when it changes hands
+	fn update_self_assessment_owner(asset: LandAsset, new_owner: &T::AccountId) {
+		SelfAssessments::<T>::mutate(asset, |maybe_assessment| {
+			if let Some(assessment) = maybe_assessment {
+				assessment.owner = new_owner.clone();
+			}
+		});
+	}
+
+	fn land_asset_metaverse(asset: &LandAsset) -> Result<MetaverseId, DispatchError> {
+		match *asset {
+			LandAsset::Estate(estate_id) => Estates::<T>::get(estate_id)
+				.map(|estate_info| estate_info.metaverse_id)
+				.ok_or(Error::<T>::EstateDoesNotExist.into()),
+			LandAsset::LandUnit(_, metaverse_id) => Ok(metaverse_id),
+		}
+	}
+
+	fn do_transfer_land_asset(asset: &LandAsset, from: &T::AccountId, to: &T::AccountId) -> DispatchResult {
+		match *asset {
+			LandAsset::Estate(estate_id) => Self::do_transfer_estate(estate_id, from, to)?,
+			LandAsset::LandUnit(coordinate, metaverse_id) => {
+				Self::do_transfer_landunit(coordinate, from, to, metaverse_id)?;
+			}
+		};
+		Ok(())
+	}
+
+	/// Account of the metaverse fund, which collects the land tax of the metaverse
+	pub fn metaverse_fund_account(metaverse_id: MetaverseId) -> T::AccountId {
+		T::MetaverseFundTreasury::get().into_sub_account(metaverse_id)
+	}
+
+	fn transfer_land_tax(who: &T::AccountId, metaverse_id: MetaverseId, tax: BalanceOf<T>) -> DispatchResult {
+		T::Currency::transfer(
+			who,
+			&Self::metaverse_fund_account(metaverse_id),
+			tax,
+			ExistenceRequirement::KeepAlive,
+		)
+	}
+
+	/// Time the land tax of the metaverse was disabled does not count towards being overdue
+	fn is_land_tax_overdue(
+		metaverse_id: MetaverseId,
+		assessment: &SelfAssessment<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+	) -> bool {
+		let paid_until = assessment.paid_until.max(LandTaxEnabledSince::<T>::get(metaverse_id));
+		<frame_system::Pallet<T>>::block_number() > paid_until.saturating_add(T::LandTaxGracePeriod::get())
+	}
+
+	/// Assessment of land which just got a price. Tax is due from now on, on the land tax terms
+	/// the metaverse has in force.
+	fn new_self_assessment(
+		metaverse_id: MetaverseId,
+		owner: &T::AccountId,
+		price: BalanceOf<T>,
+	) -> SelfAssessment<T::AccountId, BalanceOf<T>, T::BlockNumber> {
+		let (tax_rate, tax_period) = LandTaxConfigs::<T>::get(metaverse_id).map_or_else(
+			|| (Perbill::zero(), Zero::zero()),
+			|land_tax_config| (land_tax_config.tax_rate, land_tax_config.tax_period),
+		);
+
+		SelfAssessment {
+			owner: owner.clone(),
+			price,
+			paid_until: <frame_system::Pallet<T>>::block_number(),
+			tax_rate,
+			tax_period,
+		}
+	}
+
+	/// Fold the self-assessment of land joining an estate into the estate's. The estate is
+	/// assessed at the sum of both prices and has tax paid as far as the least paid of the two.
+	/// An estate is only assessed while all of its land is, so its price never leaves land out.
+	/// `first` land starts the assessment of a new estate.
+	fn merge_self_assessment(estate_id: EstateId, land: LandAsset, owner: &T::AccountId, first: bool) {
+		let land_assessment = SelfAssessments::<T>::take(land);
+
+		SelfAssessments::<T>::mutate(LandAsset::Estate(estate_id), |maybe_assessment| {
+			*maybe_assessment = match (maybe_assessment.take(), land_assessment) {
+				(_, Some(land_assessment)) if first => Some(SelfAssessment {
+					owner: owner.clone(),
+					..land_assessment
+				}),
+				(Some(mut assessment), Some(land_assessment)) => {
+					assessment.price = assessment.price.saturating_add(land_assessment.price);
+					assessment.paid_until = assessment.paid_until.min(land_assessment.paid_until);
+					Some(assessment)
+				}
+				_ => None,
+			}
+		});
+	}
+
+	/// Hand land split off an estate the share of the estate's self-assessed price that comes
+	/// with `land_unit_count` of its `estate_land_unit_count` land units. The tax paid carries
+	/// over as is.
+	fn split_self_assessment(
+		estate_id: EstateId,
+		land: LandAsset,
+		land_unit_count: u32,
+		estate_land_unit_count: u32,
+		owner: &T::AccountId,
+	) {
+		SelfAssessments::<T>::mutate(LandAsset::Estate(estate_id), |maybe_assessment| {
+			if let Some(assessment) = maybe_assessment {
+				let price = Perbill::from_rational(land_unit_count, estate_land_unit_count) * assessment.price;
+				assessment.price = assessment.price.saturating_sub(price);
+
+				SelfAssessments::<T>::insert(
+					land,
+					SelfAssessment {
+						owner: owner.clone(),
+						price,
+						..assessment.clone()
+					},
+				);
+			}
+		});
+	}
+
 	fn set_total_undeployed_land_unit(total: u64, deduct: bool) -> Result<(), DispatchError> {
 		let total_undeployed_land_units = Self::all_undeployed_land_unit();
 
@@ -1449,6 +3414,174 @@ impl<T: Config> UndeployedLandBlocksTrait<T::AccountId> for Pallet<T> {
 
 		Ok(undeployed_land_block_id)
 	}
+
+	fn check_undeployed_land_block(
+		who: &T::AccountId,
+		undeployed_land_block_id: UndeployedLandBlockId,
+	) -> Result<bool, DispatchError> {
+		let undeployed_land_block =
+			UndeployedLandBlocks::<T>::get(undeployed_land_block_id).ok_or(Error::<T>::UndeployedLandBlockNotFound)?;
+
+		Ok(undeployed_land_block.owner == *who
+			&& !undeployed_land_block.is_frozen
+			&& !T::AuctionHandler::check_item_in_auction(ItemId::Block(undeployed_land_block_id))
+			&& undeployed_land_block.undeployed_land_block_type == UndeployedLandBlockType::Transferable)
+	}
+}
+
+/// Largest area, in land units, a map query scans
+pub const MAX_MAP_QUERY_AREA: u32 = 10_000;
+
+/// Most free coordinates a single query returns
+pub const MAX_FREE_COORDINATES: u32 = 1_000;
+
+// Map queries served through the runtime API. They are meant to be called off-chain only.
+impl<T: Config> Pallet<T> {
+	/// Owners of the minted land units inside the bounding box. Nothing is returned when the part
+	/// of the box within the metaverse max bounds is larger than `MAX_MAP_QUERY_AREA`.
+	pub fn land_unit_owners_in_area(
+		metaverse_id: MetaverseId,
+		bottom_left: (i32, i32),
+		top_right: (i32, i32),
+	) -> Vec<LandUnitOwnership<T::AccountId>> {
+		let mut land_unit_owners: Vec<LandUnitOwnership<T::AccountId>> = Vec::new();
+		let (bottom_left, top_right) = match Self::map_query_area(metaverse_id, bottom_left, top_right) {
+			Some(area) => area,
+			None => return land_unit_owners,
+		};
+
+		for x in bottom_left.0..=top_right.0 {
+			for y in bottom_left.1..=top_right.1 {
+				if let Some(owner) = LandUnits::<T>::get(metaverse_id, (x, y)) {
+					land_unit_owners.push(Self::land_unit_ownership(metaverse_id, (x, y), owner));
+				}
+			}
+		}
+
+		land_unit_owners
+	}
+
+	/// Estate which contains the land unit
+	pub fn estate_by_coordinate(metaverse_id: MetaverseId, coordinate: (i32, i32)) -> Option<(EstateId, EstateInfo)> {
+		let estate_id = LandUnitEstate::<T>::get(metaverse_id, coordinate)?;
+		Estates::<T>::get(estate_id).map(|estate_info| (estate_id, estate_info))
+	}
+
+	/// Estates owned by the account
+	pub fn estates_by_owner(owner: &T::AccountId) -> Vec<(EstateId, EstateInfo)> {
+		EstateOwner::<T>::iter_prefix(owner)
+			.filter_map(|(estate_id, _)| Estates::<T>::get(estate_id).map(|estate_info| (estate_id, estate_info)))
+			.collect()
+	}
+
+	/// Up to `limit` coordinates inside both the bounding box and the metaverse max bounds which
+	/// have no land unit minted yet. `limit` is capped at `MAX_FREE_COORDINATES` and nothing is
+	/// returned when the part of the box within the max bounds is larger than
+	/// `MAX_MAP_QUERY_AREA`.
+	pub fn free_coordinates(
+		metaverse_id: MetaverseId,
+		bottom_left: (i32, i32),
+		top_right: (i32, i32),
+		limit: u32,
+	) -> Vec<(i32, i32)> {
+		let mut free_coordinates: Vec<(i32, i32)> = Vec::new();
+		let (bottom_left, top_right) = match Self::map_query_area(metaverse_id, bottom_left, top_right) {
+			Some(area) => area,
+			None => return free_coordinates,
+		};
+		let limit = limit.min(MAX_FREE_COORDINATES);
+
+		for x in bottom_left.0..=top_right.0 {
+			for y in bottom_left.1..=top_right.1 {
+				if free_coordinates.len() as u32 >= limit {
+					return free_coordinates;
+				}
+				if !LandUnits::<T>::contains_key(metaverse_id, (x, y)) {
+					free_coordinates.push((x, y));
+				}
+			}
+		}
+
+		free_coordinates
+	}
+
+	/// Minted land units touching the land unit or estate, corners included
+	pub fn neighbours(asset: LandAsset) -> Vec<LandUnitOwnership<T::AccountId>> {
+		let (metaverse_id, land_units) = match asset {
+			LandAsset::LandUnit(coordinate, metaverse_id) => (metaverse_id, sp_std::vec![coordinate]),
+			LandAsset::Estate(estate_id) => match Estates::<T>::get(estate_id) {
+				Some(estate_info) => (estate_info.metaverse_id, estate_info.land_units),
+				None => return Vec::new(),
+			},
+		};
+
+		let own_land_units: BTreeSet<(i32, i32)> = land_units.iter().cloned().collect();
+		let mut neighbour_coordinates: BTreeSet<(i32, i32)> = BTreeSet::new();
+		for (x, y) in land_units {
+			for dx in -1i32..=1 {
+				for dy in -1i32..=1 {
+					let neighbour = (x.saturating_add(dx), y.saturating_add(dy));
+					if !own_land_units.contains(&neighbour) {
+						neighbour_coordinates.insert(neighbour);
+					}
+				}
+			}
+		}
+
+		neighbour_coordinates
+			.into_iter()
+			.filter_map(|coordinate| {
+				LandUnits::<T>::get(metaverse_id, coordinate)
+					.map(|owner| Self::land_unit_ownership(metaverse_id, coordinate, owner))
+			})
+			.collect()
+	}
+
+	/// Bounding box clamped to the metaverse max bounds, as long as it is not larger than
+	/// `MAX_MAP_QUERY_AREA`
+	fn map_query_area(
+		metaverse_id: MetaverseId,
+		bottom_left: (i32, i32),
+		top_right: (i32, i32),
+	) -> Option<((i32, i32), (i32, i32))> {
+		if !MaxBounds::<T>::contains_key(metaverse_id) {
+			return None;
+		}
+
+		let max_bound = MaxBounds::<T>::get(metaverse_id);
+		let bottom_left = (bottom_left.0.max(max_bound.0), bottom_left.1.max(max_bound.0));
+		let top_right = (top_right.0.min(max_bound.1), top_right.1.min(max_bound.1));
+		if bottom_left.0 > top_right.0 || bottom_left.1 > top_right.1 {
+			return None;
+		}
+
+		let width = (top_right.0 as i64) - (bottom_left.0 as i64) + 1;
+		let height = (top_right.1 as i64) - (bottom_left.1 as i64) + 1;
+		if width.saturating_mul(height) > MAX_MAP_QUERY_AREA as i64 {
+			return None;
+		}
+
+		Some((bottom_left, top_right))
+	}
+
+	fn land_unit_ownership(
+		metaverse_id: MetaverseId,
+		coordinate: (i32, i32),
+		owner: T::AccountId,
+	) -> LandUnitOwnership<T::AccountId> {
+		match LandUnitEstate::<T>::get(metaverse_id, coordinate) {
+			Some(estate_id) => LandUnitOwnership {
+				coordinate,
+				owner: OwnerOfEstate::<T>::get(estate_id).unwrap_or(owner),
+				estate_id: Some(estate_id),
+			},
+			None => LandUnitOwnership {
+				coordinate,
+				owner,
+				estate_id: None,
+			},
+		}
+	}
 }
 
 impl<T: Config> Estate<T::AccountId> for Pallet<T> {
@@ -1491,4 +3624,29 @@ impl<T: Config> Estate<T::AccountId> for Pallet<T> {
 	fn get_total_undeploy_land_units() -> u64 {
 		TotalUndeployedLandUnit::<T>::get()
 	}
+
+	fn is_estate_leased(estate_id: EstateId) -> Result<bool, DispatchError> {
+		Ok(Leases::<T>::contains_key(LandAsset::Estate(estate_id)))
+	}
+
+	fn is_land_unit_leased(metaverse_id: MetaverseId, coordinate: (i32, i32)) -> Result<bool, DispatchError> {
+		Ok(Leases::<T>::contains_key(LandAsset::LandUnit(coordinate, metaverse_id)))
+	}
+
+	fn is_estate_collateralized(estate_id: EstateId) -> Result<bool, DispatchError> {
+		Ok(Loans::<T>::contains_key(LandAsset::Estate(estate_id)))
+	}
+
+	fn is_land_unit_collateralized(metaverse_id: MetaverseId, coordinate: (i32, i32)) -> Result<bool, DispatchError> {
+		Ok(Loans::<T>::contains_key(LandAsset::LandUnit(coordinate, metaverse_id)))
+	}
+
+	fn record_land_sale(asset: LandAsset, price: primitives::Balance) {
+		// Sold land is assessed at its sale price until the buyer declares another one
+		SelfAssessments::<T>::mutate(asset, |maybe_assessment| {
+			if let Some(assessment) = maybe_assessment {
+				assessment.price = price.saturated_into();
+			}
+		});
+	}
 }
@@ -1,12 +1,14 @@
 #![cfg(test)]
 
+use frame_support::traits::{Hooks, Nothing};
 use frame_support::{construct_runtime, ord_parameter_types, parameter_types, PalletId};
 use frame_system::EnsureSignedBy;
+use orml_traits::parameter_type_with_key;
 use sp_core::H256;
 use sp_runtime::{testing::Header, traits::IdentityLookup, DispatchError, Perbill};
 
 use auction_manager::{Auction, AuctionInfo, AuctionType, CheckAuctionItemHandler, ListingLevel};
-use primitives::FungibleTokenId;
+use primitives::{Amount, FungibleTokenId};
 
 use crate as estate;
 
@@ -31,6 +33,11 @@ pub const COORDINATE_IN_2: (i32, i32) = (-5, 5);
 pub const COORDINATE_OUT: (i32, i32) = (0, 101);
 pub const COORDINATE_IN_AUCTION: (i32, i32) = (99, 99);
 pub const ESTATE_IN_AUCTION: EstateId = 99;
+pub const UNDEPLOYED_LAND_BLOCK_IN_AUCTION: UndeployedLandBlockId = 3;
+
+pub const SOCIAL_TOKEN: FungibleTokenId = FungibleTokenId::FungibleToken(1);
+pub const LEASE_RENT: Balance = 100;
+pub const LEASE_PERIOD: BlockNumber = 10;
 
 pub const BOND_AMOUNT_1: Balance = 1000;
 pub const BOND_AMOUNT_2: Balance = 2000;
@@ -100,6 +107,7 @@ parameter_types! {
 	pub const GetNativeCurrencyId: FungibleTokenId = FungibleTokenId::NativeToken(0);
 	pub const MiningCurrencyId: FungibleTokenId = FungibleTokenId::MiningResource(0);
 	pub const LandTreasuryPalletId: PalletId = PalletId(*b"bit/land");
+	pub const MetaverseFundPalletId: PalletId = PalletId(*b"bit/fund");
 	pub const MinimumLandPrice: Balance = 10 * DOLLARS;
 }
 
@@ -114,8 +122,24 @@ impl MetaverseTrait<AccountId> for MetaverseInfoSource {
 		}
 	}
 
-	fn get_metaverse(_metaverse_id: u64) -> Option<MetaverseInfo<u128>> {
-		None
+	fn check_role(who: &AccountId, metaverse_id: &MetaverseId, role: &MetaverseRole) -> bool {
+		// Beneficiary is a builder of Alice's metaverse
+		Self::check_ownership(who, metaverse_id)
+			|| (*who == BENEFICIARY_ID && *metaverse_id == ALICE_METAVERSE_ID && *role == MetaverseRole::Builder)
+	}
+
+	fn get_metaverse(metaverse_id: u64) -> Option<MetaverseInfo<u128>> {
+		let owner = match metaverse_id {
+			ALICE_METAVERSE_ID => ALICE,
+			BOB_METAVERSE_ID => BOB,
+			_ => return None,
+		};
+		Some(MetaverseInfo {
+			owner,
+			metadata: vec![],
+			currency_id: FungibleTokenId::NativeToken(0),
+			is_frozen: false,
+		})
 	}
 
 	fn get_metaverse_token(_metaverse_id: u64) -> Option<FungibleTokenId> {
@@ -125,6 +149,10 @@ impl MetaverseTrait<AccountId> for MetaverseInfoSource {
 	fn update_metaverse_token(_metaverse_id: u64, _currency_id: FungibleTokenId) -> Result<(), DispatchError> {
 		Ok(())
 	}
+
+	fn transfer_metaverse(_from: &AccountId, _to: &AccountId, _metaverse_id: u64) -> Result<(), DispatchError> {
+		Ok(())
+	}
 }
 
 pub struct MockAuctionManager;
@@ -201,6 +229,9 @@ impl CheckAuctionItemHandler for MockAuctionManager {
 			ItemId::LandUnit(COORDINATE_IN_AUCTION, METAVERSE_ID) => {
 				return true;
 			}
+			ItemId::Block(UNDEPLOYED_LAND_BLOCK_IN_AUCTION) => {
+				return true;
+			}
 			_ => {
 				return false;
 			}
@@ -213,6 +244,29 @@ parameter_types! {
 	pub const MinimumStake: Balance = 200;
 	/// Reward payments are delayed by 2 hours (2 * 300 * block_time)
 	pub const RewardPaymentDelay: u32 = 2;
+	pub const MaxEstatePayoutsPerBlock: u32 = 1;
+	pub const LeaseGracePeriod: BlockNumber = 5;
+	pub const MaxContentUriLength: u32 = 32;
+	pub const LandTaxGracePeriod: BlockNumber = 5;
+	pub const MaxLandSaleZones: u32 = 2;
+}
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: FungibleTokenId| -> Balance {
+		Default::default()
+	};
+}
+
+impl orml_tokens::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type Amount = Amount;
+	type CurrencyId = FungibleTokenId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type OnDust = ();
+	type MaxLocks = ();
+	type DustRemovalWhitelist = Nothing;
 }
 
 impl Config for Runtime {
@@ -227,6 +281,13 @@ impl Config for Runtime {
 	type WeightInfo = ();
 	type MinimumStake = MinimumStake;
 	type RewardPaymentDelay = RewardPaymentDelay;
+	type MaxEstatePayoutsPerBlock = MaxEstatePayoutsPerBlock;
+	type FungibleTokenCurrency = Tokens;
+	type LeaseGracePeriod = LeaseGracePeriod;
+	type MaxContentUriLength = MaxContentUriLength;
+	type MetaverseFundTreasury = MetaverseFundPalletId;
+	type LandTaxGracePeriod = LandTaxGracePeriod;
+	type MaxLandSaleZones = MaxLandSaleZones;
 }
 
 construct_runtime!(
@@ -237,6 +298,7 @@ construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Tokens: orml_tokens::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Estate: estate:: {Pallet, Call, Storage, Event<T>}
 	}
 );
@@ -266,12 +328,27 @@ impl ExtBuilder {
 		.assimilate_storage(&mut t)
 		.unwrap();
 
+		orml_tokens::GenesisConfig::<Runtime> {
+			balances: vec![(BOB, SOCIAL_TOKEN, 1000)],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
 		let mut ext = sp_io::TestExternalities::new(t);
 		ext.execute_with(|| System::set_block_number(1));
 		ext
 	}
 }
 
+pub fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::on_finalize(System::block_number());
+		System::set_block_number(System::block_number() + 1);
+		System::on_initialize(System::block_number());
+		EstateModule::on_initialize(System::block_number());
+	}
+}
+
 pub fn last_event() -> Event {
 	frame_system::Pallet::<Runtime>::events()
 		.pop()
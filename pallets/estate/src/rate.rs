@@ -41,11 +41,22 @@ fn get_annual_max_issuance<T: Config>(max_supply: u64, annual_percentage: u64) -
 	max_supply.saturating_sub(circulating).saturating_mul(annual_percentage)
 }
 
-/// Compute round issuance range from round inflation range and current total issuance
-pub fn round_issuance_range<T: Config>(config: MintingRateInfo) -> Range<u64> {
+/// Compute the minting rate of a single round from the annual minting rate
+pub fn round_minting_rate<T: Config>(config: MintingRateInfo) -> Perbill {
 	// Get total round per year
-	let total_round_per_year = rounds_per_year::<T>();
+	let total_round_per_year = rounds_per_year::<T>().max(1);
+
+	// Total annual minting percent
+	let annual_percentage = Perbill::from_percent(config.annual as u32).deconstruct();
+
+	// Round percentage minting rate
+	let round_percentage = annual_percentage / total_round_per_year;
 
+	Perbill::from_parts(round_percentage)
+}
+
+/// Compute round issuance range from round inflation range and current total issuance
+pub fn round_issuance_range<T: Config>(config: MintingRateInfo) -> Range<u64> {
 	// Get total land unit circulating
 	let total_land_unit_circulating = <AllLandUnitsCount<T>>::get();
 
@@ -55,14 +66,8 @@ pub fn round_issuance_range<T: Config>(config: MintingRateInfo) -> Range<u64> {
 	// Total circulating
 	let circulating = total_land_unit_circulating.saturating_add(total_undeployed_land_unit_circulating);
 
-	// Total annual minting percent
-	let annual_percentage = Perbill::from_percent(config.annual as u32).deconstruct();
-
 	// Round percentage minting rate
-	let round_percentage = annual_percentage.checked_div(total_round_per_year).unwrap();
-
-	// Convert to percentage
-	let round_percentage_per_bill = Perbill::from_parts(round_percentage);
+	let round_percentage_per_bill = round_minting_rate::<T>(config);
 
 	// Return range - could implement more cases in the future.
 	Range {
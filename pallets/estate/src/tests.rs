@@ -17,10 +17,14 @@
 
 #![cfg(test)]
 
+use frame_support::traits::Hooks;
 use frame_support::{assert_err, assert_noop, assert_ok};
+use sp_core::H256;
 use sp_runtime::traits::BadOrigin;
 
 use mock::{Event, *};
+use primitives::estate::LandSaleZone;
+use primitives::staking::{DelayedPayout, RoundInfo};
 
 use super::*;
 
@@ -886,6 +890,61 @@ fn create_estate_should_fail_for_not_minted_land() {
 	});
 }
 
+#[test]
+fn create_estate_should_fail_for_locked_land_units() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EstateModule::set_max_bounds(Origin::root(), METAVERSE_ID, MAX_BOUND));
+		assert_ok!(EstateModule::mint_lands(
+			Origin::root(),
+			BENEFICIARY_ID,
+			METAVERSE_ID,
+			vec![COORDINATE_IN_1, COORDINATE_IN_2, COORDINATE_IN_AUCTION]
+		));
+
+		assert_noop!(
+			EstateModule::create_estate(
+				Origin::root(),
+				BENEFICIARY_ID,
+				METAVERSE_ID,
+				vec![COORDINATE_IN_1, COORDINATE_IN_AUCTION]
+			),
+			Error::<Runtime>::LandUnitAlreadyInAuction
+		);
+
+		let leased_land_unit = LandAsset::LandUnit(COORDINATE_IN_1, METAVERSE_ID);
+		assert_ok!(EstateModule::offer_lease(
+			Origin::signed(BENEFICIARY_ID),
+			leased_land_unit,
+			BOB,
+			FungibleTokenId::NativeToken(0),
+			LEASE_RENT,
+			LEASE_PERIOD,
+			2
+		));
+		assert_ok!(EstateModule::accept_lease(Origin::signed(BOB), leased_land_unit));
+		assert_noop!(
+			EstateModule::create_estate(Origin::root(), BENEFICIARY_ID, METAVERSE_ID, vec![COORDINATE_IN_1]),
+			Error::<Runtime>::LandUnitIsLeased
+		);
+
+		let collateral = LandAsset::LandUnit(COORDINATE_IN_2, METAVERSE_ID);
+		assert_ok!(EstateModule::request_loan(
+			Origin::signed(BENEFICIARY_ID),
+			collateral,
+			BOB,
+			FungibleTokenId::NativeToken(0),
+			1000,
+			1100,
+			10
+		));
+		assert_ok!(EstateModule::fund_loan(Origin::signed(BOB), collateral));
+		assert_noop!(
+			EstateModule::create_estate(Origin::root(), BENEFICIARY_ID, METAVERSE_ID, vec![COORDINATE_IN_2]),
+			Error::<Runtime>::LandUnitIsCollateralized
+		);
+	});
+}
+
 #[test]
 fn create_estate_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -1319,6 +1378,46 @@ fn transfer_undeployed_land_block_should_fail_if_not_transferable() {
 	});
 }
 
+#[test]
+fn undeployed_land_block_in_auction_should_be_locked() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EstateModule::issue_undeployed_land_blocks(
+			Origin::root(),
+			BOB,
+			4,
+			20,
+			UndeployedLandBlockType::Transferable
+		));
+
+		assert_noop!(
+			EstateModule::transfer_undeployed_land_blocks(Origin::signed(BOB), ALICE, UNDEPLOYED_LAND_BLOCK_IN_AUCTION),
+			Error::<Runtime>::UndeployedLandBlockAlreadyInAuction
+		);
+		assert_noop!(
+			EstateModule::split_undeployed_land_block(Origin::signed(BOB), UNDEPLOYED_LAND_BLOCK_IN_AUCTION, vec![5]),
+			Error::<Runtime>::UndeployedLandBlockAlreadyInAuction
+		);
+		assert_noop!(
+			EstateModule::deploy_land_block(
+				Origin::signed(BOB),
+				UNDEPLOYED_LAND_BLOCK_IN_AUCTION,
+				METAVERSE_ID,
+				vec![COORDINATE_IN_1]
+			),
+			Error::<Runtime>::UndeployedLandBlockAlreadyInAuction
+		);
+		// Council freezes do not lift the auction lock
+		assert_ok!(EstateModule::freeze_undeployed_land_blocks(
+			Origin::root(),
+			UNDEPLOYED_LAND_BLOCK_IN_AUCTION
+		));
+		assert_noop!(
+			EstateModule::burn_undeployed_land_blocks(Origin::root(), UNDEPLOYED_LAND_BLOCK_IN_AUCTION),
+			Error::<Runtime>::UndeployedLandBlockAlreadyInAuction
+		);
+	});
+}
+
 #[test]
 fn transfer_undeployed_land_block_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -2312,3 +2411,2109 @@ fn bond_more_should_reject_stake_has_already_left() {
 		);
 	});
 }
+
+// Ten rounds per year, so a 10% annual minting rate rewards 1% of the stake every round
+const STAKING_ROUND_LENGTH: u32 = 262_980;
+
+fn setup_staking_rounds() {
+	MintingRateConfig::<Runtime>::put(MintingRateInfo {
+		expect: Default::default(),
+		annual: 10,
+		max: 100_000,
+	});
+	Round::<Runtime>::put(RoundInfo::new(1, 0, STAKING_ROUND_LENGTH));
+	assert_ok!(EstateModule::issue_undeployed_land_blocks(
+		Origin::root(),
+		BOB,
+		1,
+		3000,
+		UndeployedLandBlockType::BoundToAddress
+	));
+}
+
+fn round_start(round: u32) -> BlockNumber {
+	((round - 1) * STAKING_ROUND_LENGTH) as BlockNumber
+}
+
+#[test]
+fn new_round_should_snapshot_stake() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EstateModule::set_max_bounds(Origin::root(), METAVERSE_ID, MAX_BOUND));
+		assert_ok!(EstateModule::mint_estate(
+			Origin::root(),
+			BENEFICIARY_ID,
+			METAVERSE_ID,
+			vec![COORDINATE_IN_1, COORDINATE_IN_2]
+		));
+		assert_ok!(EstateModule::bond_more(
+			Origin::signed(BENEFICIARY_ID),
+			0,
+			BOND_AMOUNT_1
+		));
+		setup_staking_rounds();
+
+		EstateModule::on_initialize(round_start(2));
+
+		assert_eq!(EstateModule::round().current, 2);
+		assert_eq!(EstateModule::staked(2), BOND_AMOUNT_1);
+		assert_eq!(
+			EstateModule::at_stake(2, 0).map(|snapshot| snapshot.total_bond),
+			Some(BOND_AMOUNT_1)
+		);
+		assert_eq!(
+			last_event(),
+			Event::Estate(crate::Event::StakeSnapshotUpdated(2, BOND_AMOUNT_1))
+		);
+	});
+}
+
+#[test]
+fn staking_rewards_should_be_paid_lazily_after_payment_delay() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EstateModule::set_max_bounds(Origin::root(), METAVERSE_ID, MAX_BOUND));
+		assert_ok!(EstateModule::mint_estate(
+			Origin::root(),
+			BENEFICIARY_ID,
+			METAVERSE_ID,
+			vec![COORDINATE_IN_1]
+		));
+		assert_ok!(EstateModule::mint_estate(
+			Origin::root(),
+			BENEFICIARY_ID,
+			METAVERSE_ID,
+			vec![COORDINATE_IN_2]
+		));
+		assert_ok!(EstateModule::bond_more(
+			Origin::signed(BENEFICIARY_ID),
+			0,
+			BOND_AMOUNT_1
+		));
+		assert_ok!(EstateModule::bond_more(
+			Origin::signed(BENEFICIARY_ID),
+			1,
+			BOND_AMOUNT_2
+		));
+		setup_staking_rounds();
+
+		let free_balance = Balances::free_balance(BENEFICIARY_ID);
+
+		// Only one stake is snapshot per block, the round lasts until its snapshot is complete
+		EstateModule::on_initialize(round_start(2));
+		assert!(EstateModule::stake_snapshot_progress().is_some());
+		EstateModule::on_initialize(round_start(3));
+		assert_eq!(EstateModule::round().current, 2);
+		assert_eq!(EstateModule::stake_snapshot_progress(), None);
+		assert_eq!(EstateModule::staked(2), 3000);
+
+		// Round 1 had no snapshot, nothing to pay
+		EstateModule::on_initialize(round_start(3) + 1);
+		assert_eq!(EstateModule::round().current, 3);
+		assert_eq!(EstateModule::delayed_payouts(1), None);
+		assert_eq!(EstateModule::next_payout_round(), None);
+		assert_eq!(Balances::free_balance(BENEFICIARY_ID), free_balance);
+		EstateModule::on_initialize(round_start(3) + 2);
+
+		// Round 2 rewards are issued once the payment delay has passed
+		EstateModule::on_initialize(round_start(4) + 1);
+		assert_eq!(
+			EstateModule::delayed_payouts(2),
+			Some(DelayedPayout {
+				round_issuance: 30,
+				total_staked: 3000,
+			})
+		);
+		assert_eq!(EstateModule::next_payout_round(), Some(2));
+		let event = Event::Estate(crate::Event::NewRound(round_start(4) + 1, 4, 30));
+		assert!(System::events().iter().any(|record| record.event == event));
+
+		// Only one estate is paid per block
+		EstateModule::on_initialize(round_start(4) + 2);
+		assert!(EstateModule::delayed_payouts(2).is_some());
+
+		EstateModule::on_initialize(round_start(4) + 3);
+		assert_eq!(EstateModule::delayed_payouts(2), None);
+		assert_eq!(EstateModule::next_payout_round(), None);
+		assert_eq!(last_event(), Event::Estate(crate::Event::StakersPaid(2)));
+
+		assert_eq!(Balances::free_balance(BENEFICIARY_ID), free_balance + 30);
+		assert_eq!(
+			Balances::reserved_balance(BENEFICIARY_ID),
+			BOND_AMOUNT_1 + BOND_AMOUNT_2
+		);
+	});
+}
+
+#[test]
+fn leave_staking_should_return_stake_on_new_round() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EstateModule::set_max_bounds(Origin::root(), METAVERSE_ID, MAX_BOUND));
+		assert_ok!(EstateModule::mint_estate(
+			Origin::root(),
+			BENEFICIARY_ID,
+			METAVERSE_ID,
+			vec![COORDINATE_IN_1, COORDINATE_IN_2]
+		));
+		assert_ok!(EstateModule::bond_more(
+			Origin::signed(BENEFICIARY_ID),
+			0,
+			BOND_AMOUNT_1
+		));
+		assert_ok!(EstateModule::leave_staking(Origin::signed(BENEFICIARY_ID), 0));
+		setup_staking_rounds();
+
+		EstateModule::on_initialize(round_start(2));
+
+		let event = Event::Estate(crate::Event::ExitQueueCleared(2));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(Balances::reserved_balance(BENEFICIARY_ID), 0);
+		assert_eq!(EstateModule::estate_stake(0, BENEFICIARY_ID), 0);
+		assert_eq!(EstateModule::total_stake(), 0);
+		assert!(EstateModule::at_stake(2, 0).is_none());
+	});
+}
+
+fn setup_leasable_estate() -> LandAsset {
+	assert_ok!(EstateModule::set_max_bounds(Origin::root(), METAVERSE_ID, MAX_BOUND));
+	assert_ok!(EstateModule::mint_estate(
+		Origin::root(),
+		BENEFICIARY_ID,
+		METAVERSE_ID,
+		vec![COORDINATE_IN_1, COORDINATE_IN_2]
+	));
+	LandAsset::Estate(0)
+}
+
+#[test]
+fn offer_lease_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		let asset = setup_leasable_estate();
+
+		assert_noop!(
+			EstateModule::offer_lease(
+				Origin::signed(ALICE),
+				asset,
+				BOB,
+				FungibleTokenId::NativeToken(0),
+				LEASE_RENT,
+				LEASE_PERIOD,
+				3
+			),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			EstateModule::offer_lease(
+				Origin::signed(BENEFICIARY_ID),
+				asset,
+				BOB,
+				FungibleTokenId::NativeToken(0),
+				0,
+				LEASE_PERIOD,
+				3
+			),
+			Error::<Runtime>::InvalidLeaseTerms
+		);
+
+		assert_ok!(EstateModule::offer_lease(
+			Origin::signed(BENEFICIARY_ID),
+			asset,
+			BOB,
+			FungibleTokenId::NativeToken(0),
+			LEASE_RENT,
+			LEASE_PERIOD,
+			3
+		));
+		assert_noop!(
+			EstateModule::offer_lease(
+				Origin::signed(BENEFICIARY_ID),
+				asset,
+				ALICE,
+				FungibleTokenId::NativeToken(0),
+				LEASE_RENT,
+				LEASE_PERIOD,
+				3
+			),
+			Error::<Runtime>::LeaseOfferAlreadyExists
+		);
+		assert_noop!(
+			EstateModule::accept_lease(Origin::signed(ALICE), asset),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(EstateModule::cancel_lease_offer(Origin::signed(BOB), asset));
+		assert_eq!(last_event(), Event::Estate(crate::Event::LeaseOfferCancelled(asset)));
+		assert_noop!(
+			EstateModule::accept_lease(Origin::signed(BOB), asset),
+			Error::<Runtime>::LeaseOfferNotFound
+		);
+	});
+}
+
+#[test]
+fn accept_lease_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let asset = setup_leasable_estate();
+
+		assert_ok!(EstateModule::offer_lease(
+			Origin::signed(BENEFICIARY_ID),
+			asset,
+			BOB,
+			FungibleTokenId::NativeToken(0),
+			LEASE_RENT,
+			LEASE_PERIOD,
+			3
+		));
+		assert_eq!(
+			last_event(),
+			Event::Estate(crate::Event::LeaseOffered(asset, BENEFICIARY_ID, BOB, LEASE_RENT))
+		);
+
+		let landlord_balance = Balances::free_balance(BENEFICIARY_ID);
+		let tenant_balance = Balances::free_balance(BOB);
+
+		assert_ok!(EstateModule::accept_lease(Origin::signed(BOB), asset));
+
+		assert_eq!(
+			EstateModule::leases(asset),
+			Some(LeaseInfo {
+				landlord: BENEFICIARY_ID,
+				tenant: BOB,
+				currency_id: FungibleTokenId::NativeToken(0),
+				rent: LEASE_RENT,
+				period: LEASE_PERIOD,
+				end_block: 31,
+				paid_until: 11,
+			})
+		);
+		assert_eq!(EstateModule::lease_offers(asset), None);
+		assert_eq!(Balances::free_balance(BENEFICIARY_ID), landlord_balance + LEASE_RENT);
+		assert_eq!(Balances::free_balance(BOB), tenant_balance - LEASE_RENT);
+
+		// Tenant gets the usage rights, the landlord keeps ownership
+		assert!(EstateModule::has_usage_rights(&BOB, &asset));
+		assert!(!EstateModule::has_usage_rights(&BENEFICIARY_ID, &asset));
+		assert_eq!(EstateModule::get_estate_owner(BENEFICIARY_ID, 0), Some(()));
+		assert_eq!(EstateModule::is_estate_leased(0), Ok(true));
+
+		assert_noop!(
+			EstateModule::transfer_estate(Origin::signed(BENEFICIARY_ID), ALICE, 0),
+			Error::<Runtime>::EstateIsLeased
+		);
+		assert_noop!(
+			EstateModule::dissolve_estate(Origin::signed(BENEFICIARY_ID), 0),
+			Error::<Runtime>::EstateIsLeased
+		);
+	});
+}
+
+#[test]
+fn pay_rent_in_social_token_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EstateModule::set_max_bounds(Origin::root(), METAVERSE_ID, MAX_BOUND));
+		assert_ok!(EstateModule::mint_land(
+			Origin::root(),
+			BENEFICIARY_ID,
+			METAVERSE_ID,
+			COORDINATE_IN_1
+		));
+		let asset = LandAsset::LandUnit(COORDINATE_IN_1, METAVERSE_ID);
+
+		assert_ok!(EstateModule::offer_lease(
+			Origin::signed(BENEFICIARY_ID),
+			asset,
+			BOB,
+			SOCIAL_TOKEN,
+			LEASE_RENT,
+			LEASE_PERIOD,
+			2
+		));
+		assert_ok!(EstateModule::accept_lease(Origin::signed(BOB), asset));
+		assert_eq!(Tokens::accounts(BOB, SOCIAL_TOKEN).free, 900);
+
+		assert_noop!(
+			EstateModule::transfer_land(Origin::signed(BENEFICIARY_ID), ALICE, METAVERSE_ID, COORDINATE_IN_1),
+			Error::<Runtime>::LandUnitIsLeased
+		);
+		assert_noop!(
+			EstateModule::pay_rent(Origin::signed(ALICE), asset),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(EstateModule::pay_rent(Origin::signed(BOB), asset));
+		assert_eq!(
+			last_event(),
+			Event::Estate(crate::Event::RentPaid(asset, BOB, LEASE_RENT, 21))
+		);
+		assert_eq!(Tokens::accounts(BOB, SOCIAL_TOKEN).free, 800);
+		assert_eq!(Tokens::accounts(BENEFICIARY_ID, SOCIAL_TOKEN).free, 200);
+
+		assert_noop!(
+			EstateModule::pay_rent(Origin::signed(BOB), asset),
+			Error::<Runtime>::RentAlreadyPaidInFull
+		);
+
+		// Lease can be closed once the term is over
+		assert_noop!(
+			EstateModule::terminate_lease(Origin::signed(BENEFICIARY_ID), asset),
+			Error::<Runtime>::LeaseIsActive
+		);
+		run_to_block(21);
+		assert_ok!(EstateModule::terminate_lease(Origin::signed(BENEFICIARY_ID), asset));
+		assert_ok!(EstateModule::transfer_land(
+			Origin::signed(BENEFICIARY_ID),
+			ALICE,
+			METAVERSE_ID,
+			COORDINATE_IN_1
+		));
+	});
+}
+
+#[test]
+fn usage_rights_should_return_to_owner_when_lease_ends() {
+	ExtBuilder::default().build().execute_with(|| {
+		let asset = setup_leasable_estate();
+
+		assert_ok!(EstateModule::offer_lease(
+			Origin::signed(BENEFICIARY_ID),
+			asset,
+			BOB,
+			FungibleTokenId::NativeToken(0),
+			LEASE_RENT,
+			LEASE_PERIOD,
+			3
+		));
+		assert_ok!(EstateModule::accept_lease(Origin::signed(BOB), asset));
+
+		// The lease term ends at block 31
+		run_to_block(30);
+		assert!(EstateModule::has_usage_rights(&BOB, &asset));
+		assert!(!EstateModule::has_usage_rights(&BENEFICIARY_ID, &asset));
+
+		run_to_block(31);
+		assert!(EstateModule::leases(asset).is_some());
+		assert!(!EstateModule::has_usage_rights(&BOB, &asset));
+		assert!(EstateModule::has_usage_rights(&BENEFICIARY_ID, &asset));
+	});
+}
+
+#[test]
+fn missed_rent_should_allow_termination_after_grace_period() {
+	ExtBuilder::default().build().execute_with(|| {
+		let asset = setup_leasable_estate();
+
+		assert_ok!(EstateModule::offer_lease(
+			Origin::signed(BENEFICIARY_ID),
+			asset,
+			BOB,
+			FungibleTokenId::NativeToken(0),
+			LEASE_RENT,
+			LEASE_PERIOD,
+			3
+		));
+		assert_ok!(EstateModule::accept_lease(Origin::signed(BOB), asset));
+
+		// Rent is paid until block 11 and the grace period lasts 5 more blocks
+		run_to_block(16);
+		assert_noop!(
+			EstateModule::terminate_lease(Origin::signed(BENEFICIARY_ID), asset),
+			Error::<Runtime>::LeaseIsActive
+		);
+
+		run_to_block(17);
+		assert_noop!(
+			EstateModule::pay_rent(Origin::signed(BOB), asset),
+			Error::<Runtime>::RentOverdue
+		);
+		assert_ok!(EstateModule::terminate_lease(Origin::signed(BENEFICIARY_ID), asset));
+		assert_eq!(
+			last_event(),
+			Event::Estate(crate::Event::LeaseTerminated(asset, BENEFICIARY_ID, BOB))
+		);
+		assert_eq!(EstateModule::leases(asset), None);
+		assert!(EstateModule::has_usage_rights(&BENEFICIARY_ID, &asset));
+
+		assert_ok!(EstateModule::transfer_estate(Origin::signed(BENEFICIARY_ID), ALICE, 0));
+	});
+}
+
+#[test]
+fn merge_estates_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EstateModule::set_max_bounds(Origin::root(), METAVERSE_ID, MAX_BOUND));
+		assert_ok!(EstateModule::mint_estate(
+			Origin::root(),
+			BENEFICIARY_ID,
+			METAVERSE_ID,
+			vec![COORDINATE_IN_1]
+		));
+		assert_ok!(EstateModule::mint_estate(
+			Origin::root(),
+			BENEFICIARY_ID,
+			METAVERSE_ID,
+			vec![COORDINATE_IN_2]
+		));
+		assert_ok!(EstateModule::bond_more(
+			Origin::signed(BENEFICIARY_ID),
+			0,
+			BOND_AMOUNT_1
+		));
+		assert_ok!(EstateModule::bond_more(
+			Origin::signed(BENEFICIARY_ID),
+			1,
+			BOND_AMOUNT_2
+		));
+
+		assert_noop!(
+			EstateModule::merge_estates(Origin::signed(ALICE), 0, vec![1]),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			EstateModule::merge_estates(Origin::signed(BENEFICIARY_ID), 0, vec![0]),
+			Error::<Runtime>::InvalidEstateMerge
+		);
+
+		assert_ok!(EstateModule::merge_estates(Origin::signed(BENEFICIARY_ID), 0, vec![1]));
+		assert_eq!(
+			last_event(),
+			Event::Estate(crate::Event::EstatesMerged(0, BENEFICIARY_ID, vec![1]))
+		);
+
+		assert_eq!(
+			EstateModule::get_estates(0),
+			Some(EstateInfo {
+				metaverse_id: METAVERSE_ID,
+				land_units: vec![COORDINATE_IN_1, COORDINATE_IN_2],
+			})
+		);
+		assert_eq!(EstateModule::get_estates(1), None);
+		assert_eq!(EstateModule::get_estate_owner(BENEFICIARY_ID, 1), None);
+		assert_eq!(EstateModule::all_estates_count(), 1);
+
+		let estate_account_id: AccountId = LandTreasuryPalletId::get().into_sub_account(0u64);
+		assert_eq!(
+			EstateModule::get_land_units(METAVERSE_ID, COORDINATE_IN_2),
+			Some(estate_account_id)
+		);
+
+		// Stake is carried over to the kept estate
+		assert_eq!(
+			EstateModule::estate_stake(0, BENEFICIARY_ID),
+			BOND_AMOUNT_1 + BOND_AMOUNT_2
+		);
+		assert_eq!(EstateModule::estate_stake(1, BENEFICIARY_ID), 0);
+		assert_eq!(EstateModule::total_stake(), BOND_AMOUNT_1 + BOND_AMOUNT_2);
+	});
+}
+
+#[test]
+fn merge_estates_should_return_stake_of_exiting_stakers() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EstateModule::set_max_bounds(Origin::root(), METAVERSE_ID, MAX_BOUND));
+		assert_ok!(EstateModule::mint_estate(
+			Origin::root(),
+			BENEFICIARY_ID,
+			METAVERSE_ID,
+			vec![COORDINATE_IN_1]
+		));
+		assert_ok!(EstateModule::mint_estate(
+			Origin::root(),
+			BENEFICIARY_ID,
+			METAVERSE_ID,
+			vec![COORDINATE_IN_2]
+		));
+		assert_ok!(EstateModule::bond_more(
+			Origin::signed(BENEFICIARY_ID),
+			0,
+			BOND_AMOUNT_1
+		));
+		assert_ok!(EstateModule::bond_more(
+			Origin::signed(BENEFICIARY_ID),
+			1,
+			BOND_AMOUNT_2
+		));
+		assert_ok!(EstateModule::leave_staking(Origin::signed(BENEFICIARY_ID), 1));
+
+		assert_ok!(EstateModule::merge_estates(Origin::signed(BENEFICIARY_ID), 0, vec![1]));
+
+		assert_eq!(EstateModule::estate_stake(0, BENEFICIARY_ID), BOND_AMOUNT_1);
+		assert_eq!(EstateModule::estate_stake(1, BENEFICIARY_ID), 0);
+		assert_eq!(EstateModule::exit_queue(BENEFICIARY_ID, 1), None);
+		assert_eq!(EstateModule::total_stake(), BOND_AMOUNT_1);
+		assert_eq!(Balances::reserved_balance(BENEFICIARY_ID), BOND_AMOUNT_1);
+	});
+}
+
+#[test]
+fn merge_estates_should_fail_for_different_metaverse() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EstateModule::set_max_bounds(Origin::root(), METAVERSE_ID, MAX_BOUND));
+		assert_ok!(EstateModule::set_max_bounds(
+			Origin::root(),
+			ALICE_METAVERSE_ID,
+			MAX_BOUND
+		));
+		assert_ok!(EstateModule::mint_estate(
+			Origin::root(),
+			BENEFICIARY_ID,
+			METAVERSE_ID,
+			vec![COORDINATE_IN_1]
+		));
+		assert_ok!(EstateModule::mint_estate(
+			Origin::root(),
+			BENEFICIARY_ID,
+			ALICE_METAVERSE_ID,
+			vec![COORDINATE_IN_1]
+		));
+
+		assert_noop!(
+			EstateModule::merge_estates(Origin::signed(BENEFICIARY_ID), 0, vec![1]),
+			Error::<Runtime>::EstatesNotInSameMetaverse
+		);
+	});
+}
+
+#[test]
+fn split_estate_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EstateModule::set_max_bounds(Origin::root(), METAVERSE_ID, MAX_BOUND));
+		assert_ok!(EstateModule::mint_estate(
+			Origin::root(),
+			BENEFICIARY_ID,
+			METAVERSE_ID,
+			vec![COORDINATE_IN_1, COORDINATE_IN_2]
+		));
+		assert_ok!(EstateModule::bond_more(
+			Origin::signed(BENEFICIARY_ID),
+			0,
+			BOND_AMOUNT_1
+		));
+
+		assert_ok!(EstateModule::split_estate(
+			Origin::signed(BENEFICIARY_ID),
+			0,
+			vec![vec![COORDINATE_IN_2]]
+		));
+		assert_eq!(
+			last_event(),
+			Event::Estate(crate::Event::EstateSplit(0, BENEFICIARY_ID, vec![1]))
+		);
+
+		assert_eq!(
+			EstateModule::get_estates(0),
+			Some(EstateInfo {
+				metaverse_id: METAVERSE_ID,
+				land_units: vec![COORDINATE_IN_1],
+			})
+		);
+		assert_eq!(
+			EstateModule::get_estates(1),
+			Some(EstateInfo {
+				metaverse_id: METAVERSE_ID,
+				land_units: vec![COORDINATE_IN_2],
+			})
+		);
+		assert_eq!(EstateModule::get_estate_owner(BENEFICIARY_ID, 1), Some(()));
+		assert_eq!(EstateModule::all_estates_count(), 2);
+
+		let new_estate_account_id: AccountId = LandTreasuryPalletId::get().into_sub_account(1u64);
+		assert_eq!(
+			EstateModule::get_land_units(METAVERSE_ID, COORDINATE_IN_2),
+			Some(new_estate_account_id)
+		);
+
+		// Stake is split pro rata by land unit count
+		assert_eq!(EstateModule::estate_stake(0, BENEFICIARY_ID), BOND_AMOUNT_1 / 2);
+		assert_eq!(EstateModule::estate_stake(1, BENEFICIARY_ID), BOND_AMOUNT_1 / 2);
+		assert_eq!(EstateModule::total_stake(), BOND_AMOUNT_1);
+	});
+}
+
+#[test]
+fn split_estate_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EstateModule::set_max_bounds(Origin::root(), METAVERSE_ID, MAX_BOUND));
+		assert_ok!(EstateModule::mint_estate(
+			Origin::root(),
+			BENEFICIARY_ID,
+			METAVERSE_ID,
+			vec![COORDINATE_IN_1, COORDINATE_IN_2]
+		));
+
+		assert_noop!(
+			EstateModule::split_estate(Origin::signed(ALICE), 0, vec![vec![COORDINATE_IN_2]]),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			EstateModule::split_estate(Origin::signed(BENEFICIARY_ID), 0, vec![vec![(1, 1)]]),
+			Error::<Runtime>::LandUnitDoesNotExist
+		);
+		// Estate can not be split into nothing
+		assert_noop!(
+			EstateModule::split_estate(
+				Origin::signed(BENEFICIARY_ID),
+				0,
+				vec![vec![COORDINATE_IN_1], vec![COORDINATE_IN_2]]
+			),
+			Error::<Runtime>::InvalidEstateSplit
+		);
+
+		assert_ok!(EstateModule::offer_lease(
+			Origin::signed(BENEFICIARY_ID),
+			LandAsset::Estate(0),
+			BOB,
+			FungibleTokenId::NativeToken(0),
+			LEASE_RENT,
+			LEASE_PERIOD,
+			3
+		));
+		assert_ok!(EstateModule::accept_lease(Origin::signed(BOB), LandAsset::Estate(0)));
+		assert_noop!(
+			EstateModule::split_estate(Origin::signed(BENEFICIARY_ID), 0, vec![vec![COORDINATE_IN_2]]),
+			Error::<Runtime>::EstateIsLeased
+		);
+	});
+}
+
+#[test]
+fn set_estate_shape_rule_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			EstateModule::set_estate_shape_rule(Origin::signed(ALICE), METAVERSE_ID, EstateShapeRule::Rectangular),
+			BadOrigin
+		);
+
+		assert_ok!(EstateModule::set_estate_shape_rule(
+			Origin::root(),
+			METAVERSE_ID,
+			EstateShapeRule::Rectangular
+		));
+		assert_eq!(
+			last_event(),
+			Event::Estate(crate::Event::EstateShapeRuleSet(
+				METAVERSE_ID,
+				EstateShapeRule::Rectangular
+			))
+		);
+		assert_eq!(
+			EstateModule::get_estate_shape_rule(METAVERSE_ID),
+			EstateShapeRule::Rectangular
+		);
+		assert_eq!(
+			EstateModule::get_estate_shape_rule(ALICE_METAVERSE_ID),
+			EstateShapeRule::Unrestricted
+		);
+	});
+}
+
+#[test]
+fn mint_estate_should_follow_connected_shape_rule() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EstateModule::set_max_bounds(Origin::root(), METAVERSE_ID, MAX_BOUND));
+		assert_ok!(EstateModule::set_estate_shape_rule(
+			Origin::root(),
+			METAVERSE_ID,
+			EstateShapeRule::FourConnected
+		));
+
+		assert_noop!(
+			EstateModule::mint_estate(Origin::root(), BENEFICIARY_ID, METAVERSE_ID, vec![(0, 0), (1, 1)]),
+			Error::<Runtime>::InvalidEstateShape
+		);
+		assert_ok!(EstateModule::mint_estate(
+			Origin::root(),
+			BENEFICIARY_ID,
+			METAVERSE_ID,
+			vec![(0, 0), (1, 0), (1, 1)]
+		));
+
+		assert_ok!(EstateModule::set_estate_shape_rule(
+			Origin::root(),
+			METAVERSE_ID,
+			EstateShapeRule::EightConnected
+		));
+		assert_ok!(EstateModule::mint_estate(
+			Origin::root(),
+			BENEFICIARY_ID,
+			METAVERSE_ID,
+			vec![(5, 5), (6, 6)]
+		));
+		assert_noop!(
+			EstateModule::mint_estate(Origin::root(), BENEFICIARY_ID, METAVERSE_ID, vec![(10, 10), (12, 12)]),
+			Error::<Runtime>::InvalidEstateShape
+		);
+	});
+}
+
+#[test]
+fn mint_estate_should_follow_rectangular_shape_rule() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EstateModule::set_max_bounds(Origin::root(), METAVERSE_ID, MAX_BOUND));
+		assert_ok!(EstateModule::set_estate_shape_rule(
+			Origin::root(),
+			METAVERSE_ID,
+			EstateShapeRule::Rectangular
+		));
+
+		assert_noop!(
+			EstateModule::mint_estate(
+				Origin::root(),
+				BENEFICIARY_ID,
+				METAVERSE_ID,
+				vec![(0, 0), (0, 1), (1, 0)]
+			),
+			Error::<Runtime>::InvalidEstateShape
+		);
+		assert_ok!(EstateModule::mint_estate(
+			Origin::root(),
+			BENEFICIARY_ID,
+			METAVERSE_ID,
+			vec![(0, 0), (0, 1), (1, 0), (1, 1)]
+		));
+	});
+}
+
+#[test]
+fn remove_land_unit_from_estate_should_follow_shape_rule() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EstateModule::set_max_bounds(Origin::root(), METAVERSE_ID, MAX_BOUND));
+		assert_ok!(EstateModule::set_estate_shape_rule(
+			Origin::root(),
+			METAVERSE_ID,
+			EstateShapeRule::FourConnected
+		));
+		assert_ok!(EstateModule::mint_estate(
+			Origin::root(),
+			BENEFICIARY_ID,
+			METAVERSE_ID,
+			vec![(0, 0), (1, 0), (2, 0)]
+		));
+
+		// Removing the middle land unit would cut the estate in two
+		assert_noop!(
+			EstateModule::remove_land_unit_from_estate(Origin::signed(BENEFICIARY_ID), 0, vec![(1, 0)]),
+			Error::<Runtime>::InvalidEstateShape
+		);
+		assert_ok!(EstateModule::remove_land_unit_from_estate(
+			Origin::signed(BENEFICIARY_ID),
+			0,
+			vec![(2, 0)]
+		));
+	});
+}
+
+#[test]
+fn set_parcel_content_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let asset = setup_leasable_estate();
+		let manifest_hash = H256::repeat_byte(1);
+
+		assert_noop!(
+			EstateModule::set_parcel_content(Origin::signed(ALICE), asset, manifest_hash, b"ipfs://scene".to_vec()),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			EstateModule::set_parcel_content(Origin::signed(BENEFICIARY_ID), asset, manifest_hash, vec![0u8; 33]),
+			Error::<Runtime>::ContentUriTooLong
+		);
+
+		assert_ok!(EstateModule::set_parcel_content(
+			Origin::signed(BENEFICIARY_ID),
+			asset,
+			manifest_hash,
+			b"ipfs://scene".to_vec()
+		));
+		assert_eq!(
+			last_event(),
+			Event::Estate(crate::Event::ParcelContentUpdated(
+				asset,
+				BENEFICIARY_ID,
+				1,
+				manifest_hash
+			))
+		);
+
+		assert_ok!(EstateModule::set_parcel_content(
+			Origin::signed(BENEFICIARY_ID),
+			asset,
+			H256::repeat_byte(2),
+			b"ipfs://scene-2".to_vec()
+		));
+		assert_eq!(
+			EstateModule::parcel_content(asset),
+			Some(ParcelContent {
+				manifest_hash: H256::repeat_byte(2),
+				uri: b"ipfs://scene-2".to_vec(),
+				version: 2,
+				updated_by: BENEFICIARY_ID,
+				updated_at: 1,
+			})
+		);
+
+		// Content goes away with the estate
+		assert_ok!(EstateModule::dissolve_estate(Origin::signed(BENEFICIARY_ID), 0));
+		assert_eq!(EstateModule::parcel_content(asset), None);
+	});
+}
+
+#[test]
+fn builder_grant_should_allow_content_update() {
+	ExtBuilder::default().build().execute_with(|| {
+		let asset = setup_leasable_estate();
+		let manifest_hash = H256::repeat_byte(1);
+
+		assert_noop!(
+			EstateModule::grant_builder(Origin::signed(ALICE), asset, BOB, None),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(EstateModule::grant_builder(
+			Origin::signed(BENEFICIARY_ID),
+			asset,
+			BOB,
+			Some(10)
+		));
+		assert_eq!(
+			last_event(),
+			Event::Estate(crate::Event::BuilderGranted(asset, BOB, Some(10)))
+		);
+
+		assert_ok!(EstateModule::set_parcel_content(
+			Origin::signed(BOB),
+			asset,
+			manifest_hash,
+			b"ipfs://scene".to_vec()
+		));
+
+		// Grant is only valid until its expiry
+		run_to_block(11);
+		assert_noop!(
+			EstateModule::set_parcel_content(Origin::signed(BOB), asset, manifest_hash, b"ipfs://scene".to_vec()),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(EstateModule::grant_builder(
+			Origin::signed(BENEFICIARY_ID),
+			asset,
+			BOB,
+			None
+		));
+		assert_ok!(EstateModule::revoke_builder(Origin::signed(BENEFICIARY_ID), asset, BOB));
+		assert_eq!(last_event(), Event::Estate(crate::Event::BuilderRevoked(asset, BOB)));
+		assert_noop!(
+			EstateModule::set_parcel_content(Origin::signed(BOB), asset, manifest_hash, b"ipfs://scene".to_vec()),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			EstateModule::revoke_builder(Origin::signed(BENEFICIARY_ID), asset, BOB),
+			Error::<Runtime>::BuilderGrantNotFound
+		);
+	});
+}
+
+#[test]
+fn builder_grant_should_lapse_when_granter_loses_usage_rights() {
+	ExtBuilder::default().build().execute_with(|| {
+		let asset = setup_leasable_estate();
+
+		assert_ok!(EstateModule::grant_builder(
+			Origin::signed(BENEFICIARY_ID),
+			asset,
+			ALICE,
+			None
+		));
+		assert!(EstateModule::can_build(&ALICE, &asset));
+
+		// Leasing the estate out hands the build rights to the tenant
+		assert_ok!(EstateModule::offer_lease(
+			Origin::signed(BENEFICIARY_ID),
+			asset,
+			BOB,
+			FungibleTokenId::NativeToken(0),
+			LEASE_RENT,
+			LEASE_PERIOD,
+			3
+		));
+		assert_ok!(EstateModule::accept_lease(Origin::signed(BOB), asset));
+
+		assert!(!EstateModule::can_build(&ALICE, &asset));
+		assert!(!EstateModule::can_build(&BENEFICIARY_ID, &asset));
+		assert!(EstateModule::can_build(&BOB, &asset));
+	});
+}
+
+fn setup_map() {
+	assert_ok!(EstateModule::set_max_bounds(Origin::root(), METAVERSE_ID, MAX_BOUND));
+	assert_ok!(EstateModule::mint_lands(
+		Origin::root(),
+		BOB,
+		METAVERSE_ID,
+		vec![(0, 0)]
+	));
+	assert_ok!(EstateModule::mint_estate(
+		Origin::root(),
+		BENEFICIARY_ID,
+		METAVERSE_ID,
+		vec![(1, 0), (1, 1)]
+	));
+}
+
+fn sorted_by_coordinate(mut land_units: Vec<LandUnitOwnership<AccountId>>) -> Vec<LandUnitOwnership<AccountId>> {
+	land_units.sort_by_key(|land_unit| land_unit.coordinate);
+	land_units
+}
+
+#[test]
+fn land_unit_owners_in_area_should_resolve_estate_owner() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_map();
+
+		assert_eq!(
+			sorted_by_coordinate(EstateModule::land_unit_owners_in_area(METAVERSE_ID, (0, 0), (1, 1))),
+			vec![
+				LandUnitOwnership {
+					coordinate: (0, 0),
+					owner: BOB,
+					estate_id: None,
+				},
+				LandUnitOwnership {
+					coordinate: (1, 0),
+					owner: BENEFICIARY_ID,
+					estate_id: Some(0),
+				},
+				LandUnitOwnership {
+					coordinate: (1, 1),
+					owner: BENEFICIARY_ID,
+					estate_id: Some(0),
+				},
+			]
+		);
+		assert_eq!(
+			EstateModule::land_unit_owners_in_area(METAVERSE_ID, (1, 1), (5, 5)),
+			vec![LandUnitOwnership {
+				coordinate: (1, 1),
+				owner: BENEFICIARY_ID,
+				estate_id: Some(0),
+			}]
+		);
+		assert_eq!(
+			EstateModule::land_unit_owners_in_area(ALICE_METAVERSE_ID, (0, 0), (1, 1)),
+			vec![]
+		);
+
+		// Boxes over the query area limit are not scanned
+		assert_eq!(
+			EstateModule::land_unit_owners_in_area(
+				METAVERSE_ID,
+				(MAX_BOUND.0, MAX_BOUND.0),
+				(MAX_BOUND.1, MAX_BOUND.1)
+			),
+			vec![]
+		);
+
+		assert_ok!(EstateModule::transfer_estate(Origin::signed(BENEFICIARY_ID), ALICE, 0));
+		assert_eq!(
+			EstateModule::land_unit_owners_in_area(METAVERSE_ID, (1, 1), (1, 1)),
+			vec![LandUnitOwnership {
+				coordinate: (1, 1),
+				owner: ALICE,
+				estate_id: Some(0),
+			}]
+		);
+	});
+}
+
+#[test]
+fn estate_queries_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_map();
+
+		let estate_info = EstateInfo {
+			metaverse_id: METAVERSE_ID,
+			land_units: vec![(1, 0), (1, 1)],
+		};
+		assert_eq!(
+			EstateModule::estate_by_coordinate(METAVERSE_ID, (1, 1)),
+			Some((0, estate_info.clone()))
+		);
+		assert_eq!(EstateModule::estate_by_coordinate(METAVERSE_ID, (0, 0)), None);
+		assert_eq!(EstateModule::estate_by_coordinate(ALICE_METAVERSE_ID, (1, 1)), None);
+
+		assert_eq!(EstateModule::estates_by_owner(&BENEFICIARY_ID), vec![(0, estate_info)]);
+		assert_eq!(EstateModule::estates_by_owner(&BOB), vec![]);
+
+		assert_ok!(EstateModule::remove_land_unit_from_estate(
+			Origin::signed(BENEFICIARY_ID),
+			0,
+			vec![(1, 1)]
+		));
+		assert_eq!(EstateModule::estate_by_coordinate(METAVERSE_ID, (1, 1)), None);
+
+		assert_ok!(EstateModule::dissolve_estate(Origin::signed(BENEFICIARY_ID), 0));
+		assert_eq!(EstateModule::estate_by_coordinate(METAVERSE_ID, (1, 0)), None);
+		assert_eq!(EstateModule::get_owner_of_estate(0), None);
+	});
+}
+
+#[test]
+fn free_coordinates_should_skip_minted_land_and_respect_limit() {
+	ExtBuilder::default().build().execute_with(|| {
+		// No max bound set yet
+		assert_eq!(EstateModule::free_coordinates(METAVERSE_ID, (0, 0), (1, 1), 10), vec![]);
+
+		setup_map();
+
+		assert_eq!(
+			EstateModule::free_coordinates(METAVERSE_ID, (0, 0), (2, 1), 10),
+			vec![(0, 1), (2, 0), (2, 1)]
+		);
+		assert_eq!(
+			EstateModule::free_coordinates(METAVERSE_ID, (0, 0), (2, 1), 2),
+			vec![(0, 1), (2, 0)]
+		);
+		// Clamped to the metaverse max bounds
+		assert_eq!(
+			EstateModule::free_coordinates(METAVERSE_ID, (100, 100), (105, 105), 10),
+			vec![(100, 100)]
+		);
+		// Areas larger than a map query scans are refused and the limit is capped
+		assert_eq!(
+			EstateModule::free_coordinates(METAVERSE_ID, (-100, -100), (100, 100), 10),
+			vec![]
+		);
+		assert_eq!(
+			EstateModule::free_coordinates(METAVERSE_ID, (-100, -100), (-1, -1), u32::MAX).len() as u32,
+			MAX_FREE_COORDINATES
+		);
+	});
+}
+
+#[test]
+fn neighbours_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_map();
+
+		assert_eq!(
+			EstateModule::neighbours(LandAsset::LandUnit((0, 0), METAVERSE_ID)),
+			vec![
+				LandUnitOwnership {
+					coordinate: (1, 0),
+					owner: BENEFICIARY_ID,
+					estate_id: Some(0),
+				},
+				LandUnitOwnership {
+					coordinate: (1, 1),
+					owner: BENEFICIARY_ID,
+					estate_id: Some(0),
+				},
+			]
+		);
+		assert_eq!(
+			EstateModule::neighbours(LandAsset::Estate(0)),
+			vec![LandUnitOwnership {
+				coordinate: (0, 0),
+				owner: BOB,
+				estate_id: None,
+			}]
+		);
+		assert_eq!(EstateModule::neighbours(LandAsset::Estate(1)), vec![]);
+	});
+}
+
+#[test]
+fn split_undeployed_land_block_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EstateModule::issue_undeployed_land_blocks(
+			Origin::root(),
+			BOB,
+			1,
+			20,
+			UndeployedLandBlockType::BoundToAddress
+		));
+		let undeployed_land_block_id: UndeployedLandBlockId = 0;
+
+		assert_ok!(EstateModule::split_undeployed_land_block(
+			Origin::signed(BOB),
+			undeployed_land_block_id,
+			vec![5, 10]
+		));
+		assert_eq!(
+			last_event(),
+			Event::Estate(crate::Event::UndeployedLandBlockSplit(
+				BOB,
+				undeployed_land_block_id,
+				vec![1, 2]
+			))
+		);
+
+		assert_eq!(
+			EstateModule::get_undeployed_land_block(undeployed_land_block_id)
+				.unwrap()
+				.number_land_units,
+			5
+		);
+		let new_undeployed_land_block = EstateModule::get_undeployed_land_block(2).unwrap();
+		assert_eq!(new_undeployed_land_block.number_land_units, 10);
+		assert_eq!(new_undeployed_land_block.owner, BOB);
+		assert_eq!(
+			new_undeployed_land_block.undeployed_land_block_type,
+			UndeployedLandBlockType::BoundToAddress
+		);
+		assert_eq!(EstateModule::get_undeployed_land_block_owner(BOB, 1), Some(()));
+		assert_eq!(EstateModule::get_undeployed_land_block_owner(BOB, 2), Some(()));
+		assert_eq!(EstateModule::all_undeployed_land_unit(), 20);
+
+		// Split land blocks stay bound to the owner
+		assert_noop!(
+			EstateModule::transfer_undeployed_land_blocks(Origin::signed(BOB), ALICE, 2),
+			Error::<Runtime>::UndeployedLandBlockIsNotTransferable
+		);
+	});
+}
+
+#[test]
+fn split_undeployed_land_block_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EstateModule::issue_undeployed_land_blocks(
+			Origin::root(),
+			BOB,
+			1,
+			20,
+			UndeployedLandBlockType::Transferable
+		));
+		let undeployed_land_block_id: UndeployedLandBlockId = 0;
+
+		assert_noop!(
+			EstateModule::split_undeployed_land_block(Origin::signed(ALICE), undeployed_land_block_id, vec![5]),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			EstateModule::split_undeployed_land_block(Origin::signed(BOB), undeployed_land_block_id, vec![]),
+			Error::<Runtime>::InvalidUndeployedLandBlockSplit
+		);
+		assert_noop!(
+			EstateModule::split_undeployed_land_block(Origin::signed(BOB), undeployed_land_block_id, vec![0]),
+			Error::<Runtime>::InvalidUndeployedLandBlockSplit
+		);
+		assert_noop!(
+			EstateModule::split_undeployed_land_block(Origin::signed(BOB), undeployed_land_block_id, vec![10, 10]),
+			Error::<Runtime>::InvalidUndeployedLandBlockSplit
+		);
+		assert_noop!(
+			EstateModule::split_undeployed_land_block(Origin::signed(BOB), undeployed_land_block_id, vec![15, 10]),
+			Error::<Runtime>::UndeployedLandBlockDoesNotHaveEnoughLandUnits
+		);
+
+		assert_ok!(EstateModule::approve_undeployed_land_blocks(
+			Origin::signed(BOB),
+			ALICE,
+			undeployed_land_block_id
+		));
+		assert_noop!(
+			EstateModule::split_undeployed_land_block(Origin::signed(BOB), undeployed_land_block_id, vec![5]),
+			Error::<Runtime>::UndeployedLandBlockIsApproved
+		);
+		assert_ok!(EstateModule::unapprove_undeployed_land_blocks(
+			Origin::signed(BOB),
+			undeployed_land_block_id
+		));
+
+		assert_ok!(EstateModule::freeze_undeployed_land_blocks(
+			Origin::root(),
+			undeployed_land_block_id
+		));
+		assert_noop!(
+			EstateModule::split_undeployed_land_block(Origin::signed(BOB), undeployed_land_block_id, vec![5]),
+			Error::<Runtime>::UndeployedLandBlockFreezed
+		);
+	});
+}
+
+#[test]
+fn merge_undeployed_land_blocks_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EstateModule::issue_undeployed_land_blocks(
+			Origin::root(),
+			BOB,
+			3,
+			20,
+			UndeployedLandBlockType::Transferable
+		));
+
+		assert_ok!(EstateModule::merge_undeployed_land_blocks(
+			Origin::signed(BOB),
+			0,
+			vec![1, 2]
+		));
+		assert_eq!(
+			last_event(),
+			Event::Estate(crate::Event::UndeployedLandBlocksMerged(BOB, 0, vec![1, 2]))
+		);
+
+		assert_eq!(
+			EstateModule::get_undeployed_land_block(0).unwrap().number_land_units,
+			60
+		);
+		assert_eq!(EstateModule::get_undeployed_land_block(1), None);
+		assert_eq!(EstateModule::get_undeployed_land_block(2), None);
+		assert_eq!(EstateModule::get_undeployed_land_block_owner(BOB, 1), None);
+		assert_eq!(EstateModule::get_undeployed_land_block_owner(BOB, 2), None);
+		assert_eq!(EstateModule::all_undeployed_land_unit(), 60);
+	});
+}
+
+#[test]
+fn merge_undeployed_land_blocks_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EstateModule::issue_undeployed_land_blocks(
+			Origin::root(),
+			BOB,
+			2,
+			20,
+			UndeployedLandBlockType::Transferable
+		));
+		assert_ok!(EstateModule::issue_undeployed_land_blocks(
+			Origin::root(),
+			BOB,
+			1,
+			20,
+			UndeployedLandBlockType::BoundToAddress
+		));
+		assert_ok!(EstateModule::issue_undeployed_land_blocks(
+			Origin::root(),
+			ALICE,
+			1,
+			20,
+			UndeployedLandBlockType::Transferable
+		));
+
+		assert_noop!(
+			EstateModule::merge_undeployed_land_blocks(Origin::signed(BOB), 0, vec![]),
+			Error::<Runtime>::InvalidUndeployedLandBlockMerge
+		);
+		assert_noop!(
+			EstateModule::merge_undeployed_land_blocks(Origin::signed(BOB), 0, vec![0]),
+			Error::<Runtime>::InvalidUndeployedLandBlockMerge
+		);
+		assert_noop!(
+			EstateModule::merge_undeployed_land_blocks(Origin::signed(BOB), 0, vec![1, 1]),
+			Error::<Runtime>::UndeployedLandBlockNotFound
+		);
+		assert_noop!(
+			EstateModule::merge_undeployed_land_blocks(Origin::signed(BOB), 0, vec![2]),
+			Error::<Runtime>::UndeployedLandBlockTypeMismatch
+		);
+		assert_noop!(
+			EstateModule::merge_undeployed_land_blocks(Origin::signed(BOB), 0, vec![3]),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(EstateModule::approve_undeployed_land_blocks(
+			Origin::signed(BOB),
+			ALICE,
+			1
+		));
+		assert_noop!(
+			EstateModule::merge_undeployed_land_blocks(Origin::signed(BOB), 0, vec![1]),
+			Error::<Runtime>::UndeployedLandBlockIsApproved
+		);
+	});
+}
+
+const TAXED_LAND_UNIT: LandAsset = LandAsset::LandUnit((0, 0), ALICE_METAVERSE_ID);
+const LAND_TAX_PERIOD: BlockNumber = 10;
+
+fn setup_land_tax() {
+	assert_ok!(EstateModule::set_max_bounds(
+		Origin::root(),
+		ALICE_METAVERSE_ID,
+		MAX_BOUND
+	));
+	assert_ok!(EstateModule::mint_lands(
+		Origin::root(),
+		BOB,
+		ALICE_METAVERSE_ID,
+		vec![(0, 0)]
+	));
+	assert_ok!(EstateModule::set_land_tax_config(
+		Origin::signed(ALICE),
+		ALICE_METAVERSE_ID,
+		Some(LandTaxConfig {
+			tax_rate: sp_runtime::Perbill::from_percent(10),
+			tax_period: LAND_TAX_PERIOD,
+		})
+	));
+}
+
+#[test]
+fn set_land_tax_config_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let land_tax_config = LandTaxConfig {
+			tax_rate: sp_runtime::Perbill::from_percent(10),
+			tax_period: LAND_TAX_PERIOD,
+		};
+
+		assert_noop!(
+			EstateModule::set_land_tax_config(Origin::signed(BOB), ALICE_METAVERSE_ID, Some(land_tax_config)),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			EstateModule::set_land_tax_config(
+				Origin::signed(ALICE),
+				ALICE_METAVERSE_ID,
+				Some(LandTaxConfig {
+					tax_rate: sp_runtime::Perbill::from_percent(10),
+					tax_period: 0,
+				})
+			),
+			Error::<Runtime>::InvalidLandTaxConfig
+		);
+
+		assert_ok!(EstateModule::set_land_tax_config(
+			Origin::signed(ALICE),
+			ALICE_METAVERSE_ID,
+			Some(land_tax_config)
+		));
+		assert_eq!(
+			last_event(),
+			Event::Estate(crate::Event::LandTaxConfigSet(
+				ALICE_METAVERSE_ID,
+				Some(land_tax_config)
+			))
+		);
+		assert_eq!(
+			EstateModule::get_land_tax_config(ALICE_METAVERSE_ID),
+			Some(land_tax_config)
+		);
+
+		assert_ok!(EstateModule::set_land_tax_config(
+			Origin::signed(ALICE),
+			ALICE_METAVERSE_ID,
+			None
+		));
+		assert_eq!(EstateModule::get_land_tax_config(ALICE_METAVERSE_ID), None);
+	});
+}
+
+#[test]
+fn self_assess_and_pay_land_tax_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_land_tax();
+		let metaverse_fund_account = EstateModule::metaverse_fund_account(ALICE_METAVERSE_ID);
+
+		assert_noop!(
+			EstateModule::self_assess(Origin::signed(ALICE), TAXED_LAND_UNIT, 1000),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			EstateModule::self_assess(Origin::signed(BOB), TAXED_LAND_UNIT, 0),
+			Error::<Runtime>::InvalidLandPrice
+		);
+
+		// Minted land is not assessed until the owner declares a price
+		assert_eq!(EstateModule::get_self_assessment(TAXED_LAND_UNIT), None);
+
+		// Declaring a price does not pay tax
+		assert_ok!(EstateModule::self_assess(Origin::signed(BOB), TAXED_LAND_UNIT, 1000));
+		assert_eq!(
+			last_event(),
+			Event::Estate(crate::Event::LandSelfAssessed(TAXED_LAND_UNIT, BOB, 1000))
+		);
+		assert_eq!(Balances::free_balance(metaverse_fund_account), 0);
+
+		// The first payment is on the terms in force when the land tax was enabled
+		assert_ok!(EstateModule::pay_land_tax(Origin::signed(BOB), TAXED_LAND_UNIT));
+		assert_eq!(
+			last_event(),
+			Event::Estate(crate::Event::LandTaxPaid(TAXED_LAND_UNIT, BOB, 100, 11))
+		);
+		assert_eq!(
+			EstateModule::get_self_assessment(TAXED_LAND_UNIT),
+			Some(SelfAssessment {
+				owner: BOB,
+				price: 1000,
+				paid_until: 11,
+				tax_rate: sp_runtime::Perbill::from_percent(10),
+				tax_period: LAND_TAX_PERIOD,
+			})
+		);
+		assert_eq!(Balances::free_balance(BOB), 99900);
+		assert_eq!(Balances::free_balance(metaverse_fund_account), 100);
+
+		assert_noop!(
+			EstateModule::pay_land_tax(Origin::signed(BOB), TAXED_LAND_UNIT),
+			Error::<Runtime>::LandTaxAlreadyPaid
+		);
+
+		run_to_block(11);
+		assert_ok!(EstateModule::pay_land_tax(Origin::signed(BOB), TAXED_LAND_UNIT));
+		assert_eq!(
+			last_event(),
+			Event::Estate(crate::Event::LandTaxPaid(TAXED_LAND_UNIT, BOB, 100, 21))
+		);
+		assert_eq!(Balances::free_balance(metaverse_fund_account), 200);
+
+		// Changing the price does not charge tax again
+		assert_ok!(EstateModule::self_assess(Origin::signed(BOB), TAXED_LAND_UNIT, 2000));
+		assert_eq!(
+			EstateModule::get_self_assessment(TAXED_LAND_UNIT),
+			Some(SelfAssessment {
+				owner: BOB,
+				price: 2000,
+				paid_until: 21,
+				tax_rate: sp_runtime::Perbill::from_percent(10),
+				tax_period: LAND_TAX_PERIOD,
+			})
+		);
+		assert_eq!(Balances::free_balance(metaverse_fund_account), 200);
+
+		// A new tax rate applies from the period after the next one
+		assert_ok!(EstateModule::set_land_tax_config(
+			Origin::signed(ALICE),
+			ALICE_METAVERSE_ID,
+			Some(LandTaxConfig {
+				tax_rate: sp_runtime::Perbill::from_percent(20),
+				tax_period: LAND_TAX_PERIOD,
+			})
+		));
+		run_to_block(21);
+		assert_ok!(EstateModule::pay_land_tax(Origin::signed(BOB), TAXED_LAND_UNIT));
+		assert_eq!(
+			last_event(),
+			Event::Estate(crate::Event::LandTaxPaid(TAXED_LAND_UNIT, BOB, 200, 31))
+		);
+		run_to_block(31);
+		assert_ok!(EstateModule::pay_land_tax(Origin::signed(BOB), TAXED_LAND_UNIT));
+		assert_eq!(
+			last_event(),
+			Event::Estate(crate::Event::LandTaxPaid(TAXED_LAND_UNIT, BOB, 400, 41))
+		);
+
+		// Metaverses without land tax do not take assessments
+		assert_ok!(EstateModule::set_max_bounds(Origin::root(), METAVERSE_ID, MAX_BOUND));
+		assert_ok!(EstateModule::mint_lands(
+			Origin::root(),
+			BOB,
+			METAVERSE_ID,
+			vec![(0, 0)]
+		));
+		assert_noop!(
+			EstateModule::self_assess(Origin::signed(BOB), LandAsset::LandUnit((0, 0), METAVERSE_ID), 1000),
+			Error::<Runtime>::LandTaxNotEnabled
+		);
+	});
+}
+
+#[test]
+fn force_buy_land_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_land_tax();
+
+		// Land is not on sale before the owner declares a price
+		assert_noop!(
+			EstateModule::force_buy_land(Origin::signed(ALICE), TAXED_LAND_UNIT, 1000),
+			Error::<Runtime>::SelfAssessmentNotFound
+		);
+
+		assert_ok!(EstateModule::self_assess(Origin::signed(BOB), TAXED_LAND_UNIT, 1000));
+
+		assert_noop!(
+			EstateModule::force_buy_land(Origin::signed(ALICE), TAXED_LAND_UNIT, 999),
+			Error::<Runtime>::LandPriceAboveLimit
+		);
+
+		assert_ok!(EstateModule::force_buy_land(
+			Origin::signed(ALICE),
+			TAXED_LAND_UNIT,
+			1000
+		));
+		assert_eq!(
+			last_event(),
+			Event::Estate(crate::Event::LandForceBought(TAXED_LAND_UNIT, BOB, ALICE, 1000))
+		);
+		assert_eq!(EstateModule::get_land_units(ALICE_METAVERSE_ID, (0, 0)), Some(ALICE));
+		assert_eq!(Balances::free_balance(ALICE), 99000);
+		assert_eq!(Balances::free_balance(BOB), 100900);
+
+		// The assessment and its tax duty stay with the land unit
+		assert_eq!(
+			EstateModule::get_self_assessment(TAXED_LAND_UNIT),
+			Some(SelfAssessment {
+				owner: ALICE,
+				price: 1000,
+				paid_until: 1,
+				tax_rate: sp_runtime::Perbill::from_percent(10),
+				tax_period: LAND_TAX_PERIOD,
+			})
+		);
+
+		assert_ok!(EstateModule::transfer_land(
+			Origin::signed(ALICE),
+			BOB,
+			ALICE_METAVERSE_ID,
+			(0, 0)
+		));
+		assert_eq!(EstateModule::get_self_assessment(TAXED_LAND_UNIT).unwrap().owner, BOB);
+	});
+}
+
+#[test]
+fn reclaim_land_should_work_after_grace_period() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_land_tax();
+		let metaverse_fund_account = EstateModule::metaverse_fund_account(ALICE_METAVERSE_ID);
+
+		assert_ok!(EstateModule::self_assess(Origin::signed(BOB), TAXED_LAND_UNIT, 1000));
+		assert_ok!(EstateModule::pay_land_tax(Origin::signed(BOB), TAXED_LAND_UNIT));
+
+		// Tax is paid until block 11 and the grace period runs until block 16
+		run_to_block(16);
+		assert_noop!(
+			EstateModule::reclaim_land(Origin::signed(ALICE), TAXED_LAND_UNIT),
+			Error::<Runtime>::LandTaxNotOverdue
+		);
+
+		run_to_block(17);
+		assert_noop!(
+			EstateModule::self_assess(Origin::signed(BOB), TAXED_LAND_UNIT, 500),
+			Error::<Runtime>::LandTaxOverdue
+		);
+
+		assert_ok!(EstateModule::reclaim_land(Origin::signed(ALICE), TAXED_LAND_UNIT));
+		assert_eq!(
+			last_event(),
+			Event::Estate(crate::Event::LandReclaimed(
+				TAXED_LAND_UNIT,
+				BOB,
+				metaverse_fund_account
+			))
+		);
+		assert_eq!(
+			EstateModule::get_land_units(ALICE_METAVERSE_ID, (0, 0)),
+			Some(metaverse_fund_account)
+		);
+		// The metaverse fund keeps the land on sale at the declared price
+		assert_eq!(
+			EstateModule::get_self_assessment(TAXED_LAND_UNIT),
+			Some(SelfAssessment {
+				owner: metaverse_fund_account,
+				price: 1000,
+				paid_until: 17,
+				tax_rate: sp_runtime::Perbill::from_percent(10),
+				tax_period: LAND_TAX_PERIOD,
+			})
+		);
+	});
+}
+
+#[test]
+fn land_tax_should_not_fall_due_while_disabled() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_land_tax();
+
+		assert_ok!(EstateModule::self_assess(Origin::signed(BOB), TAXED_LAND_UNIT, 1000));
+		assert_ok!(EstateModule::pay_land_tax(Origin::signed(BOB), TAXED_LAND_UNIT));
+
+		assert_ok!(EstateModule::set_land_tax_config(
+			Origin::signed(ALICE),
+			ALICE_METAVERSE_ID,
+			None
+		));
+		run_to_block(30);
+		assert_ok!(EstateModule::set_land_tax_config(
+			Origin::signed(ALICE),
+			ALICE_METAVERSE_ID,
+			Some(LandTaxConfig {
+				tax_rate: sp_runtime::Perbill::from_percent(10),
+				tax_period: LAND_TAX_PERIOD,
+			})
+		));
+		assert_eq!(EstateModule::land_tax_enabled_since(ALICE_METAVERSE_ID), 30);
+
+		// The owner gets the grace period again once the land tax is enabled
+		run_to_block(35);
+		assert_noop!(
+			EstateModule::reclaim_land(Origin::signed(ALICE), TAXED_LAND_UNIT),
+			Error::<Runtime>::LandTaxNotOverdue
+		);
+
+		// Tax starts over from when the land tax was enabled
+		assert_ok!(EstateModule::pay_land_tax(Origin::signed(BOB), TAXED_LAND_UNIT));
+		assert_eq!(
+			last_event(),
+			Event::Estate(crate::Event::LandTaxPaid(TAXED_LAND_UNIT, BOB, 100, 40))
+		);
+	});
+}
+
+#[test]
+fn estate_reshapes_should_carry_self_assessments() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_land_tax();
+		let first_land_unit = TAXED_LAND_UNIT;
+		let second_land_unit = LandAsset::LandUnit((0, 1), ALICE_METAVERSE_ID);
+		let estate = LandAsset::Estate(0);
+		let assessed_price =
+			|asset: LandAsset| EstateModule::get_self_assessment(asset).map(|assessment| assessment.price);
+
+		assert_ok!(EstateModule::mint_lands(
+			Origin::root(),
+			BOB,
+			ALICE_METAVERSE_ID,
+			vec![(0, 1)]
+		));
+		assert_ok!(EstateModule::self_assess(Origin::signed(BOB), first_land_unit, 1000));
+		assert_ok!(EstateModule::self_assess(Origin::signed(BOB), second_land_unit, 3000));
+
+		// An estate is assessed at the sum of its land units
+		assert_ok!(EstateModule::create_estate(
+			Origin::root(),
+			BOB,
+			ALICE_METAVERSE_ID,
+			vec![(0, 0), (0, 1)]
+		));
+		assert_eq!(assessed_price(estate), Some(4000));
+		assert_eq!(assessed_price(first_land_unit), None);
+		assert_eq!(assessed_price(second_land_unit), None);
+
+		// Land split off takes its share by land unit count
+		assert_ok!(EstateModule::split_estate(Origin::signed(BOB), 0, vec![vec![(0, 1)]]));
+		assert_eq!(assessed_price(estate), Some(2000));
+		assert_eq!(assessed_price(LandAsset::Estate(1)), Some(2000));
+
+		assert_ok!(EstateModule::merge_estates(Origin::signed(BOB), 0, vec![1]));
+		assert_eq!(assessed_price(estate), Some(4000));
+		assert_eq!(assessed_price(LandAsset::Estate(1)), None);
+
+		assert_ok!(EstateModule::remove_land_unit_from_estate(
+			Origin::signed(BOB),
+			0,
+			vec![(0, 1)]
+		));
+		assert_eq!(assessed_price(estate), Some(2000));
+		assert_eq!(assessed_price(second_land_unit), Some(2000));
+
+		assert_ok!(EstateModule::add_land_unit_to_estate(
+			Origin::signed(BOB),
+			0,
+			vec![(0, 1)]
+		));
+		assert_eq!(assessed_price(estate), Some(4000));
+		assert_eq!(assessed_price(second_land_unit), None);
+
+		assert_ok!(EstateModule::dissolve_estate(Origin::signed(BOB), 0));
+		assert_eq!(assessed_price(estate), None);
+		assert_eq!(assessed_price(first_land_unit), Some(2000));
+		assert_eq!(assessed_price(second_land_unit), Some(2000));
+
+		// Minted land is not assessed
+		assert_ok!(EstateModule::mint_estate(
+			Origin::root(),
+			BOB,
+			ALICE_METAVERSE_ID,
+			vec![(5, 5), (5, 6)]
+		));
+		assert_eq!(assessed_price(LandAsset::Estate(2)), None);
+
+		// An estate is not assessed while any of its land is not
+		assert_ok!(EstateModule::mint_lands(
+			Origin::root(),
+			BOB,
+			ALICE_METAVERSE_ID,
+			vec![(0, 2)]
+		));
+		assert_ok!(EstateModule::create_estate(
+			Origin::root(),
+			BOB,
+			ALICE_METAVERSE_ID,
+			vec![(0, 1), (0, 2)]
+		));
+		assert_eq!(assessed_price(LandAsset::Estate(3)), None);
+		assert_eq!(assessed_price(second_land_unit), None);
+		assert_ok!(EstateModule::add_land_unit_to_estate(
+			Origin::signed(BOB),
+			3,
+			vec![(0, 0)]
+		));
+		assert_eq!(assessed_price(LandAsset::Estate(3)), None);
+		assert_eq!(assessed_price(first_land_unit), None);
+	});
+}
+
+#[test]
+fn request_loan_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		let asset = setup_leasable_estate();
+		let native = FungibleTokenId::NativeToken(0);
+
+		assert_noop!(
+			EstateModule::request_loan(Origin::signed(ALICE), asset, BOB, native, 1000, 1100, 10),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			EstateModule::request_loan(Origin::signed(BENEFICIARY_ID), asset, BOB, native, 0, 1100, 10),
+			Error::<Runtime>::InvalidLoanTerms
+		);
+		assert_noop!(
+			EstateModule::request_loan(Origin::signed(BENEFICIARY_ID), asset, BOB, native, 1200, 1100, 10),
+			Error::<Runtime>::InvalidLoanTerms
+		);
+		assert_noop!(
+			EstateModule::request_loan(Origin::signed(BENEFICIARY_ID), asset, BOB, native, 1000, 1100, 0),
+			Error::<Runtime>::InvalidLoanTerms
+		);
+
+		assert_ok!(EstateModule::request_loan(
+			Origin::signed(BENEFICIARY_ID),
+			asset,
+			BOB,
+			native,
+			1000,
+			1100,
+			10
+		));
+		assert_eq!(
+			last_event(),
+			Event::Estate(crate::Event::LoanRequested(asset, BENEFICIARY_ID, BOB, 1000))
+		);
+		assert_noop!(
+			EstateModule::request_loan(Origin::signed(BENEFICIARY_ID), asset, BOB, native, 1000, 1100, 10),
+			Error::<Runtime>::LoanRequestAlreadyExists
+		);
+
+		assert_noop!(
+			EstateModule::fund_loan(Origin::signed(ALICE), asset),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(EstateModule::cancel_loan_request(Origin::signed(BOB), asset));
+		assert_eq!(EstateModule::loan_requests(asset), None);
+	});
+}
+
+#[test]
+fn fund_and_repay_loan_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let asset = setup_leasable_estate();
+
+		assert_ok!(EstateModule::request_loan(
+			Origin::signed(BENEFICIARY_ID),
+			asset,
+			BOB,
+			FungibleTokenId::NativeToken(0),
+			1000,
+			1100,
+			10
+		));
+		assert_ok!(EstateModule::fund_loan(Origin::signed(BOB), asset));
+		assert_eq!(
+			last_event(),
+			Event::Estate(crate::Event::LoanFunded(asset, BENEFICIARY_ID, BOB, 11))
+		);
+		assert_eq!(EstateModule::loan_requests(asset), None);
+		assert_eq!(Balances::free_balance(BOB), 99000);
+		assert_eq!(Balances::free_balance(BENEFICIARY_ID), 1001000);
+		assert_eq!(EstateModule::is_estate_collateralized(0), Ok(true));
+
+		// Collateral is locked until the loan is settled
+		assert_noop!(
+			EstateModule::transfer_estate(Origin::signed(BENEFICIARY_ID), ALICE, 0),
+			Error::<Runtime>::EstateIsCollateralized
+		);
+		assert_noop!(
+			EstateModule::dissolve_estate(Origin::signed(BENEFICIARY_ID), 0),
+			Error::<Runtime>::EstateIsCollateralized
+		);
+		assert_noop!(
+			EstateModule::offer_lease(
+				Origin::signed(BENEFICIARY_ID),
+				asset,
+				ALICE,
+				FungibleTokenId::NativeToken(0),
+				LEASE_RENT,
+				LEASE_PERIOD,
+				3
+			),
+			Error::<Runtime>::EstateIsCollateralized
+		);
+		assert_noop!(
+			EstateModule::claim_collateral(Origin::signed(BOB), asset),
+			Error::<Runtime>::LoanNotDue
+		);
+
+		assert_noop!(
+			EstateModule::repay_loan(Origin::signed(BOB), asset),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(EstateModule::repay_loan(Origin::signed(BENEFICIARY_ID), asset));
+		assert_eq!(
+			last_event(),
+			Event::Estate(crate::Event::LoanRepaid(asset, BENEFICIARY_ID, BOB, 1100))
+		);
+		assert_eq!(Balances::free_balance(BOB), 100100);
+		assert_eq!(Balances::free_balance(BENEFICIARY_ID), 999900);
+		assert_eq!(EstateModule::loans(asset), None);
+
+		assert_ok!(EstateModule::transfer_estate(Origin::signed(BENEFICIARY_ID), ALICE, 0));
+	});
+}
+
+#[test]
+fn claim_collateral_should_work_after_due_block() {
+	ExtBuilder::default().build().execute_with(|| {
+		let asset = setup_leasable_estate();
+
+		assert_ok!(EstateModule::request_loan(
+			Origin::signed(BENEFICIARY_ID),
+			asset,
+			BOB,
+			SOCIAL_TOKEN,
+			500,
+			600,
+			10
+		));
+		assert_ok!(EstateModule::fund_loan(Origin::signed(BOB), asset));
+		assert_eq!(Tokens::accounts(BENEFICIARY_ID, SOCIAL_TOKEN).free, 500);
+
+		run_to_block(11);
+		assert_noop!(
+			EstateModule::claim_collateral(Origin::signed(BOB), asset),
+			Error::<Runtime>::LoanNotDue
+		);
+
+		run_to_block(12);
+		assert_noop!(
+			EstateModule::claim_collateral(Origin::signed(ALICE), asset),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(EstateModule::claim_collateral(Origin::signed(BOB), asset));
+		assert_eq!(
+			last_event(),
+			Event::Estate(crate::Event::CollateralClaimed(asset, BENEFICIARY_ID, BOB))
+		);
+		assert_eq!(EstateModule::get_estate_owner(BOB, 0), Some(()));
+		assert_eq!(EstateModule::get_estate_owner(BENEFICIARY_ID, 0), None);
+		assert_eq!(EstateModule::loans(asset), None);
+	});
+}
+
+fn land_sale(
+	max_units_per_account: Option<u32>,
+	whitelist_until: Option<BlockNumber>,
+) -> LandSale<Balance, BlockNumber> {
+	LandSale {
+		bottom_left: (0, 0),
+		top_right: (9, 9),
+		unit_price: 100,
+		zones: vec![LandSaleZone {
+			bottom_left: (0, 0),
+			top_right: (1, 1),
+			unit_price: 300,
+		}],
+		max_units_per_account,
+		whitelist_until,
+	}
+}
+
+#[test]
+fn open_land_sale_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			EstateModule::open_land_sale(Origin::signed(BOB), ALICE_METAVERSE_ID, land_sale(None, None)),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			EstateModule::open_land_sale(Origin::signed(ALICE), ALICE_METAVERSE_ID, land_sale(None, None)),
+			Error::<Runtime>::NoMaxBoundSet
+		);
+
+		assert_ok!(EstateModule::set_max_bounds(Origin::root(), ALICE_METAVERSE_ID, (0, 5)));
+		assert_noop!(
+			EstateModule::open_land_sale(Origin::signed(ALICE), ALICE_METAVERSE_ID, land_sale(None, None)),
+			Error::<Runtime>::InvalidLandSale
+		);
+
+		assert_ok!(EstateModule::set_max_bounds(
+			Origin::root(),
+			ALICE_METAVERSE_ID,
+			MAX_BOUND
+		));
+		let mut sale = land_sale(None, None);
+		sale.zones[0].top_right = (10, 10);
+		assert_noop!(
+			EstateModule::open_land_sale(Origin::signed(ALICE), ALICE_METAVERSE_ID, sale.clone()),
+			Error::<Runtime>::InvalidLandSale
+		);
+		sale.zones = vec![sale.zones[0].clone(); 3];
+		assert_noop!(
+			EstateModule::open_land_sale(Origin::signed(ALICE), ALICE_METAVERSE_ID, sale),
+			Error::<Runtime>::InvalidLandSale
+		);
+
+		assert_ok!(EstateModule::open_land_sale(
+			Origin::signed(ALICE),
+			ALICE_METAVERSE_ID,
+			land_sale(None, None)
+		));
+		assert_eq!(
+			last_event(),
+			Event::Estate(crate::Event::LandSaleOpened(ALICE_METAVERSE_ID))
+		);
+		assert_noop!(
+			EstateModule::open_land_sale(Origin::signed(ALICE), ALICE_METAVERSE_ID, land_sale(None, None)),
+			Error::<Runtime>::LandSaleAlreadyOpen
+		);
+	});
+}
+
+#[test]
+fn buy_land_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EstateModule::set_max_bounds(
+			Origin::root(),
+			ALICE_METAVERSE_ID,
+			MAX_BOUND
+		));
+		assert_ok!(EstateModule::open_land_sale(
+			Origin::signed(ALICE),
+			ALICE_METAVERSE_ID,
+			land_sale(Some(3), None)
+		));
+		let metaverse_fund_account = EstateModule::metaverse_fund_account(ALICE_METAVERSE_ID);
+
+		// Zone price applies inside the zone, the unit price elsewhere
+		assert_ok!(EstateModule::buy_land(
+			Origin::signed(BOB),
+			ALICE_METAVERSE_ID,
+			vec![(0, 0), (5, 5)]
+		));
+		assert_eq!(
+			last_event(),
+			Event::Estate(crate::Event::LandBought(
+				BOB,
+				ALICE_METAVERSE_ID,
+				vec![(0, 0), (5, 5)],
+				400
+			))
+		);
+		assert_eq!(EstateModule::get_land_units(ALICE_METAVERSE_ID, (0, 0)), Some(BOB));
+		assert_eq!(EstateModule::get_land_units(ALICE_METAVERSE_ID, (5, 5)), Some(BOB));
+		assert_eq!(EstateModule::all_land_units_count(), 2);
+		assert_eq!(Balances::free_balance(BOB), 99600);
+		assert_eq!(Balances::free_balance(metaverse_fund_account), 400);
+		assert_eq!(EstateModule::land_sale_purchases(ALICE_METAVERSE_ID, BOB), 2);
+
+		assert_noop!(
+			EstateModule::buy_land(Origin::signed(BOB), ALICE_METAVERSE_ID, vec![(10, 10)]),
+			Error::<Runtime>::LandUnitNotForSale
+		);
+		assert_noop!(
+			EstateModule::buy_land(Origin::signed(BOB), ALICE_METAVERSE_ID, vec![(6, 6), (7, 7)]),
+			Error::<Runtime>::LandSalePurchaseLimitExceeded
+		);
+		assert_noop!(
+			EstateModule::buy_land(Origin::signed(BENEFICIARY_ID), ALICE_METAVERSE_ID, vec![(0, 0)]),
+			Error::<Runtime>::LandUnitIsNotAvailable
+		);
+		assert_noop!(
+			EstateModule::buy_land(Origin::signed(BOB), BOB_METAVERSE_ID, vec![(0, 0)]),
+			Error::<Runtime>::LandSaleNotFound
+		);
+	});
+}
+
+#[test]
+fn buy_land_should_respect_whitelist_phase() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EstateModule::set_max_bounds(
+			Origin::root(),
+			ALICE_METAVERSE_ID,
+			MAX_BOUND
+		));
+		assert_ok!(EstateModule::open_land_sale(
+			Origin::signed(ALICE),
+			ALICE_METAVERSE_ID,
+			land_sale(None, Some(10))
+		));
+
+		assert_noop!(
+			EstateModule::buy_land(Origin::signed(BOB), ALICE_METAVERSE_ID, vec![(5, 5)]),
+			Error::<Runtime>::NotWhitelistedForLandSale
+		);
+
+		assert_noop!(
+			EstateModule::update_land_sale_whitelist(Origin::signed(BOB), ALICE_METAVERSE_ID, vec![BOB], true),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(EstateModule::update_land_sale_whitelist(
+			Origin::signed(ALICE),
+			ALICE_METAVERSE_ID,
+			vec![BOB],
+			true
+		));
+		assert_ok!(EstateModule::buy_land(
+			Origin::signed(BOB),
+			ALICE_METAVERSE_ID,
+			vec![(5, 5)]
+		));
+		assert_noop!(
+			EstateModule::buy_land(Origin::signed(BENEFICIARY_ID), ALICE_METAVERSE_ID, vec![(6, 6)]),
+			Error::<Runtime>::NotWhitelistedForLandSale
+		);
+
+		// Sale opens to everyone after the whitelist phase
+		run_to_block(11);
+		assert_ok!(EstateModule::buy_land(
+			Origin::signed(BENEFICIARY_ID),
+			ALICE_METAVERSE_ID,
+			vec![(6, 6)]
+		));
+
+		assert_ok!(EstateModule::close_land_sale(Origin::signed(ALICE), ALICE_METAVERSE_ID));
+		assert_eq!(
+			last_event(),
+			Event::Estate(crate::Event::LandSaleClosed(ALICE_METAVERSE_ID))
+		);
+		assert_eq!(EstateModule::get_land_sale(ALICE_METAVERSE_ID), None);
+		assert_eq!(EstateModule::land_sale_whitelist(ALICE_METAVERSE_ID, BOB), None);
+		assert_eq!(EstateModule::land_sale_purchases(ALICE_METAVERSE_ID, BOB), 0);
+		assert_noop!(
+			EstateModule::buy_land(Origin::signed(BOB), ALICE_METAVERSE_ID, vec![(7, 7)]),
+			Error::<Runtime>::LandSaleNotFound
+		);
+	});
+}
+
+#[test]
+fn metaverse_builder_can_build_on_land_held_by_metaverse_owner() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EstateModule::set_max_bounds(
+			Origin::root(),
+			ALICE_METAVERSE_ID,
+			MAX_BOUND
+		));
+		assert_ok!(EstateModule::mint_estate(
+			Origin::root(),
+			ALICE,
+			ALICE_METAVERSE_ID,
+			vec![COORDINATE_IN_1, COORDINATE_IN_2]
+		));
+		assert_ok!(EstateModule::mint_land(Origin::root(), BOB, ALICE_METAVERSE_ID, (0, 0)));
+		let estate = LandAsset::Estate(0);
+		let bob_land_unit = LandAsset::LandUnit((0, 0), ALICE_METAVERSE_ID);
+
+		assert!(EstateModule::can_build(&BENEFICIARY_ID, &estate));
+		assert!(!EstateModule::can_build(&BENEFICIARY_ID, &bob_land_unit));
+		assert_ok!(EstateModule::set_parcel_content(
+			Origin::signed(BENEFICIARY_ID),
+			estate,
+			H256::repeat_byte(1),
+			b"ipfs://scene".to_vec()
+		));
+		assert_noop!(
+			EstateModule::set_parcel_content(
+				Origin::signed(BENEFICIARY_ID),
+				bob_land_unit,
+				H256::repeat_byte(1),
+				b"ipfs://scene".to_vec()
+			),
+			Error::<Runtime>::NoPermission
+		);
+	});
+}
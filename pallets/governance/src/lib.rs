@@ -35,7 +35,7 @@ use frame_support::{
 use sp_runtime::traits::{Dispatchable, Hash, Saturating, Zero};
 use sp_std::prelude::*;
 
-use metaverse_primitive::MetaverseTrait;
+use metaverse_primitive::{MetaverseRole, MetaverseTrait};
 pub use pallet::*;
 use primitives::{MetaverseId, ProposalId, ReferendumId};
 pub use types::*;
@@ -243,7 +243,7 @@ pub mod pallet {
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// Metaverse owner can update referendum parameters
+		/// Metaverse owner and admins can update referendum parameters
 		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
 		pub fn update_referendum_parameters(
 			origin: OriginFor<T>,
@@ -252,7 +252,7 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			let from = ensure_signed(origin)?;
 			ensure!(
-				T::MetaverseInfo::check_ownership(&from, &metaverse_id),
+				T::MetaverseInfo::check_role(&from, &metaverse_id, &MetaverseRole::Admin),
 				Error::<T>::AccountIsNotMetaverseOwner
 			);
 			<ReferendumParametersOf<T>>::remove(metaverse_id);
@@ -375,15 +375,22 @@ pub mod pallet {
 			}
 		}
 
-		/// Cancel proposal if you are the proposal owner, the proposal exist, and it has not
-		/// launched as a referendum yet
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		/// Cancel a proposal that has not launched as a referendum yet and slash its deposits.
+		/// Root and the moderators of the metaverse can cancel proposals.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 1))]
 		pub fn cancel_proposal(
 			origin: OriginFor<T>,
 			proposal: ProposalId,
 			metaverse_id: MetaverseId,
 		) -> DispatchResultWithPostInfo {
-			ensure_root(origin)?;
+			if let Ok(from) = ensure_signed(origin.clone()) {
+				ensure!(
+					T::MetaverseInfo::check_role(&from, &metaverse_id, &MetaverseRole::Moderator),
+					Error::<T>::InsufficientPrivileges
+				);
+			} else {
+				ensure_root(origin)?;
+			}
 			let proposal_info = Self::proposals(metaverse_id, proposal).ok_or(Error::<T>::ProposalDoesNotExist)?;
 			if let Some((depositors, deposit)) = <DepositOf<T>>::take(proposal) {
 				<Proposals<T>>::remove(metaverse_id, proposal);
@@ -14,8 +14,9 @@ use sp_runtime::{
 	traits::{AccountIdConversion, BlakeTwo256, Hash, IdentityLookup},
 };
 
-use metaverse_primitive::{MetaverseInfo as MetaversePrimitiveInfo, MetaverseLandTrait, MetaverseTrait};
-use primitives::{Amount, FungibleTokenId};
+use auction_manager::CheckAuctionItemHandler;
+use metaverse_primitive::{MetaverseInfo as MetaversePrimitiveInfo, MetaverseLandTrait, MetaverseRole, MetaverseTrait};
+use primitives::{Amount, FungibleTokenId, ItemId};
 
 use crate as governance;
 
@@ -129,6 +130,12 @@ impl MetaverseTrait<AccountId> for MetaverseInfo {
 		}
 	}
 
+	fn check_role(who: &AccountId, country_id: &CountryId, role: &MetaverseRole) -> bool {
+		// Bob is an admin of Alice's metaverse
+		Self::check_ownership(who, country_id)
+			|| (*who == BOB && *country_id == ALICE_COUNTRY_ID && *role == MetaverseRole::Admin)
+	}
+
 	fn get_metaverse(_metaverse_id: u64) -> Option<MetaversePrimitiveInfo<AccountId>> {
 		None
 	}
@@ -140,6 +147,10 @@ impl MetaverseTrait<AccountId> for MetaverseInfo {
 	fn update_metaverse_token(_metaverse_id: u64, _currency_id: FungibleTokenId) -> Result<(), DispatchError> {
 		Ok(())
 	}
+
+	fn transfer_metaverse(_from: &AccountId, _to: &AccountId, _metaverse_id: u64) -> Result<(), DispatchError> {
+		Ok(())
+	}
 }
 
 pub struct MetaverseLandInfo {}
@@ -180,6 +191,17 @@ parameter_types! {
 	pub const MaxTokenMetadata: u32 = 1024;
 	pub const MinContribution: Balance = 1;
 	pub const MaxNumberOfStakersPerMetaverse: u32 = 512;
+	pub const MaxRoleNameLength: u32 = 32;
+	pub const MaxRolesPerMetaverse: u32 = 256;
+	pub const MaxMetadataHistory: u32 = 5;
+}
+
+pub struct MockAuctionManager;
+
+impl CheckAuctionItemHandler for MockAuctionManager {
+	fn check_item_in_auction(_item_id: ItemId) -> bool {
+		false
+	}
 }
 
 impl pallet_metaverse::Config for Runtime {
@@ -193,6 +215,10 @@ impl pallet_metaverse::Config for Runtime {
 	type MetaverseRegistrationDeposit = MinContribution;
 	type MinStakingAmount = MinContribution;
 	type MaxNumberOfStakersPerMetaverse = MaxNumberOfStakersPerMetaverse;
+	type MaxRoleNameLength = MaxRoleNameLength;
+	type MaxRolesPerMetaverse = MaxRolesPerMetaverse;
+	type MaxMetadataHistory = MaxMetadataHistory;
+	type AuctionHandler = MockAuctionManager;
 	type WeightInfo = ();
 }
 
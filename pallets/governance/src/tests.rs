@@ -33,6 +33,21 @@ fn update_country_referendum_parameters_when_not_country_owner_does_not_work() {
 	});
 }
 
+#[test]
+fn update_country_referendum_parameters_by_metaverse_admin_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(GovernanceModule::update_referendum_parameters(
+			Origin::signed(BOB),
+			ALICE_COUNTRY_ID,
+			REFERENDUM_PARAMETERS
+		));
+		assert_eq!(
+			last_event(),
+			Event::Governance(crate::Event::ReferendumParametersUpdated(ALICE_COUNTRY_ID))
+		);
+	});
+}
+
 // Creating preimage tests
 #[test]
 fn create_new_preimage_work() {
@@ -290,6 +305,37 @@ fn create_new_proposal_when_queue_full_does_not_work() {
 // 	});
 // }
 
+#[test]
+fn cancel_proposal_by_metaverse_moderator_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let hash = set_freeze_metaverse_proposal_hash(1);
+		add_freeze_metaverse_preimage(hash);
+		assert_ok!(GovernanceModule::propose(
+			Origin::signed(ALICE),
+			BOB_COUNTRY_ID,
+			600,
+			hash.clone(),
+			PROPOSAL_DESCRIPTION.to_vec()
+		));
+		let proposal_id = GovernanceModule::next_proposal() - 1;
+
+		assert_noop!(
+			GovernanceModule::cancel_proposal(Origin::signed(ALICE), proposal_id, BOB_COUNTRY_ID),
+			Error::<Runtime>::InsufficientPrivileges
+		);
+		assert_ok!(GovernanceModule::cancel_proposal(
+			Origin::signed(BOB),
+			proposal_id,
+			BOB_COUNTRY_ID
+		));
+		assert_eq!(
+			last_event(),
+			Event::Governance(crate::Event::ProposalCancelled(BOB_COUNTRY_ID, proposal_id))
+		);
+		assert!(GovernanceModule::proposals(BOB_COUNTRY_ID, proposal_id).is_none());
+	});
+}
+
 // Fast track proposal tests
 #[test]
 fn fast_track_proposal_work() {
@@ -23,7 +23,7 @@ use frame_support::{
 	ensure,
 	pallet_prelude::*,
 	traits::{Currency, ExistenceRequirement, LockableCurrency, ReservableCurrency},
-	PalletId,
+	transactional, PalletId,
 };
 use frame_system::{ensure_signed, pallet_prelude::*};
 use orml_traits::MultiCurrency;
@@ -34,11 +34,12 @@ use sp_runtime::{
 };
 use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 
+use auction_manager::CheckAuctionItemHandler;
 use core_primitives::*;
 use core_primitives::{MetaverseInfo, MetaverseTrait};
 pub use pallet::*;
 use primitives::staking::MetaverseStakingTrait;
-use primitives::{FungibleTokenId, MetaverseId, RoundIndex};
+use primitives::{FungibleTokenId, ItemId, MetaverseId, RoundIndex};
 pub use weights::WeightInfo;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -120,6 +121,17 @@ pub mod pallet {
 		type MinStakingAmount: Get<BalanceOf<Self>>;
 		/// Maximum amount of stakers per metaverse
 		type MaxNumberOfStakersPerMetaverse: Get<u32>;
+		/// Maximum length of a custom role name
+		#[pallet::constant]
+		type MaxRoleNameLength: Get<u32>;
+		/// Maximum number of team members holding roles in a metaverse
+		#[pallet::constant]
+		type MaxRolesPerMetaverse: Get<u32>;
+		/// Number of previous metadata versions kept per metaverse
+		#[pallet::constant]
+		type MaxMetadataHistory: Get<u32>;
+		/// Auction handler to check whether a metaverse is listed
+		type AuctionHandler: CheckAuctionItemHandler;
 		/// Weight implementation for estate extrinsics
 		type WeightInfo: WeightInfo;
 	}
@@ -144,6 +156,23 @@ pub mod pallet {
 	#[pallet::getter(fn get_freezing_metaverse)]
 	pub(super) type FreezedMetaverses<T: Config> = StorageMap<_, Twox64Concat, MetaverseId, (), OptionQuery>;
 
+	/// Roles granted to the team of a metaverse
+	#[pallet::storage]
+	#[pallet::getter(fn get_metaverse_roles)]
+	pub type MetaverseRoles<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, MetaverseId, Twox64Concat, T::AccountId, Vec<MetaverseRole>, ValueQuery>;
+
+	/// Number of team members holding roles in a metaverse
+	#[pallet::storage]
+	#[pallet::getter(fn get_metaverse_role_members)]
+	pub type MetaverseRoleMembers<T: Config> = StorageMap<_, Twox64Concat, MetaverseId, u32, ValueQuery>;
+
+	/// Previous metadata of a metaverse with the block it was replaced at, oldest first
+	#[pallet::storage]
+	#[pallet::getter(fn get_metadata_history)]
+	pub type MetaverseMetadataHistory<T: Config> =
+		StorageMap<_, Twox64Concat, MetaverseId, Vec<(T::BlockNumber, MetaverseMetadata)>, ValueQuery>;
+
 	/// Metaverse staking related storage
 
 	/// Staking round info
@@ -194,6 +223,9 @@ pub mod pallet {
 		MetaverseStaked(T::AccountId, MetaverseId, BalanceOf<T>),
 		MetaverseUnstaked(T::AccountId, MetaverseId, BalanceOf<T>),
 		MetaverseStakingRewarded(T::AccountId, MetaverseId, RoundIndex, BalanceOf<T>),
+		MetaverseRoleGranted(MetaverseId, T::AccountId, MetaverseRole),
+		MetaverseRoleRevoked(MetaverseId, T::AccountId, MetaverseRole),
+		MetaverseMetadataUpdated(MetaverseId, MetaverseMetadata),
 	}
 
 	#[pallet::error]
@@ -232,6 +264,18 @@ pub mod pallet {
 		MetaverseStakingAlreadyPaid,
 		/// Metaverse has no stake
 		MetaverseHasNoStake,
+		/// Metaverse is frozen
+		MetaverseIsFrozen,
+		/// Metaverse is listed in an auction
+		MetaverseIsInAuction,
+		/// Account already holds the role
+		RoleAlreadyGranted,
+		/// Account does not hold the role
+		RoleNotFound,
+		/// Custom role name is empty or too long
+		InvalidRoleName,
+		/// Metaverse has reached the maximum number of team members
+		TooManyRoleMembers,
 	}
 
 	#[pallet::call]
@@ -272,38 +316,17 @@ pub mod pallet {
 		}
 
 		#[pallet::weight(T::WeightInfo::transfer_metaverse())]
+		#[transactional]
 		pub fn transfer_metaverse(
 			origin: OriginFor<T>,
 			to: T::AccountId,
 			metaverse_id: MetaverseId,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			// Get owner of the metaverse
-			MetaverseOwner::<T>::try_mutate_exists(
-				&who,
-				&metaverse_id,
-				|metaverse_by_owner| -> DispatchResultWithPostInfo {
-					// Ensure there is record of the metaverse owner with metaverse id, account
-					// id and delete them
-					ensure!(metaverse_by_owner.is_some(), Error::<T>::NoPermission);
-
-					if who == to {
-						// No change needed
-						return Ok(().into());
-					}
-
-					*metaverse_by_owner = None;
-					MetaverseOwner::<T>::insert(to.clone(), metaverse_id.clone(), ());
 
-					Metaverses::<T>::try_mutate_exists(&metaverse_id, |metaverse| -> DispatchResultWithPostInfo {
-						let mut metaverse_record = metaverse.as_mut().ok_or(Error::<T>::NoPermission)?;
-						metaverse_record.owner = to.clone();
-						Self::deposit_event(Event::<T>::TransferredMetaverse(metaverse_id, who.clone(), to.clone()));
+			Self::do_transfer_metaverse(&who, &to, metaverse_id)?;
 
-						Ok(().into())
-					})
-				},
-			)
+			Ok(().into())
 		}
 
 		#[pallet::weight(T::WeightInfo::freeze_metaverse())]
@@ -346,13 +369,63 @@ pub mod pallet {
 			let metaverse_info = Metaverses::<T>::get(metaverse_id).ok_or(Error::<T>::MetaverseInfoNotFound)?;
 
 			ensure!(metaverse_info.is_frozen, Error::<T>::OnlyFrozenMetaverseCanBeDestroyed);
+			ensure!(
+				!Self::is_metaverse_in_auction(metaverse_id),
+				Error::<T>::MetaverseIsInAuction
+			);
 
 			MetaverseOwner::<T>::remove(metaverse_info.owner, &metaverse_id);
 			Metaverses::<T>::remove(&metaverse_id);
+			Self::clear_metaverse_roles(metaverse_id);
+			MetaverseMetadataHistory::<T>::remove(metaverse_id);
 			Self::deposit_event(Event::<T>::MetaverseDestroyed(metaverse_id));
 			Ok(().into())
 		}
 
+		/// Update the metadata of a metaverse, keeping the replaced metadata in its history.
+		/// Only the metaverse owner can update it.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 2))]
+		pub fn update_metaverse_metadata(
+			origin: OriginFor<T>,
+			metaverse_id: MetaverseId,
+			metadata: MetaverseMetadata,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				metadata.len() as u32 <= T::MaxMetaverseMetadata::get(),
+				Error::<T>::MaxMetadataExceeded
+			);
+			ensure!(Self::check_ownership(&who, &metaverse_id), Error::<T>::NoPermission);
+			ensure!(
+				!Self::is_metaverse_in_auction(metaverse_id),
+				Error::<T>::MetaverseIsInAuction
+			);
+
+			Metaverses::<T>::try_mutate(metaverse_id, |maybe_metaverse| -> DispatchResult {
+				let metaverse_info = maybe_metaverse.as_mut().ok_or(Error::<T>::MetaverseInfoNotFound)?;
+				ensure!(!metaverse_info.is_frozen, Error::<T>::MetaverseIsFrozen);
+				let previous_metadata = sp_std::mem::replace(&mut metaverse_info.metadata, metadata.clone());
+
+				let max_history = T::MaxMetadataHistory::get() as usize;
+				MetaverseMetadataHistory::<T>::mutate_exists(metaverse_id, |maybe_history| {
+					let history = maybe_history.get_or_insert_with(Vec::new);
+					history.push((<frame_system::Pallet<T>>::block_number(), previous_metadata));
+					// Drops every version beyond the limit, also after the limit was lowered
+					let excess = history.len().saturating_sub(max_history);
+					history.drain(..excess);
+					if history.is_empty() {
+						*maybe_history = None;
+					}
+				});
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::<T>::MetaverseMetadataUpdated(metaverse_id, metadata));
+
+			Ok(().into())
+		}
+
 		/// Register metaverse for staking
 		/// only metaverse owner can register for staking
 		#[pallet::weight(T::WeightInfo::register_metaverse())]
@@ -596,6 +669,82 @@ pub mod pallet {
 			<MetaverseRoundStake<T>>::insert(&metaverse_id, round, metaverse_stake_per_round);
 			Ok(().into())
 		}
+
+		/// Grant a role in the metaverse to a member of the team, only the metaverse owner can
+		/// manage roles
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 2))]
+		pub fn grant_metaverse_role(
+			origin: OriginFor<T>,
+			metaverse_id: MetaverseId,
+			who: T::AccountId,
+			role: MetaverseRole,
+		) -> DispatchResultWithPostInfo {
+			let owner = ensure_signed(origin)?;
+
+			ensure!(Self::check_ownership(&owner, &metaverse_id), Error::<T>::NoPermission);
+			if let MetaverseRole::Custom(name) = &role {
+				ensure!(
+					!name.is_empty() && name.len() as u32 <= T::MaxRoleNameLength::get(),
+					Error::<T>::InvalidRoleName
+				);
+			}
+
+			MetaverseRoles::<T>::try_mutate(metaverse_id, &who, |roles| -> DispatchResult {
+				let index = roles.binary_search(&role).err().ok_or(Error::<T>::RoleAlreadyGranted)?;
+				if roles.is_empty() {
+					MetaverseRoleMembers::<T>::try_mutate(metaverse_id, |members| -> DispatchResult {
+						ensure!(
+							*members < T::MaxRolesPerMetaverse::get(),
+							Error::<T>::TooManyRoleMembers
+						);
+						*members += 1;
+						Ok(())
+					})?;
+				}
+				roles.insert(index, role.clone());
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::<T>::MetaverseRoleGranted(metaverse_id, who, role));
+
+			Ok(().into())
+		}
+
+		/// Revoke a role in the metaverse. The metaverse owner can revoke any role and members
+		/// can give up their own.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 2))]
+		pub fn revoke_metaverse_role(
+			origin: OriginFor<T>,
+			metaverse_id: MetaverseId,
+			who: T::AccountId,
+			role: MetaverseRole,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(
+				sender == who || Self::check_ownership(&sender, &metaverse_id),
+				Error::<T>::NoPermission
+			);
+
+			MetaverseRoles::<T>::try_mutate_exists(metaverse_id, &who, |maybe_roles| -> DispatchResult {
+				let roles = maybe_roles.as_mut().ok_or(Error::<T>::RoleNotFound)?;
+				let index = roles.binary_search(&role).map_err(|_| Error::<T>::RoleNotFound)?;
+				roles.remove(index);
+				if roles.is_empty() {
+					*maybe_roles = None;
+					MetaverseRoleMembers::<T>::mutate_exists(metaverse_id, |members| {
+						*members = members
+							.map(|members| members.saturating_sub(1))
+							.filter(|members| *members > 0);
+					});
+				}
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::<T>::MetaverseRoleRevoked(metaverse_id, who, role));
+
+			Ok(().into())
+		}
 	}
 
 	#[pallet::hooks]
@@ -622,6 +771,47 @@ impl<T: Config> Pallet<T> {
 		Ok(metaverse_id)
 	}
 
+	fn do_transfer_metaverse(who: &T::AccountId, to: &T::AccountId, metaverse_id: MetaverseId) -> DispatchResult {
+		// Every check runs before the first write, the auction settles transfers outside of an
+		// extrinsic
+		ensure!(
+			MetaverseOwner::<T>::contains_key(&who, &metaverse_id),
+			Error::<T>::NoPermission
+		);
+		let mut metaverse_record = Metaverses::<T>::get(&metaverse_id).ok_or(Error::<T>::NoPermission)?;
+		// Listed metaverses are locked by the auction, which unlocks them before settling
+		ensure!(
+			!Self::is_metaverse_in_auction(metaverse_id),
+			Error::<T>::MetaverseIsInAuction
+		);
+		ensure!(!metaverse_record.is_frozen, Error::<T>::MetaverseIsFrozen);
+
+		if who == to {
+			// No change needed
+			return Ok(());
+		}
+
+		MetaverseOwner::<T>::remove(&who, &metaverse_id);
+		MetaverseOwner::<T>::insert(to.clone(), metaverse_id, ());
+		metaverse_record.owner = to.clone();
+		Metaverses::<T>::insert(metaverse_id, metaverse_record);
+		// The team of the previous owner does not carry over
+		Self::clear_metaverse_roles(metaverse_id);
+		Self::deposit_event(Event::<T>::TransferredMetaverse(metaverse_id, who.clone(), to.clone()));
+
+		Ok(())
+	}
+
+	/// Remove the whole team of a metaverse, bounded by the maximum number of team members
+	fn clear_metaverse_roles(metaverse_id: MetaverseId) {
+		let _ = MetaverseRoles::<T>::remove_prefix(metaverse_id, Some(T::MaxRolesPerMetaverse::get()));
+		MetaverseRoleMembers::<T>::remove(metaverse_id);
+	}
+
+	fn is_metaverse_in_auction(metaverse_id: MetaverseId) -> bool {
+		T::AuctionHandler::check_item_in_auction(ItemId::Country(metaverse_id))
+	}
+
 	/// The account ID of the treasury pot.
 	///
 	/// This actually does computation. If you need to keep using it, then make sure you cache the
@@ -647,6 +837,16 @@ impl<T: Config> MetaverseTrait<T::AccountId> for Pallet<T> {
 		Self::get_metaverse_owner(who, metaverse_id) == Some(())
 	}
 
+	fn check_role(who: &T::AccountId, metaverse_id: &MetaverseId, role: &MetaverseRole) -> bool {
+		if Self::check_ownership(who, metaverse_id) {
+			return true;
+		}
+
+		let roles = Self::get_metaverse_roles(metaverse_id, who);
+		// Admins hold every built-in role but not the custom ones
+		roles.contains(role) || (roles.contains(&MetaverseRole::Admin) && !matches!(role, MetaverseRole::Custom(_)))
+	}
+
 	fn get_metaverse(metaverse_id: MetaverseId) -> Option<MetaverseInfo<T::AccountId>> {
 		Self::get_metaverse(metaverse_id)
 	}
@@ -672,6 +872,10 @@ impl<T: Config> MetaverseTrait<T::AccountId> for Pallet<T> {
 			Ok(())
 		})
 	}
+
+	fn transfer_metaverse(from: &T::AccountId, to: &T::AccountId, metaverse_id: MetaverseId) -> DispatchResult {
+		Self::do_transfer_metaverse(from, to, metaverse_id)
+	}
 }
 
 impl<T: Config> MetaverseStakingTrait<BalanceOf<T>> for Pallet<T> {
@@ -24,6 +24,7 @@ pub const BOB: AccountId = 2;
 pub const FREEDY: AccountId = 3;
 pub const METAVERSE_ID: MetaverseId = 0;
 pub const COUNTRY_ID_NOT_EXIST: MetaverseId = 1;
+pub const METAVERSE_IN_AUCTION: MetaverseId = 2;
 
 pub const DOLLARS: Balance = 1_000_000_000_000_000_000;
 
@@ -84,6 +85,24 @@ parameter_types! {
 	pub const MinContribution: Balance = 1;
 	pub const MinStakingAmount: Balance = 100;
 	pub const MaxNumberOfStakersPerMetaverse: u32 = 1;
+	pub const MaxRoleNameLength: u32 = 8;
+	pub const MaxRolesPerMetaverse: u32 = 2;
+	pub const MaxMetadataHistory: u32 = 2;
+}
+
+pub struct MockAuctionManager;
+
+impl CheckAuctionItemHandler for MockAuctionManager {
+	fn check_item_in_auction(item_id: ItemId) -> bool {
+		match item_id {
+			ItemId::Country(METAVERSE_IN_AUCTION) => {
+				return true;
+			}
+			_ => {
+				return false;
+			}
+		}
+	}
 }
 
 ord_parameter_types! {
@@ -102,6 +121,10 @@ impl Config for Runtime {
 	type MetaverseRegistrationDeposit = MinContribution;
 	type MinStakingAmount = MinStakingAmount;
 	type MaxNumberOfStakersPerMetaverse = MaxNumberOfStakersPerMetaverse;
+	type MaxRoleNameLength = MaxRoleNameLength;
+	type MaxRolesPerMetaverse = MaxRolesPerMetaverse;
+	type MaxMetadataHistory = MaxMetadataHistory;
+	type AuctionHandler = MockAuctionManager;
 	type WeightInfo = ();
 }
 
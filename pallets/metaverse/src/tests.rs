@@ -85,6 +85,46 @@ fn transfer_metaverse_should_fail() {
 	})
 }
 
+#[test]
+fn transfer_frozen_metaverse_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(MetaverseModule::create_metaverse(Origin::signed(ALICE), vec![1]));
+		assert_ok!(MetaverseModule::freeze_metaverse(Origin::signed(ALICE), METAVERSE_ID));
+		assert_noop!(
+			MetaverseModule::transfer_metaverse(Origin::signed(ALICE), BOB, METAVERSE_ID),
+			Error::<Runtime>::MetaverseIsFrozen
+		);
+		// Transfers settled by other pallets leave no owner record behind either
+		assert_noop!(
+			<MetaverseModule as MetaverseTrait<AccountId>>::transfer_metaverse(&ALICE, &BOB, METAVERSE_ID),
+			Error::<Runtime>::MetaverseIsFrozen
+		);
+		assert!(!MetaverseModule::check_ownership(&BOB, &METAVERSE_ID));
+	})
+}
+
+#[test]
+fn metaverse_in_auction_should_be_locked() {
+	ExtBuilder::default().build().execute_with(|| {
+		for _ in 0..3 {
+			assert_ok!(MetaverseModule::create_metaverse(Origin::signed(ALICE), vec![1]));
+		}
+		assert_noop!(
+			MetaverseModule::transfer_metaverse(Origin::signed(ALICE), BOB, METAVERSE_IN_AUCTION),
+			Error::<Runtime>::MetaverseIsInAuction
+		);
+		// Council freezes do not lift the auction lock
+		assert_ok!(MetaverseModule::freeze_metaverse(
+			Origin::signed(ALICE),
+			METAVERSE_IN_AUCTION
+		));
+		assert_noop!(
+			MetaverseModule::destroy_metaverse(Origin::signed(ALICE), METAVERSE_IN_AUCTION),
+			Error::<Runtime>::MetaverseIsInAuction
+		);
+	})
+}
+
 #[test]
 fn freeze_metaverse_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -346,3 +386,226 @@ fn unstake_should_work() {
 		assert_eq!(*(metaverse_stake_per_round.stakers.entry(ALICE).or_default()), 9900u64);
 	})
 }
+
+#[test]
+fn metaverse_roles_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(MetaverseModule::create_metaverse(Origin::signed(ALICE), vec![1]));
+
+		assert_noop!(
+			MetaverseModule::grant_metaverse_role(Origin::signed(BOB), METAVERSE_ID, FREEDY, MetaverseRole::Builder),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			MetaverseModule::grant_metaverse_role(
+				Origin::signed(ALICE),
+				METAVERSE_ID,
+				BOB,
+				MetaverseRole::Custom(vec![1; 9])
+			),
+			Error::<Runtime>::InvalidRoleName
+		);
+
+		assert_ok!(MetaverseModule::grant_metaverse_role(
+			Origin::signed(ALICE),
+			METAVERSE_ID,
+			BOB,
+			MetaverseRole::Admin
+		));
+		assert_eq!(
+			last_event(),
+			Event::Metaverse(crate::Event::MetaverseRoleGranted(
+				METAVERSE_ID,
+				BOB,
+				MetaverseRole::Admin
+			))
+		);
+		assert_ok!(MetaverseModule::grant_metaverse_role(
+			Origin::signed(ALICE),
+			METAVERSE_ID,
+			FREEDY,
+			MetaverseRole::Custom(b"dj".to_vec())
+		));
+		assert_noop!(
+			MetaverseModule::grant_metaverse_role(Origin::signed(ALICE), METAVERSE_ID, BOB, MetaverseRole::Admin),
+			Error::<Runtime>::RoleAlreadyGranted
+		);
+
+		// Admins hold the built-in roles, owners hold every role
+		let dj = MetaverseRole::Custom(b"dj".to_vec());
+		assert!(MetaverseModule::check_role(
+			&BOB,
+			&METAVERSE_ID,
+			&MetaverseRole::Builder
+		));
+		assert!(!MetaverseModule::check_role(&BOB, &METAVERSE_ID, &dj));
+		assert!(MetaverseModule::check_role(&FREEDY, &METAVERSE_ID, &dj));
+		assert!(!MetaverseModule::check_role(
+			&FREEDY,
+			&METAVERSE_ID,
+			&MetaverseRole::Moderator
+		));
+		assert!(MetaverseModule::check_role(&ALICE, &METAVERSE_ID, &dj));
+
+		// Members can give up their own roles only
+		assert_noop!(
+			MetaverseModule::revoke_metaverse_role(Origin::signed(FREEDY), METAVERSE_ID, BOB, MetaverseRole::Admin),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(MetaverseModule::revoke_metaverse_role(
+			Origin::signed(BOB),
+			METAVERSE_ID,
+			BOB,
+			MetaverseRole::Admin
+		));
+		assert_eq!(
+			last_event(),
+			Event::Metaverse(crate::Event::MetaverseRoleRevoked(
+				METAVERSE_ID,
+				BOB,
+				MetaverseRole::Admin
+			))
+		);
+		assert!(!MetaverseModule::check_role(
+			&BOB,
+			&METAVERSE_ID,
+			&MetaverseRole::Builder
+		));
+		assert_noop!(
+			MetaverseModule::revoke_metaverse_role(Origin::signed(ALICE), METAVERSE_ID, BOB, MetaverseRole::Admin),
+			Error::<Runtime>::RoleNotFound
+		);
+	});
+}
+
+#[test]
+fn metaverse_roles_should_be_limited_per_metaverse() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(MetaverseModule::create_metaverse(Origin::signed(ALICE), vec![1]));
+		assert_ok!(MetaverseModule::grant_metaverse_role(
+			Origin::signed(ALICE),
+			METAVERSE_ID,
+			BOB,
+			MetaverseRole::Builder
+		));
+		assert_ok!(MetaverseModule::grant_metaverse_role(
+			Origin::signed(ALICE),
+			METAVERSE_ID,
+			FREEDY,
+			MetaverseRole::Moderator
+		));
+		// More roles for an existing member do not count towards the limit
+		assert_ok!(MetaverseModule::grant_metaverse_role(
+			Origin::signed(ALICE),
+			METAVERSE_ID,
+			BOB,
+			MetaverseRole::Moderator
+		));
+		assert_eq!(MetaverseModule::get_metaverse_role_members(METAVERSE_ID), 2);
+
+		assert_noop!(
+			MetaverseModule::grant_metaverse_role(Origin::signed(ALICE), METAVERSE_ID, 4, MetaverseRole::Builder),
+			Error::<Runtime>::TooManyRoleMembers
+		);
+
+		assert_ok!(MetaverseModule::revoke_metaverse_role(
+			Origin::signed(FREEDY),
+			METAVERSE_ID,
+			FREEDY,
+			MetaverseRole::Moderator
+		));
+		assert_eq!(MetaverseModule::get_metaverse_role_members(METAVERSE_ID), 1);
+		assert_ok!(MetaverseModule::grant_metaverse_role(
+			Origin::signed(ALICE),
+			METAVERSE_ID,
+			4,
+			MetaverseRole::Builder
+		));
+		assert_eq!(MetaverseModule::get_metaverse_role_members(METAVERSE_ID), 2);
+	});
+}
+
+#[test]
+fn metaverse_roles_should_be_cleared_on_transfer() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(MetaverseModule::create_metaverse(Origin::signed(ALICE), vec![1]));
+		assert_ok!(MetaverseModule::grant_metaverse_role(
+			Origin::signed(ALICE),
+			METAVERSE_ID,
+			FREEDY,
+			MetaverseRole::Moderator
+		));
+
+		assert_ok!(MetaverseModule::transfer_metaverse(
+			Origin::signed(ALICE),
+			BOB,
+			METAVERSE_ID
+		));
+		assert_eq!(MetaverseModule::get_metaverse_roles(METAVERSE_ID, FREEDY), vec![]);
+		assert_eq!(MetaverseModule::get_metaverse_role_members(METAVERSE_ID), 0);
+		assert!(!MetaverseModule::check_role(
+			&FREEDY,
+			&METAVERSE_ID,
+			&MetaverseRole::Moderator
+		));
+		assert!(MetaverseModule::check_role(&BOB, &METAVERSE_ID, &MetaverseRole::Admin));
+	});
+}
+
+#[test]
+fn update_metaverse_metadata_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(MetaverseModule::create_metaverse(Origin::signed(ALICE), vec![1]));
+
+		assert_noop!(
+			MetaverseModule::update_metaverse_metadata(Origin::signed(BOB), METAVERSE_ID, vec![2]),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			MetaverseModule::update_metaverse_metadata(Origin::signed(ALICE), METAVERSE_ID, vec![2; 1025]),
+			Error::<Runtime>::MaxMetadataExceeded
+		);
+
+		// Longer history than the limit, as left behind by a lowered limit
+		MetaverseMetadataHistory::<Runtime>::insert(METAVERSE_ID, vec![(0, vec![7]); 4]);
+
+		for version in 2..5u8 {
+			System::set_block_number(version as u64);
+			assert_ok!(MetaverseModule::update_metaverse_metadata(
+				Origin::signed(ALICE),
+				METAVERSE_ID,
+				vec![version]
+			));
+			assert_eq!(
+				last_event(),
+				Event::Metaverse(crate::Event::MetaverseMetadataUpdated(METAVERSE_ID, vec![version]))
+			);
+		}
+
+		assert_eq!(MetaverseModule::get_metaverse(METAVERSE_ID).unwrap().metadata, vec![4]);
+		// Only the latest replaced versions are kept
+		assert_eq!(
+			MetaverseModule::get_metadata_history(METAVERSE_ID),
+			vec![(3, vec![2]), (4, vec![3])]
+		);
+	});
+}
+
+#[test]
+fn update_metaverse_metadata_should_fail_for_locked_metaverse() {
+	ExtBuilder::default().build().execute_with(|| {
+		for _ in 0..3 {
+			assert_ok!(MetaverseModule::create_metaverse(Origin::signed(ALICE), vec![1]));
+		}
+		assert_noop!(
+			MetaverseModule::update_metaverse_metadata(Origin::signed(ALICE), METAVERSE_IN_AUCTION, vec![2]),
+			Error::<Runtime>::MetaverseIsInAuction
+		);
+
+		assert_ok!(MetaverseModule::freeze_metaverse(Origin::signed(ALICE), METAVERSE_ID));
+		assert_noop!(
+			MetaverseModule::update_metaverse_metadata(Origin::signed(ALICE), METAVERSE_ID, vec![2]),
+			Error::<Runtime>::MetaverseIsFrozen
+		);
+	});
+}
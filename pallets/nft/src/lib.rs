@@ -920,6 +920,10 @@ impl<T: Config> NFTTrait<T::AccountId> for Pallet<T> {
 		Ok(class_collection_id == collection_id)
 	}
 
+	fn check_class_exists(class_id: &Self::ClassId) -> bool {
+		NftModule::<T>::classes(class_id).is_some()
+	}
+
 	fn check_item_on_listing(class_id: Self::ClassId, token_id: Self::TokenId) -> Result<bool, DispatchError> {
 		let fixed_class_id = TryInto::<ClassId>::try_into(class_id).unwrap_or_default();
 		let fixed_nft_id = TryInto::<TokenId>::try_into(token_id).unwrap_or_default();
@@ -123,6 +123,10 @@ impl MetaverseTrait<AccountId> for MetaverseInfoSource {
 		}
 	}
 
+	fn check_role(who: &AccountId, metaverse_id: &MetaverseId, _role: &MetaverseRole) -> bool {
+		Self::check_ownership(who, metaverse_id)
+	}
+
 	fn get_metaverse(metaverse_id: u64) -> Option<MetaverseInfo<u128>> {
 		None
 	}
@@ -134,6 +138,10 @@ impl MetaverseTrait<AccountId> for MetaverseInfoSource {
 	fn update_metaverse_token(metaverse_id: u64, currency_id: FungibleTokenId) -> Result<(), DispatchError> {
 		Ok(())
 	}
+
+	fn transfer_metaverse(from: &AccountId, to: &AccountId, metaverse_id: u64) -> Result<(), DispatchError> {
+		Ok(())
+	}
 }
 
 pub struct DEXManager {}
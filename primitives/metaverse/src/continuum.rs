@@ -1,7 +1,82 @@
+use codec::{Decode, Encode};
+use scale_info::{prelude::vec::Vec, TypeInfo};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{DispatchError, RuntimeDebug};
+
 use crate::{MetaverseId, SpotId};
-use sp_runtime::DispatchError;
 
 pub trait Continuum<AccountId> {
 	fn transfer_spot(spot_id: SpotId, from: &AccountId, to: &(AccountId, MetaverseId))
 		-> Result<SpotId, DispatchError>;
 }
+
+/// Travel link between two metaverses holding neighbouring continuum spots
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Eq, PartialEq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct ContinuumPortal {
+	/// Linked metaverses, the proposing side first
+	pub metaverses: (MetaverseId, MetaverseId),
+	/// Neighbouring spots of the metaverses when the portal was proposed
+	pub spots: (SpotId, SpotId),
+	/// Portal metadata
+	pub metadata: Vec<u8>,
+}
+
+/// Stage of a continuum spot in the map
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Eq, PartialEq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum SpotStatus {
+	/// Held by the treasury and open for expressions of interest
+	Free,
+	/// Held by a metaverse
+	Occupied,
+	/// Collecting expressions of interest, in the EOI set or an active auction slot
+	ExpressionOfInterest,
+	/// Listed in an auction
+	InAuction,
+	/// Bidders are being voted on by the neighbours
+	GoodNeighbourhoodProtocol,
+}
+
+/// Neighbour votes on a bidder of an ongoing referendum
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Eq, PartialEq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct SpotReferendumTally<AccountId> {
+	/// Bidder
+	pub who: AccountId,
+	/// Nay votes
+	pub nays: u8,
+	/// All votes
+	pub turnout: u8,
+}
+
+/// Ongoing good neighbourhood referendum on a spot
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Eq, PartialEq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct SpotReferendum<AccountId, BlockNumber> {
+	/// Block the referendum ends at
+	pub end: BlockNumber,
+	/// Distinct owners of neighbouring spots when the referendum started
+	pub neighbours: u8,
+	/// Tally per bidder
+	pub tallies: Vec<SpotReferendumTally<AccountId>>,
+}
+
+/// Continuum spot as rendered on the map
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Eq, PartialEq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct ContinuumSpotInfo<AccountId, Balance, BlockNumber> {
+	/// Spot coordinate
+	pub coordinate: (i32, i32),
+	/// Spot id, None when no spot was created at the coordinate yet
+	pub spot_id: Option<SpotId>,
+	/// Occupant metaverse
+	pub metaverse_id: Option<MetaverseId>,
+	/// Spot status
+	pub status: SpotStatus,
+	/// Current buy now price
+	pub price: Balance,
+	/// Ongoing referendum on the spot
+	pub referendum: Option<SpotReferendum<AccountId, BlockNumber>>,
+}
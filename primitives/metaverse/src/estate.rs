@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 use sp_runtime::DispatchError;
 use sp_runtime::{Perbill, RuntimeDebug};
 
-use crate::{EstateId, MetaverseId};
+use crate::{Balance, EstateId, FungibleTokenId, Hash, MetaverseId};
 
 pub trait Estate<AccountId> {
 	fn transfer_estate(estate_id: EstateId, from: &AccountId, to: &AccountId) -> Result<EstateId, DispatchError>;
@@ -23,6 +23,16 @@ pub trait Estate<AccountId> {
 	fn get_total_land_units() -> u64;
 
 	fn get_total_undeploy_land_units() -> u64;
+
+	fn is_estate_leased(estate_id: EstateId) -> Result<bool, DispatchError>;
+
+	fn is_land_unit_leased(metaverse_id: MetaverseId, coordinate: (i32, i32)) -> Result<bool, DispatchError>;
+
+	fn is_estate_collateralized(estate_id: EstateId) -> Result<bool, DispatchError>;
+
+	fn is_land_unit_collateralized(metaverse_id: MetaverseId, coordinate: (i32, i32)) -> Result<bool, DispatchError>;
+
+	fn record_land_sale(asset: LandAsset, price: Balance);
 }
 
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -33,3 +43,220 @@ pub struct EstateInfo {
 	/// Land Units
 	pub land_units: Vec<(i32, i32)>,
 }
+
+/// Shape every estate of a metaverse must have
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Eq, PartialEq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum EstateShapeRule {
+	/// Land units can be anywhere on the map
+	Unrestricted,
+	/// Land units must be connected through their edges
+	FourConnected,
+	/// Land units must be connected through their edges or corners
+	EightConnected,
+	/// Land units must fill a rectangle
+	Rectangular,
+}
+
+impl Default for EstateShapeRule {
+	fn default() -> Self {
+		EstateShapeRule::Unrestricted
+	}
+}
+
+/// Estate or land unit addressed by leases, content, builder grants and the other land features
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Eq, PartialEq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum LandAsset {
+	Estate(EstateId),
+	LandUnit((i32, i32), MetaverseId),
+}
+
+/// Lease terms proposed by the landlord, waiting for the tenant to accept
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Eq, PartialEq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct LeaseOffer<AccountId, Balance, BlockNumber> {
+	/// Owner of the leased property
+	pub landlord: AccountId,
+	/// Account which gets the usage rights
+	pub tenant: AccountId,
+	/// Currency the rent is paid in
+	pub currency_id: FungibleTokenId,
+	/// Rent due every period
+	pub rent: Balance,
+	/// Length of a rent period in blocks
+	pub period: BlockNumber,
+	/// Number of periods in the lease term
+	pub periods: u32,
+}
+
+/// Active lease of a land property
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Eq, PartialEq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct LeaseInfo<AccountId, Balance, BlockNumber> {
+	/// Owner of the leased property
+	pub landlord: AccountId,
+	/// Account which has the usage rights
+	pub tenant: AccountId,
+	/// Currency the rent is paid in
+	pub currency_id: FungibleTokenId,
+	/// Rent due every period
+	pub rent: Balance,
+	/// Length of a rent period in blocks
+	pub period: BlockNumber,
+	/// Block at which the lease term ends
+	pub end_block: BlockNumber,
+	/// Block until which rent has been paid
+	pub paid_until: BlockNumber,
+}
+
+/// Pointer to the scene built on an estate or land unit
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Eq, PartialEq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct ParcelContent<AccountId, BlockNumber> {
+	/// Hash of the scene manifest
+	pub manifest_hash: Hash,
+	/// Location of the scene manifest
+	pub uri: Vec<u8>,
+	/// Content version, increased on every update
+	pub version: u32,
+	/// Account which made the last update
+	pub updated_by: AccountId,
+	/// Block of the last update
+	pub updated_at: BlockNumber,
+}
+
+/// Right to update the content of an estate or land unit
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Eq, PartialEq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct BuilderGrant<AccountId, BlockNumber> {
+	/// Account which gave the grant. The grant lapses once it loses its usage rights
+	pub granted_by: AccountId,
+	/// Block after which the grant is no longer valid
+	pub expires_at: Option<BlockNumber>,
+}
+
+/// Owner of a land unit as shown on the map. Land units of an estate resolve to the estate owner.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Eq, PartialEq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct LandUnitOwnership<AccountId> {
+	/// Coordinate of the land unit
+	pub coordinate: (i32, i32),
+	/// Owner of the land unit, or of the estate it belongs to
+	pub owner: AccountId,
+	/// Estate the land unit belongs to
+	pub estate_id: Option<EstateId>,
+}
+
+/// Self-assessed land tax settings of a metaverse
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Eq, PartialEq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct LandTaxConfig<BlockNumber> {
+	/// Share of the self-assessed price due as tax every period
+	pub tax_rate: Perbill,
+	/// Length of a tax period in blocks
+	pub tax_period: BlockNumber,
+}
+
+/// Price an owner declared for an estate or land unit. Anyone can buy the asset at this price.
+/// Land starts out assessed at the price it was minted or last sold at.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Eq, PartialEq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct SelfAssessment<AccountId, Balance, BlockNumber> {
+	/// Current owner of the asset
+	pub owner: AccountId,
+	/// Declared price
+	pub price: Balance,
+	/// Block until which tax has been paid
+	pub paid_until: BlockNumber,
+	/// Share of the price due as tax for the next period
+	pub tax_rate: Perbill,
+	/// Length of the next tax period in blocks
+	pub tax_period: BlockNumber,
+}
+
+/// Terms of a loan an owner asks for against an estate or land unit
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Eq, PartialEq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct LoanRequest<AccountId, Balance, BlockNumber> {
+	/// Owner of the collateral
+	pub borrower: AccountId,
+	/// Account asked to fund the loan
+	pub lender: AccountId,
+	/// Currency the loan is paid out and repaid in
+	pub currency_id: FungibleTokenId,
+	/// Amount lent to the borrower
+	pub principal: Balance,
+	/// Amount the borrower repays, principal included
+	pub repayment: Balance,
+	/// Blocks from funding until the loan is due
+	pub duration: BlockNumber,
+}
+
+/// Loan secured by an estate or land unit, which stays locked until the loan is settled
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Eq, PartialEq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct LoanInfo<AccountId, Balance, BlockNumber> {
+	/// Owner of the collateral
+	pub borrower: AccountId,
+	/// Account which funded the loan
+	pub lender: AccountId,
+	/// Currency the loan is repaid in
+	pub currency_id: FungibleTokenId,
+	/// Amount the borrower repays, principal included
+	pub repayment: Balance,
+	/// Block after which the lender can claim the collateral
+	pub due_block: BlockNumber,
+}
+
+/// Area of a primary land sale sold at its own price per land unit
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Eq, PartialEq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct LandSaleZone<Balance> {
+	/// Bottom left coordinate of the zone
+	pub bottom_left: (i32, i32),
+	/// Top right coordinate of the zone
+	pub top_right: (i32, i32),
+	/// Price of a land unit in the zone
+	pub unit_price: Balance,
+}
+
+/// Primary sale of land units a metaverse owner runs within the metaverse bounds
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Eq, PartialEq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct LandSale<Balance, BlockNumber> {
+	/// Bottom left coordinate of the area on sale
+	pub bottom_left: (i32, i32),
+	/// Top right coordinate of the area on sale
+	pub top_right: (i32, i32),
+	/// Price of a land unit outside every zone
+	pub unit_price: Balance,
+	/// Zones with their own price. The first zone containing a coordinate sets its price
+	pub zones: Vec<LandSaleZone<Balance>>,
+	/// Maximum number of land units one account can buy in the sale
+	pub max_units_per_account: Option<u32>,
+	/// Block until which only whitelisted accounts can buy
+	pub whitelist_until: Option<BlockNumber>,
+}
+
+impl<Balance: Copy, BlockNumber> LandSale<Balance, BlockNumber> {
+	/// Whether the coordinate lies in the area on sale
+	pub fn contains(&self, coordinate: (i32, i32)) -> bool {
+		is_within(coordinate, self.bottom_left, self.top_right)
+	}
+
+	/// Price of a land unit at the coordinate
+	pub fn price_of(&self, coordinate: (i32, i32)) -> Balance {
+		self.zones
+			.iter()
+			.find(|zone| is_within(coordinate, zone.bottom_left, zone.top_right))
+			.map_or(self.unit_price, |zone| zone.unit_price)
+	}
+}
+
+fn is_within(coordinate: (i32, i32), bottom_left: (i32, i32), top_right: (i32, i32)) -> bool {
+	coordinate.0 >= bottom_left.0
+		&& coordinate.0 <= top_right.0
+		&& coordinate.1 >= bottom_left.1
+		&& coordinate.1 <= top_right.1
+}
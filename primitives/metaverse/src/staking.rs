@@ -2,7 +2,7 @@ use codec::{Decode, Encode};
 use sp_runtime::DispatchResult;
 use sp_std::vec::Vec;
 
-use crate::{AccountId, MetaverseId, RoundIndex, RuntimeDebug, TypeInfo};
+use crate::{AccountId, EstateId, MetaverseId, RoundIndex, RuntimeDebug, TypeInfo};
 
 #[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
 /// The current round index and transition information
@@ -28,6 +28,26 @@ pub struct Bond<AccountId, Balance> {
 	pub amount: Balance,
 }
 
+#[derive(Default, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+/// Rewards set aside for a finished round, waiting to be paid out to its stakers
+pub struct DelayedPayout<Balance> {
+	/// Total reward issued for the round
+	pub round_issuance: Balance,
+	/// Total stake backing the round snapshot
+	pub total_staked: Balance,
+}
+
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+/// Progress of the stake snapshot taken over the first blocks of a round
+pub struct SnapshotProgress<AccountId> {
+	/// Round the snapshot is taken for
+	pub round: RoundIndex,
+	/// Last estate stake visited, the snapshot resumes after it
+	pub last_stake: Option<(EstateId, AccountId)>,
+	/// Whether any staker who left got their bond back
+	pub exits_cleared: bool,
+}
+
 impl<B: Copy + sp_std::ops::Add<Output = B> + sp_std::ops::Sub<Output = B> + From<u32> + PartialOrd> RoundInfo<B> {
 	pub fn new(current: RoundIndex, first: B, length: u32) -> RoundInfo<B> {
 		RoundInfo { current, first, length }
@@ -70,8 +70,13 @@ pub use constants::{currency::*, time::*};
 use currencies::BasicCurrencyAdapter;
 pub use estate::{MintingRateInfo, Range as MintingRange};
 //use pallet_evm::{EnsureAddressTruncated, HashedAddressMapping};
+use auction_manager::{SaleRecord, SalesHistory};
 use estate::weights::WeightInfo;
-use primitives::{Amount, Balance, BlockNumber, ClassId, FungibleTokenId, Moment, NftId, RoundIndex};
+use primitives::continuum::{ContinuumPortal, ContinuumSpotInfo};
+use primitives::estate::{EstateInfo, LandAsset, LandUnitOwnership};
+use primitives::{
+	Amount, Balance, BlockNumber, ClassId, EstateId, FungibleTokenId, ItemId, MetaverseId, Moment, NftId, RoundIndex,
+};
 
 // primitives imports
 use crate::opaque::SessionKeys;
@@ -486,6 +491,9 @@ parameter_types! {
 	pub MaxMetaverseMetadata: u32 = 1024;
 	pub MinContribution: Balance = 1 * DOLLARS;
 	pub MaxNumberOfStakerPerMetaverse: u32 = 512;
+	pub MaxRoleNameLength: u32 = 32;
+	pub MaxRolesPerMetaverse: u32 = 256;
+	pub MaxMetadataHistory: u32 = 5;
 }
 
 impl metaverse::Config for Runtime {
@@ -500,6 +508,10 @@ impl metaverse::Config for Runtime {
 	type MinStakingAmount = MinContribution;
 	type MaxNumberOfStakersPerMetaverse = MaxNumberOfStakerPerMetaverse;
 	type MultiCurrency = Currencies;
+	type MaxRoleNameLength = MaxRoleNameLength;
+	type MaxRolesPerMetaverse = MaxRolesPerMetaverse;
+	type MaxMetadataHistory = MaxMetadataHistory;
+	type AuctionHandler = Auction;
 }
 
 parameter_types! {
@@ -508,6 +520,11 @@ parameter_types! {
 	pub const MinBlocksPerLandIssuanceRound: u32 = 20;
 	pub const MinimumStake: Balance = 100 * DOLLARS;
 	pub const RewardPaymentDelay: u32 = 1;
+	pub const MaxEstatePayoutsPerBlock: u32 = 10;
+	pub const LeaseGracePeriod: BlockNumber = 1 * DAYS;
+	pub const MaxContentUriLength: u32 = 256;
+	pub const LandTaxGracePeriod: BlockNumber = 1 * DAYS;
+	pub const MaxLandSaleZones: u32 = 20;
 }
 
 impl estate::Config for Runtime {
@@ -522,15 +539,36 @@ impl estate::Config for Runtime {
 	type WeightInfo = weights::module_estate::WeightInfo<Runtime>;
 	type MinimumStake = MinimumStake;
 	type RewardPaymentDelay = RewardPaymentDelay;
+	type MaxEstatePayoutsPerBlock = MaxEstatePayoutsPerBlock;
+	type FungibleTokenCurrency = Tokens;
+	type LeaseGracePeriod = LeaseGracePeriod;
+	type MaxContentUriLength = MaxContentUriLength;
+	type MetaverseFundTreasury = MetaverseNetworkTreasuryPalletId;
+	type LandTaxGracePeriod = LandTaxGracePeriod;
+	type MaxLandSaleZones = MaxLandSaleZones;
 }
 
 parameter_types! {
 	pub const AuctionTimeToClose: u32 = 100; // Default 100800 Blocks
 	pub const ContinuumSessionDuration: BlockNumber = 100; // Default 43200 Blocks
 	pub const SpotAuctionChillingDuration: BlockNumber = 100; // Default 43200 Blocks
+	pub const NeighbourBanThreshold: Perbill = Perbill::from_percent(50); // Nay share that bans a bidder
+	pub const SpotLeaseSessions: u32 = 30; // Sessions a continuum spot lease lasts
+	pub const MaxSpotLeaseExpiriesPerBlock: u32 = 50;
+	pub const MaxSpotReleasesPerBlock: u32 = 50;
+	pub const SpotPriceAdjustment: Perbill = Perbill::from_parts(125_000_000); // 12.5% per sale off target
+	pub const TargetSpotSalesPerSession: u32 = 5;
+	pub const CentralSpotPremium: u32 = 9; // Origin spot costs ten times the edge
+	pub const NeighbourSpotPremium: Perbill = Perbill::from_percent(10);
+	pub const MinimumSpotPrice: Balance = 1 * DOLLARS;
+	pub const SpotRelocationFee: Balance = 1 * DOLLARS;
+	pub const EOIDeposit: Balance = 1 * DOLLARS;
+	pub const MaxPortalMetadata: u32 = 1024;
 	pub const MinimumAuctionDuration: BlockNumber = 30; // Minimum duration is 300 blocks
 	pub const RoyaltyFee: u16 = 10; // Loyalty fee 0.1%
 	pub const MaxFinality: u32 = 100; // Maximum finalize auctions per block
+	pub const MaxSettlementAttempts: u32 = 3; // Retries before a failed settlement is refunded
+	pub const MaxSalesHistory: u32 = 20; // Sales kept per item and per collection
 }
 
 impl auction::Config for Runtime {
@@ -543,9 +581,14 @@ impl auction::Config for Runtime {
 	type MetaverseInfoSource = Metaverse;
 	type MinimumAuctionDuration = MinimumAuctionDuration;
 	type EstateHandler = Estate;
+	type UndeployedLandBlockHandler = Estate;
 	type RoyaltyFee = RoyaltyFee;
 	type MaxFinality = MaxFinality;
+	type MaxSettlementAttempts = MaxSettlementAttempts;
+	type MaxSalesHistory = MaxSalesHistory;
+	type RoundHandler = Mining;
 	type NFTHandler = Nft;
+	type WeightInfo = weights::module_auction::WeightInfo<Runtime>;
 }
 
 impl continuum::Config for Runtime {
@@ -558,6 +601,18 @@ impl continuum::Config for Runtime {
 	type ContinuumTreasury = MetaverseNetworkTreasuryPalletId;
 	type Currency = Balances;
 	type MetaverseInfoSource = Metaverse;
+	type NeighbourBanThreshold = NeighbourBanThreshold;
+	type SpotLeaseSessions = SpotLeaseSessions;
+	type MaxSpotLeaseExpiriesPerBlock = MaxSpotLeaseExpiriesPerBlock;
+	type MaxSpotReleasesPerBlock = MaxSpotReleasesPerBlock;
+	type SpotPriceAdjustment = SpotPriceAdjustment;
+	type TargetSpotSalesPerSession = TargetSpotSalesPerSession;
+	type CentralSpotPremium = CentralSpotPremium;
+	type NeighbourSpotPremium = NeighbourSpotPremium;
+	type MinimumSpotPrice = MinimumSpotPrice;
+	type SpotRelocationFee = SpotRelocationFee;
+	type EOIDeposit = EOIDeposit;
+	type MaxPortalMetadata = MaxPortalMetadata;
 }
 
 impl tokenization::Config for Runtime {
@@ -1382,6 +1437,76 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl auction_runtime_api::AuctionApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+		fn item_sales_history(item_id: ItemId) -> Vec<SaleRecord<AccountId, Balance, BlockNumber>> {
+			Auction::item_sales_history(item_id)
+		}
+
+		fn class_sales_history(class_id: ClassId) -> Vec<SaleRecord<AccountId, Balance, BlockNumber>> {
+			Auction::class_sales_history(class_id)
+		}
+
+		fn last_sale_price(item_id: ItemId) -> Option<Balance> {
+			<Auction as SalesHistory<Balance>>::last_sale_price(item_id)
+		}
+
+		fn class_last_sale_price(class_id: ClassId) -> Option<Balance> {
+			<Auction as SalesHistory<Balance>>::class_last_sale_price(class_id)
+		}
+
+		fn class_volume(class_id: ClassId, round: RoundIndex) -> Balance {
+			<Auction as SalesHistory<Balance>>::class_volume(class_id, round)
+		}
+	}
+
+	impl estate_runtime_api::EstateApi<Block, AccountId> for Runtime {
+		fn land_unit_owners_in_area(
+			metaverse_id: MetaverseId,
+			bottom_left: (i32, i32),
+			top_right: (i32, i32),
+		) -> Vec<LandUnitOwnership<AccountId>> {
+			Estate::land_unit_owners_in_area(metaverse_id, bottom_left, top_right)
+		}
+
+		fn estate_by_coordinate(metaverse_id: MetaverseId, coordinate: (i32, i32)) -> Option<(EstateId, EstateInfo)> {
+			Estate::estate_by_coordinate(metaverse_id, coordinate)
+		}
+
+		fn estates_by_owner(owner: AccountId) -> Vec<(EstateId, EstateInfo)> {
+			Estate::estates_by_owner(&owner)
+		}
+
+		fn free_coordinates(
+			metaverse_id: MetaverseId,
+			bottom_left: (i32, i32),
+			top_right: (i32, i32),
+			limit: u32,
+		) -> Vec<(i32, i32)> {
+			Estate::free_coordinates(metaverse_id, bottom_left, top_right, limit)
+		}
+
+		fn neighbours(asset: LandAsset) -> Vec<LandUnitOwnership<AccountId>> {
+			Estate::neighbours(asset)
+		}
+	}
+
+	impl continuum_runtime_api::ContinuumApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+		fn spot_price(coordinate: (i32, i32)) -> Balance {
+			Continuum::spot_price(coordinate)
+		}
+
+		fn portals() -> Vec<ContinuumPortal> {
+			Continuum::portals()
+		}
+
+		fn map_region(
+			bottom_left: (i32, i32),
+			top_right: (i32, i32),
+		) -> Vec<ContinuumSpotInfo<AccountId, Balance, BlockNumber>> {
+			Continuum::map_region(bottom_left, top_right)
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
 		fn query_info(
 			uxt: <Block as BlockT>::Extrinsic,
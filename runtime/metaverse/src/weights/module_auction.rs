@@ -30,4 +30,9 @@ impl<T: frame_system::Config> auction::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(9 as Weight))
 			.saturating_add(T::DbWeight::get().writes(10 as Weight))
 	}
+	fn finalize_auction() -> Weight {
+		(254_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(13 as Weight))
+	}
 }
@@ -16,7 +16,7 @@ use sp_std::{
 	vec::Vec,
 };
 
-use primitives::{AssetId, AuctionId, ClassId, FungibleTokenId, ItemId, MetaverseId, TokenId};
+use primitives::{AssetId, AuctionId, ClassId, FungibleTokenId, ItemId, MetaverseId, RoundIndex, TokenId};
 
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 pub enum Change<Value> {
@@ -58,6 +58,27 @@ pub struct AuctionItem<AccountId, BlockNumber, Balance> {
 	pub currency_id: FungibleTokenId,
 }
 
+/// Escrowed offer against any token of a collection
+#[cfg_attr(feature = "std", derive(PartialEq, Eq))]
+#[derive(Encode, Decode, Clone, RuntimeDebug, TypeInfo)]
+pub struct CollectionOffer<Balance> {
+	/// Price paid for each token
+	pub price_per_token: Balance,
+	/// Number of tokens still wanted
+	pub quantity: u32,
+}
+
+/// Settled sale of an item
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct SaleRecord<AccountId, Balance, BlockNumber> {
+	pub item_id: ItemId,
+	pub seller: AccountId,
+	pub buyer: AccountId,
+	pub price: Balance,
+	/// Block the sale settled in
+	pub block_number: BlockNumber,
+}
+
 /// Auction info.
 #[cfg_attr(feature = "std", derive(PartialEq, Eq))]
 #[derive(Encode, Decode, RuntimeDebug, TypeInfo)]
@@ -128,6 +149,16 @@ pub trait CheckAuctionItemHandler {
 	fn check_item_in_auction(item_id: ItemId) -> bool;
 }
 
+/// Price reference from settled sales
+pub trait SalesHistory<Balance> {
+	/// Price of the last sale of this item
+	fn last_sale_price(item_id: ItemId) -> Option<Balance>;
+	/// Price of the last sale of any token in this collection
+	fn class_last_sale_price(class_id: ClassId) -> Option<Balance>;
+	/// Total sales volume of this collection during a round
+	fn class_volume(class_id: ClassId, round: RoundIndex) -> Balance;
+}
+
 /// The result of bid handling.
 pub struct OnNewBidResult<BlockNumber> {
 	/// Indicates if the bid was accepted
@@ -32,6 +32,19 @@ pub struct MetaverseInfo<AccountId> {
 	pub is_frozen: bool,
 }
 
+/// Role a metaverse owner can grant to the members of their team
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Ord, PartialOrd, RuntimeDebug, TypeInfo)]
+pub enum MetaverseRole {
+	/// Manages the metaverse on behalf of the owner, holds every built-in role
+	Admin,
+	/// Moderates the metaverse community
+	Moderator,
+	/// Builds on the land held by the metaverse owner
+	Builder,
+	/// Role defined by the metaverse, by name
+	Custom(Vec<u8>),
+}
+
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 pub struct MetaverseFund<AccountId, Balance> {
 	/// The fund account of this metaverse
@@ -47,12 +60,16 @@ pub struct MetaverseFund<AccountId, Balance> {
 pub trait MetaverseTrait<AccountId> {
 	/// Check the ownership of this metaverse
 	fn check_ownership(who: &AccountId, metaverse_id: &MetaverseId) -> bool;
+	/// Check if the account holds the role in this metaverse, the owner holds every role
+	fn check_role(who: &AccountId, metaverse_id: &MetaverseId, role: &MetaverseRole) -> bool;
 	/// Get the detail of this metaverse
 	fn get_metaverse(metaverse_id: MetaverseId) -> Option<MetaverseInfo<AccountId>>;
 	/// Get metaverse token detail
 	fn get_metaverse_token(metaverse_id: MetaverseId) -> Option<FungibleTokenId>;
 	/// Update metaverse token, this only use once per metaverse
 	fn update_metaverse_token(metaverse_id: MetaverseId, currency_id: FungibleTokenId) -> Result<(), DispatchError>;
+	/// Transfer metaverse ownership
+	fn transfer_metaverse(from: &AccountId, to: &AccountId, metaverse_id: MetaverseId) -> DispatchResult;
 }
 
 pub trait MetaverseLandTrait<AccountId> {
@@ -83,6 +100,12 @@ pub trait UndeployedLandBlocksTrait<AccountId> {
 	fn freeze_undeployed_land_block(
 		undeployed_land_block_id: UndeployedLandBlockId,
 	) -> Result<UndeployedLandBlockId, DispatchError>;
+
+	/// Check if `who` owns this undeployed land block and is able to transfer it
+	fn check_undeployed_land_block(
+		who: &AccountId,
+		undeployed_land_block_id: UndeployedLandBlockId,
+	) -> Result<bool, DispatchError>;
 }
 
 pub trait NFTTrait<AccountId> {
@@ -105,6 +128,8 @@ pub trait NFTTrait<AccountId> {
 		collection_id: GroupCollectionId,
 		class_id: Self::ClassId,
 	) -> Result<bool, DispatchError>;
+	/// Check if the class exists
+	fn check_class_exists(class_id: &Self::ClassId) -> bool;
 	/// Check if item is on listing
 	fn check_item_on_listing(class_id: Self::ClassId, token_id: Self::TokenId) -> Result<bool, DispatchError>;
 	/// transfer nft